@@ -0,0 +1,295 @@
+// Derive macros for `cif::FromDataBlock` and `cif::FromLoop`.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Expr, Fields, LitStr, Type};
+
+/// Reads a struct from the single data items of a data block.
+///
+/// Field attributes in `#[cif(...)]`:
+/// - `tag = "_name"`: data name, `_<field name>` by default
+/// - `alias = "_name"`: fallback data name, can be repeated
+/// - `optional`: `Option<T>` field that is `None` if the item is missing, `?` or `.`.
+///   `Option<T>` fields are optional without the attribute as well.
+/// - `default`, `default = "path"` or `default = expression`: value if the item is missing, `?`
+///   or `.`, from `Default::default()`, a call of the function `path` or the expression.
+///   `Option<T>` fields cannot have a default.
+/// - `uncertainty`: `Option<f64>` field with the standard uncertainty of the item
+#[proc_macro_derive(FromDataBlock, attributes(cif))]
+pub fn derive_from_data_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input, Target::DataBlock)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Reads the rows of a loop as a `Vec` of structs. Takes the same field attributes as
+/// `FromDataBlock`, which apply to the loop columns.
+#[proc_macro_derive(FromLoop, attributes(cif))]
+pub fn derive_from_loop(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input, Target::Loop)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Clone, Copy)]
+enum Target {
+    DataBlock,
+    Loop,
+}
+
+enum Kind {
+    Required,
+    Optional(Type),
+    Default(Option<Expr>),
+    Uncertainty,
+}
+
+struct Field {
+    ident: syn::Ident,
+    ty: Type,
+    tags: Vec<String>,
+    kind: Kind,
+}
+
+fn expand(input: &DeriveInput, target: Target) -> syn::Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "only structs can be read from CIF data",
+        ));
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(
+            input.span(),
+            "only structs with named fields can be read from CIF data",
+        ));
+    };
+
+    let fields = fields
+        .named
+        .iter()
+        .map(parse_field)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match target {
+        Target::DataBlock => {
+            let values = fields.iter().map(|field| {
+                let ident = &field.ident;
+                let tags = &field.tags;
+                let parse = parse_value(field);
+
+                quote! {
+                    #ident: {
+                        let value = ::cif::__private::first(data_block, &[#(#tags),*]);
+                        #parse
+                    }
+                }
+            });
+
+            quote! {
+                impl #impl_generics ::cif::FromDataBlock for #name #ty_generics #where_clause {
+                    fn from_data_block(
+                        data_block: &::cif::DataBlock,
+                    ) -> ::cif::__private::anyhow::Result<Self> {
+                        Ok(Self { #(#values),* })
+                    }
+                }
+            }
+        }
+        Target::Loop => {
+            let columns = fields
+                .iter()
+                .map(|field| format_ident!("column_{}", field.ident))
+                .collect::<Vec<_>>();
+
+            let all_tags = fields.iter().map(|field| {
+                let tags = &field.tags;
+
+                quote! { &[#(#tags),*] }
+            });
+
+            let lookups = fields.iter().zip(&columns).map(|(field, column)| {
+                let tags = &field.tags;
+
+                quote! {
+                    let #column = ::cif::__private::column(data_block, &[#(#tags),*]);
+                }
+            });
+
+            let values = fields.iter().zip(&columns).map(|(field, column)| {
+                let ident = &field.ident;
+                let parse = parse_value(field);
+
+                quote! {
+                    #ident: {
+                        let value = #column.and_then(|(tag, values)| {
+                            values.get(index).map(|value| (tag, value))
+                        });
+                        #parse
+                    }
+                }
+            });
+
+            quote! {
+                impl #impl_generics ::cif::FromLoop for #name #ty_generics #where_clause {
+                    fn from_loop(
+                        data_block: &::cif::DataBlock,
+                    ) -> ::cif::__private::anyhow::Result<::std::vec::Vec<Self>> {
+                        #(#lookups)*
+
+                        let rows = ::cif::__private::row_count(data_block, &[#(#all_tags),*])?;
+
+                        (0..rows)
+                            .map(|index| Ok(Self { #(#values),* }))
+                            .collect()
+                    }
+                }
+            }
+        }
+    };
+
+    Ok(body)
+}
+
+/// Expression that parses `value: Option<(&str, &CifValue)>` into the field
+fn parse_value(field: &Field) -> TokenStream {
+    let ty = &field.ty;
+    let tags = &field.tags;
+
+    match &field.kind {
+        Kind::Required => quote! {
+            ::cif::__private::required::<#ty>(value, &[#(#tags),*])?
+        },
+        Kind::Optional(inner) => quote! {
+            ::cif::__private::optional::<#inner>(value)?
+        },
+        Kind::Default(Some(default)) => quote! {
+            ::cif::__private::optional::<#ty>(value)?.unwrap_or_else(|| #default)
+        },
+        Kind::Default(None) => quote! {
+            ::cif::__private::optional::<#ty>(value)?.unwrap_or_default()
+        },
+        Kind::Uncertainty => quote! {
+            ::cif::__private::uncertainty(value)?
+        },
+    }
+}
+
+fn parse_field(field: &syn::Field) -> syn::Result<Field> {
+    let ident = field.ident.clone().expect("named field");
+
+    let mut tag = None;
+    let mut aliases = Vec::new();
+    let mut optional = false;
+    let mut default = None;
+    let mut uncertainty = false;
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cif"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("tag") {
+                tag = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("alias") {
+                aliases.push(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("optional") {
+                optional = true;
+            } else if meta.path.is_ident("uncertainty") {
+                uncertainty = true;
+            } else if meta.path.is_ident("default") {
+                default = Some(match meta.input.peek(syn::Token![=]) {
+                    true => Some(default_expr(meta.value()?.parse::<Expr>()?)?),
+                    false => None,
+                });
+            } else {
+                return Err(meta.error("unknown `cif` attribute"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    let tag = tag.unwrap_or_else(|| format!("_{}", ident.to_string().trim_start_matches("r#")));
+
+    let option_inner = option_inner(&field.ty);
+
+    let kind = match (uncertainty, default, optional, option_inner) {
+        (true, None, _, Some(_)) => Kind::Uncertainty,
+        (true, _, _, _) => {
+            return Err(syn::Error::new(
+                field.ty.span(),
+                "`uncertainty` needs an `Option<f64>` field without a default",
+            ))
+        }
+        (false, Some(_), _, Some(_)) => {
+            return Err(syn::Error::new(
+                field.ty.span(),
+                "`default` needs a field that is not an `Option<T>`",
+            ))
+        }
+        (false, Some(default), _, None) => Kind::Default(default),
+        (false, None, _, Some(inner)) => Kind::Optional(inner),
+        (false, None, true, None) => {
+            return Err(syn::Error::new(
+                field.ty.span(),
+                "`optional` needs an `Option<T>` field",
+            ))
+        }
+        (false, None, false, None) => Kind::Required,
+    };
+
+    Ok(Field {
+        ident,
+        ty: field.ty.clone(),
+        tags: std::iter::once(tag).chain(aliases).collect(),
+        kind,
+    })
+}
+
+/// Call of the function `path` for `default = "path"` like in serde, or a plain expression like
+/// `default = 1.0`
+fn default_expr(expr: Expr) -> syn::Result<Expr> {
+    match &expr {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(text),
+            ..
+        }) => {
+            let path = text.parse::<syn::ExprPath>()?;
+
+            Ok(syn::parse_quote!(#path()))
+        }
+        _ => Ok(expr),
+    }
+}
+
+/// `T` of an `Option<T>` field
+fn option_inner(ty: &Type) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+
+    match arguments.args.first()? {
+        syn::GenericArgument::Type(inner) => Some(inner.clone()),
+        _ => None,
+    }
+}
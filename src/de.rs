@@ -0,0 +1,800 @@
+// Serde deserializer for data blocks. A data block is a map from data names without the leading
+// `_` to their values. A loop is a sequence of rows under the category prefix that its data names
+// share (`atom_site` for `_atom_site_label`, `_atom_site_fract_x`, ...), and every row is a map from
+// the rest of the data names (`label`, `fract_x`, ...) to the values. A single looped data name is
+// also a sequence of its values.
+
+use serde::de::{
+    self, value::StrDeserializer, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+
+use crate::{
+    measured::Measured,
+    parser::{names_match, try_read_cif, DataBlock, Loop},
+    value::CifValue,
+};
+
+/// Error of [`from_str`] and [`crate::to_string`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error(String);
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+/// Deserializes `T` from the first data block of `text`
+pub fn from_str<T: de::DeserializeOwned>(text: &str) -> Result<T, Error> {
+    from_bytes(text.as_bytes())
+}
+
+/// Deserializes `T` from the first data block of `bytes`
+pub fn from_bytes<T: de::DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    let cif = try_read_cif(bytes).map_err(|error| Error(error.to_string()))?;
+
+    let (_, data_block) = cif
+        .first_key_value()
+        .ok_or_else(|| Error("No data block found".to_string()))?;
+
+    from_data_block(data_block)
+}
+
+pub fn from_data_block<'de, T: de::Deserialize<'de>>(
+    data_block: &'de DataBlock,
+) -> Result<T, Error> {
+    T::deserialize(DataBlockDeserializer { data_block })
+}
+
+/// Value of a key of a data block
+#[derive(Clone, Copy)]
+enum Entry<'de> {
+    Value(&'de CifValue),
+    Column(&'de [CifValue]),
+    Rows(Loop<'de>, usize),
+}
+
+struct DataBlockDeserializer<'de> {
+    data_block: &'de DataBlock,
+}
+
+impl<'de> DataBlockDeserializer<'de> {
+    /// Every item, single looped data name and loop category of the data block
+    fn entries(&self) -> Vec<(String, Entry<'de>)> {
+        let mut entries = Vec::new();
+
+        for (name, values) in self.data_block.iter() {
+            if !self.data_block.is_looped(name) {
+                if let Some(value) = values.first() {
+                    entries.push((
+                        name.trim_start_matches('_').to_string(),
+                        Entry::Value(value),
+                    ));
+                }
+            }
+        }
+
+        for loop_ in self.data_block.loops() {
+            match loop_.names() {
+                [name] => entries.push((
+                    name.trim_start_matches('_').to_string(),
+                    Entry::Column(loop_.column(name).unwrap_or_default()),
+                )),
+                _ => {
+                    let prefix = category_prefix(loop_.names());
+
+                    if let Some(category) = category(prefix) {
+                        entries.push((category.to_string(), Entry::Rows(loop_, prefix.len())));
+                    }
+                }
+            }
+        }
+
+        entries
+    }
+
+    /// Entries for the fields of a struct, looked up without regard to case
+    fn field_entries(&self, fields: &'static [&'static str]) -> Vec<(String, Entry<'de>)> {
+        fields
+            .iter()
+            .filter_map(|field| {
+                let name = match field.starts_with('_') {
+                    true => field.to_string(),
+                    false => format!("_{}", field),
+                };
+
+                if let Some(values) = self.data_block.get(&name) {
+                    let entry = match self.data_block.find_loop(&name) {
+                        Some(_) => Entry::Column(values),
+                        None => Entry::Value(values.first()?),
+                    };
+
+                    return Some((field.to_string(), entry));
+                }
+
+                // a loop whose data names all start with `_<field>_`
+                let prefix = format!("{}_", name);
+
+                self.data_block
+                    .loops()
+                    .find(|loop_| {
+                        loop_.names().iter().all(|name| {
+                            name.len() > prefix.len()
+                                && name.is_char_boundary(prefix.len())
+                                && names_match(&name[..prefix.len()], &prefix)
+                        })
+                    })
+                    .map(|loop_| (field.to_string(), Entry::Rows(loop_, prefix.len())))
+            })
+            .collect()
+    }
+}
+
+/// Longest common prefix of the data names that ends with `_`, e.g. `_atom_site_`
+fn category_prefix(names: &[String]) -> &str {
+    let Some(first) = names.first() else {
+        return "";
+    };
+
+    let mut length = first.len();
+
+    for name in names {
+        length = first
+            .bytes()
+            .zip(name.bytes())
+            .take(length)
+            .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+            .count();
+    }
+
+    match first[..length].rfind('_') {
+        Some(end) if first.is_char_boundary(end + 1) => &first[..end + 1],
+        _ => "",
+    }
+}
+
+fn category(prefix: &str) -> Option<&str> {
+    Some(prefix.trim_matches('_')).filter(|category| !category.is_empty())
+}
+
+impl<'de> de::Deserializer<'de> for DataBlockDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(EntriesAccess::new(self.entries()))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_map(EntriesAccess::new(self.field_entries(fields)))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+struct EntriesAccess<'de> {
+    entries: std::vec::IntoIter<(String, Entry<'de>)>,
+    value: Option<Entry<'de>>,
+}
+
+impl<'de> EntriesAccess<'de> {
+    fn new(entries: Vec<(String, Entry<'de>)>) -> Self {
+        Self {
+            entries: entries.into_iter(),
+            value: None,
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for EntriesAccess<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+
+        self.value = Some(value);
+
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        match self.value.take() {
+            Some(Entry::Value(value)) => seed.deserialize(ValueDeserializer { value }),
+            Some(Entry::Column(values)) => {
+                seed.deserialize(SeqDeserializer(ValuesAccess(values.iter())))
+            }
+            Some(Entry::Rows(loop_, prefix_length)) => {
+                seed.deserialize(SeqDeserializer(RowsAccess {
+                    loop_,
+                    prefix_length,
+                    index: 0,
+                }))
+            }
+            None => Err(de::Error::custom("value requested before its key")),
+        }
+    }
+}
+
+/// Deserializer of anything with [`SeqAccess`]
+struct SeqDeserializer<A>(A);
+
+impl<'de, A: SeqAccess<'de, Error = Error>> de::Deserializer<'de> for SeqDeserializer<A> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(self.0)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct ValuesAccess<I>(I);
+
+impl<'de, I: Iterator<Item = &'de CifValue>> SeqAccess<'de> for ValuesAccess<I> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.0
+            .next()
+            .map(|value| seed.deserialize(ValueDeserializer { value }))
+            .transpose()
+    }
+}
+
+struct RowsAccess<'de> {
+    loop_: Loop<'de>,
+    prefix_length: usize,
+    index: usize,
+}
+
+impl<'de> SeqAccess<'de> for RowsAccess<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.index >= self.loop_.len() {
+            return Ok(None);
+        }
+
+        let row = RowDeserializer {
+            loop_: self.loop_,
+            prefix_length: self.prefix_length,
+            index: self.index,
+        };
+
+        self.index += 1;
+
+        seed.deserialize(row).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.loop_.len().saturating_sub(self.index))
+    }
+}
+
+/// Row of a loop, as a map from the data names without their category prefix to the values
+struct RowDeserializer<'de> {
+    loop_: Loop<'de>,
+    prefix_length: usize,
+    index: usize,
+}
+
+impl<'de> RowDeserializer<'de> {
+    fn value(&self, name: &str) -> &'de CifValue {
+        // values missing in incomplete loops are unknown
+        self.loop_
+            .column(name)
+            .and_then(|column| column.get(self.index))
+            .unwrap_or(&CifValue::Unknown)
+    }
+}
+
+impl<'de> de::Deserializer<'de> for RowDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let entries = self
+            .loop_
+            .names()
+            .iter()
+            .map(|name| {
+                (
+                    name[self.prefix_length..].to_string(),
+                    Entry::Value(self.value(name)),
+                )
+            })
+            .collect();
+
+        visitor.visit_map(EntriesAccess::new(entries))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let prefix = &self.loop_.names()[0][..self.prefix_length];
+
+        let entries = fields
+            .iter()
+            .filter_map(|field| {
+                self.loop_
+                    .names()
+                    .iter()
+                    .find(|name| {
+                        names_match(name, &format!("{}{}", prefix, field))
+                            || names_match(name, &format!("_{}", field.trim_start_matches('_')))
+                    })
+                    .map(|name| (field.to_string(), Entry::Value(self.value(name))))
+            })
+            .collect();
+
+        visitor.visit_map(EntriesAccess::new(entries))
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let values = self
+            .loop_
+            .names()
+            .iter()
+            .map(|name| self.value(name))
+            .collect::<Vec<_>>();
+
+        visitor.visit_seq(ValuesAccess(values.into_iter()))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct map enum identifier ignored_any
+    }
+}
+
+/// Deserializer of a single value. `?` and `.` are `None`, and numbers can be read as `f64` without
+/// their standard uncertainty or as [`Measured`] / `(f64, Option<f64>)` with it.
+struct ValueDeserializer<'de> {
+    value: &'de CifValue,
+}
+
+impl<'de> ValueDeserializer<'de> {
+    fn parse<T: std::str::FromStr>(&self) -> Result<T, Error>
+    where
+        <T as std::str::FromStr>::Err: std::error::Error + Send + Sync + 'static,
+    {
+        self.value.parse::<T>().map_err(|_| {
+            Error(format!(
+                "Failed to parse `{}` as {}",
+                self.value,
+                std::any::type_name::<T>()
+            ))
+        })
+    }
+
+    fn measured(&self) -> Result<Measured, Error> {
+        self.value
+            .as_measured()
+            .ok_or_else(|| Error(format!("`{}` is not a number", self.value)))
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            CifValue::Unknown | CifValue::Inapplicable => visitor.visit_none(),
+            CifValue::Numeric(text) => match (text.parse::<i64>(), text.parse::<u64>()) {
+                (Ok(integer), _) => visitor.visit_i64(integer),
+                (_, Ok(integer)) => visitor.visit_u64(integer),
+                _ => visitor.visit_f64(self.measured()?.value),
+            },
+            CifValue::Text(text) => visitor.visit_borrowed_str(text),
+            CifValue::List(list) => visitor.visit_seq(ValuesAccess(list.iter())),
+            CifValue::Table(table) => visitor.visit_map(TableAccess {
+                entries: table.iter(),
+                value: None,
+            }),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value.as_str() {
+            Some(text) => visitor.visit_borrowed_str(text),
+            None => Err(Error(format!("Expected text but found `{}`", self.value))),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value.as_str() {
+            Some(text) => visitor.visit_borrowed_bytes(text.as_bytes()),
+            None => Err(Error(format!("Expected text but found `{}`", self.value))),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value.is_null() {
+            true => visitor.visit_none(),
+            false => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            CifValue::List(list) => visitor.visit_seq(ValuesAccess(list.iter())),
+            _ => Err(Error(format!("Expected a list but found `{}`", self.value))),
+        }
+    }
+
+    /// A number as `(value, su)`, or a list
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value {
+            CifValue::List(_) => self.deserialize_seq(visitor),
+            _ => visitor.visit_seq(MeasuredAccess {
+                measured: self.measured()?,
+                index: 0,
+            }),
+        }
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.value {
+            CifValue::Table(table) => visitor.visit_map(TableAccess {
+                entries: table.iter(),
+                value: None,
+            }),
+            _ => Err(Error(format!(
+                "Expected a table but found `{}`",
+                self.value
+            ))),
+        }
+    }
+
+    /// A table, or a number as [`Measured`]
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value {
+            CifValue::Table(_) => self.deserialize_map(visitor),
+            _ if name == crate::measured::SERDE_NAME => visitor.visit_map(MeasuredAccess {
+                measured: self.measured()?,
+                index: 0,
+            }),
+            _ => self.deserialize_map(visitor),
+        }
+    }
+
+    /// Unit variants from text
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value.as_str() {
+            Some(text) => {
+                visitor.visit_enum::<StrDeserializer<'_, Error>>(text.into_deserializer())
+            }
+            None => Err(Error(format!(
+                "Expected a variant but found `{}`",
+                self.value
+            ))),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+}
+
+struct TableAccess<'de> {
+    entries: indexmap::map::Iter<'de, String, CifValue>,
+    value: Option<&'de CifValue>,
+}
+
+impl<'de> MapAccess<'de> for TableAccess<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+
+        self.value = Some(value);
+
+        seed.deserialize(key.as_str().into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(ValueDeserializer { value }),
+            None => Err(de::Error::custom("value requested before its key")),
+        }
+    }
+}
+
+/// A number as the sequence `value, su` or the map `{value, su}`
+struct MeasuredAccess {
+    measured: Measured,
+    index: usize,
+}
+
+impl<'de> SeqAccess<'de> for MeasuredAccess {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.index += 1;
+
+        match self.index {
+            1 => seed
+                .deserialize(self.measured.value.into_deserializer())
+                .map(Some),
+            2 => seed
+                .deserialize(UncertaintyDeserializer(self.measured.su))
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for MeasuredAccess {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let key = match self.index {
+            0 => "value",
+            1 => "su",
+            _ => return Ok(None),
+        };
+
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        self.next_element_seed(seed)?
+            .ok_or_else(|| de::Error::custom("value requested before its key"))
+    }
+}
+
+struct UncertaintyDeserializer(Option<f64>);
+
+impl<'de> de::Deserializer<'de> for UncertaintyDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Some(su) => visitor.visit_f64(su),
+            None => visitor.visit_none(),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Some(su) => visitor.visit_some(su.into_deserializer()),
+            None => visitor.visit_none(),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+
+    use crate::measured::Measured;
+
+    use super::from_str;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Shape {
+        Point,
+        Sphere,
+    }
+
+    #[test]
+    fn test_values() {
+        #[derive(Debug, Deserialize)]
+        struct Block {
+            a: f64,
+            a_pair: (f64, Option<f64>),
+            a_measured: Measured,
+            b: Option<f64>,
+            b_pair: (f64, Option<f64>),
+            b_measured: Option<Measured>,
+            c: Option<Measured>,
+            d: Option<String>,
+            e: Shape,
+            f: (i32, String),
+        }
+
+        let block: Block = from_str(
+            "#\\#CIF_2.0
+data_x
+_a 1.5(5)
+_a_pair 1.5(5)
+_a_measured 1.5(5)
+_b 2
+_b_pair 2
+_b_measured 2
+_c ?
+_d .
+_e Sphere
+_f [1 two]
+",
+        )
+        .unwrap();
+
+        assert_eq!(block.a, 1.5);
+        assert_eq!(block.a_pair, (1.5, Some(0.5)));
+        assert_eq!(block.a_measured, Measured::new(1.5, Some(0.5)));
+        assert_eq!(block.b, Some(2.0));
+        assert_eq!(block.b_pair, (2.0, None));
+        assert_eq!(block.b_measured, Some(Measured::new(2.0, None)));
+        assert_eq!(block.c, None);
+        assert_eq!(block.d, None);
+        assert_eq!(block.e, Shape::Sphere);
+        assert_eq!(block.f, (1, "two".to_string()));
+    }
+
+    #[test]
+    fn test_errors() {
+        #[derive(Debug, Deserialize)]
+        struct Value<T> {
+            #[allow(dead_code)]
+            a: T,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Point {
+            #[allow(dead_code)]
+            x: f64,
+        }
+
+        assert!(from_str::<Value<Measured>>("data_x\n_a ?\n").is_err());
+        assert!(from_str::<Value<Measured>>("data_x\n_a text\n").is_err());
+        assert!(from_str::<Value<f64>>("data_x\n_a .\n").is_err());
+        assert!(from_str::<Value<Shape>>("data_x\n_a 1\n").is_err());
+        assert!(from_str::<Value<Shape>>("data_x\n_a Cube\n").is_err());
+
+        // only `Measured` is read from a number, other structs need a table
+        assert!(from_str::<Value<Point>>("data_x\n_a 1.5(3)\n").is_err());
+    }
+}
@@ -0,0 +1,639 @@
+// Lossless representation of a CIF file for editing single values. The file is kept as bytes
+// together with the spans of its data blocks, items and loops, so everything that is not edited
+// (whitespace, comments, quoting) is written back unchanged.
+
+use std::ops::Range;
+
+use crate::{
+    error::CifError,
+    lexer::{CifVersion, Lexeme, Lexer, Token},
+    parser::{names_match, read_cif, Cif, Parser},
+    value::CifValue,
+    writer::{format_value, Formatted, WriteOptions},
+};
+
+/// CIF file that can be edited without changing the formatting of the unedited parts
+#[derive(Debug, Clone)]
+pub struct Document {
+    bytes: Vec<u8>,
+    version: CifVersion,
+    blocks: Vec<BlockNode>,
+}
+
+#[derive(Debug, Clone)]
+struct BlockNode {
+    name: String,
+    /// End of the last item or loop, or of the `data_` header
+    end: usize,
+    items: Vec<ItemNode>,
+}
+
+#[derive(Debug, Clone)]
+enum ItemNode {
+    Item {
+        name: String,
+        tag: Range<usize>,
+        value: Range<usize>,
+    },
+    Loop {
+        keyword: Range<usize>,
+        names: Vec<(String, Range<usize>)>,
+        values: Vec<Range<usize>>,
+    },
+}
+
+impl ItemNode {
+    fn end(&self) -> usize {
+        match self {
+            ItemNode::Item { value, .. } => value.end,
+            ItemNode::Loop {
+                keyword,
+                names,
+                values,
+            } => {
+                values
+                    .last()
+                    .or(names.last().map(|(_, tag)| tag))
+                    .unwrap_or(keyword)
+                    .end
+            }
+        }
+    }
+}
+
+impl Document {
+    /// Fails on the first syntax error, since edits of a broken file could end up anywhere
+    pub fn parse(bytes: &[u8]) -> Result<Self, CifError> {
+        Parser::new(bytes).try_parse()?;
+
+        let mut document = Self {
+            bytes: bytes.to_vec(),
+            version: CifVersion::default(),
+            blocks: Vec::new(),
+        };
+
+        document.build();
+
+        Ok(document)
+    }
+
+    pub fn version(&self) -> CifVersion {
+        self.version
+    }
+
+    /// The file with all edits applied
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Parsed data blocks of the edited file
+    pub fn to_cif(&self) -> Cif {
+        read_cif(&self.bytes)
+    }
+
+    /// Replaces the value of a data item that is not in a loop
+    pub fn set_value(
+        &mut self,
+        data_block: &str,
+        name: &str,
+        value: impl Into<CifValue>,
+    ) -> anyhow::Result<()> {
+        let span = match self.find_item(data_block, name)? {
+            ItemNode::Item { value, .. } => value.clone(),
+            ItemNode::Loop { .. } => {
+                return Err(anyhow::anyhow!(
+                    "Data name `{}` is looped, use `set_loop_value`",
+                    name
+                ))
+            }
+        };
+
+        self.replace_value(&value.into(), span);
+
+        Ok(())
+    }
+
+    /// Replaces the value of a loop column in the row `row`
+    pub fn set_loop_value(
+        &mut self,
+        data_block: &str,
+        name: &str,
+        row: usize,
+        value: impl Into<CifValue>,
+    ) -> anyhow::Result<()> {
+        let span = match self.find_item(data_block, name)? {
+            ItemNode::Loop { names, values, .. } => {
+                let column = names
+                    .iter()
+                    .position(|(loop_name, _)| names_match(loop_name, name));
+
+                column
+                    .and_then(|column| values.get(row * names.len() + column))
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("Loop of `{}` has no row {}", name, row))?
+            }
+            ItemNode::Item { .. } => {
+                return Err(anyhow::anyhow!(
+                    "Data name `{}` is not looped, use `set_value`",
+                    name
+                ))
+            }
+        };
+
+        self.replace_value(&value.into(), span);
+
+        Ok(())
+    }
+
+    /// Adds a data item after the last item or loop of the data block
+    pub fn insert_item(
+        &mut self,
+        data_block: &str,
+        name: &str,
+        value: impl Into<CifValue>,
+    ) -> anyhow::Result<()> {
+        if self.find_item(data_block, name).is_ok() {
+            return Err(anyhow::anyhow!(
+                "Data name `{}` already exists in data block `{}`",
+                name,
+                data_block
+            ));
+        }
+
+        let end = self.find_block(data_block)?.end;
+
+        let text = match format_value(&value.into(), &self.write_options()) {
+            Formatted::Inline(value) => format!("\n{} {}", name, value),
+            Formatted::TextField(value) => format!("\n{}\n{}", name, value),
+        };
+
+        let text = self.with_line_break_after(text, end);
+
+        self.splice(end..end, text.as_bytes());
+
+        Ok(())
+    }
+
+    /// Adds a row at the end of the loop that contains `name`
+    pub fn append_loop_row(
+        &mut self,
+        data_block: &str,
+        name: &str,
+        row: Vec<CifValue>,
+    ) -> anyhow::Result<()> {
+        let item = self.find_item(data_block, name)?;
+
+        let ItemNode::Loop { names, .. } = item else {
+            return Err(anyhow::anyhow!("Data name `{}` is not looped", name));
+        };
+
+        if names.len() != row.len() {
+            return Err(anyhow::anyhow!(
+                "Loop has {} data names but the row has {} values",
+                names.len(),
+                row.len()
+            ));
+        }
+
+        let end = item.end();
+
+        let mut text = String::from("\n");
+
+        for value in &row {
+            match format_value(value, &self.write_options()) {
+                Formatted::Inline(value) => {
+                    if !text.ends_with('\n') {
+                        text.push(' ');
+                    }
+
+                    text.push_str(&value);
+                }
+                Formatted::TextField(value) => {
+                    if !text.ends_with('\n') {
+                        text.push('\n');
+                    }
+
+                    text.push_str(&value);
+                    text.push('\n');
+                }
+            }
+        }
+
+        let text = self.with_line_break_after(text.trim_end_matches('\n').to_string(), end);
+
+        self.splice(end..end, text.as_bytes());
+
+        Ok(())
+    }
+
+    /// Removes a data item, or a column of a loop. A loop without columns is removed as a whole.
+    pub fn delete_item(&mut self, data_block: &str, name: &str) -> anyhow::Result<()> {
+        let mut removals = match self.find_item(data_block, name)? {
+            ItemNode::Item { tag, value, .. } => vec![self.whole_lines(tag.start..value.end)],
+            ItemNode::Loop {
+                keyword,
+                names,
+                values,
+            } if names.len() == 1 => {
+                let end = values.last().unwrap_or(&names[0].1).end;
+
+                vec![self.whole_lines(keyword.start..end)]
+            }
+            ItemNode::Loop { names, values, .. } => {
+                let column = names
+                    .iter()
+                    .position(|(loop_name, _)| names_match(loop_name, name))
+                    .expect("the loop was found by this data name");
+
+                let mut removals = vec![self.whole_lines(names[column].1.clone())];
+
+                for value in values.iter().skip(column).step_by(names.len()) {
+                    removals.push(self.with_leading_whitespace(value.clone()));
+                }
+
+                removals
+            }
+        };
+
+        removals.sort_by_key(|removal| removal.start);
+
+        for removal in removals.into_iter().rev() {
+            self.bytes.drain(removal);
+        }
+
+        self.build();
+
+        Ok(())
+    }
+
+    fn write_options(&self) -> WriteOptions {
+        WriteOptions {
+            version: self.version,
+            ..WriteOptions::default()
+        }
+    }
+
+    fn find_block(&self, data_block: &str) -> anyhow::Result<&BlockNode> {
+        self.blocks
+            .iter()
+            .find(|block| names_match(&block.name, data_block))
+            .ok_or_else(|| anyhow::anyhow!("Data block `{}` not found", data_block))
+    }
+
+    fn find_item(&self, data_block: &str, name: &str) -> anyhow::Result<&ItemNode> {
+        self.find_block(data_block)?
+            .items
+            .iter()
+            .find(|item| match item {
+                ItemNode::Item {
+                    name: item_name, ..
+                } => names_match(item_name, name),
+                ItemNode::Loop { names, .. } => names
+                    .iter()
+                    .any(|(loop_name, _)| names_match(loop_name, name)),
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Data name `{}` not found in data block `{}`",
+                    name,
+                    data_block
+                )
+            })
+    }
+
+    /// Replaces the value at `span` by `value`. Text fields have to start and end on their own
+    /// line, so the blanks that padded the old value are removed before them.
+    fn replace_value(&mut self, value: &CifValue, mut span: Range<usize>) {
+        let value = match format_value(value, &self.write_options()) {
+            Formatted::Inline(value) => value,
+            Formatted::TextField(value) => {
+                while span.start > 0 && matches!(self.bytes[span.start - 1], b' ' | b'\t') {
+                    span.start -= 1;
+                }
+
+                let value = match self.is_line_start(span.start) {
+                    true => value,
+                    false => format!("\n{}", value),
+                };
+
+                self.with_line_break_after(value, span.end)
+            }
+        };
+
+        self.splice(span, value.as_bytes());
+    }
+
+    /// Adds a line break to `text` if it ends in a text field that would be followed directly by
+    /// the bytes at `offset`
+    fn with_line_break_after(&self, mut text: String, offset: usize) -> String {
+        let next = self.bytes.get(offset);
+
+        if text.ends_with(';') && next.is_some_and(|byte| !byte.is_ascii_whitespace()) {
+            text.push('\n');
+        }
+
+        text
+    }
+
+    fn is_line_start(&self, offset: usize) -> bool {
+        offset == 0 || matches!(self.bytes[offset - 1], b'\n' | b'\r')
+    }
+
+    /// Extends `span` to the whole lines if nothing else is on them
+    fn whole_lines(&self, span: Range<usize>) -> Range<usize> {
+        let is_blank = |byte: &u8| matches!(byte, b' ' | b'\t');
+
+        let mut start = span.start;
+
+        while start > 0 && is_blank(&self.bytes[start - 1]) {
+            start -= 1;
+        }
+
+        let mut end = span.end;
+
+        while self.bytes.get(end).is_some_and(is_blank) {
+            end += 1;
+        }
+
+        let line_start = self.is_line_start(start);
+
+        match self.bytes.get(end) {
+            Some(b'\r') if line_start && self.bytes.get(end + 1) == Some(&b'\n') => start..end + 2,
+            Some(b'\n' | b'\r') if line_start => start..end + 1,
+            None if line_start => start..end,
+            _ => self.with_leading_whitespace(span),
+        }
+    }
+
+    /// Extends `span` by the whitespace before it, or only by the line break before a text field
+    fn with_leading_whitespace(&self, span: Range<usize>) -> Range<usize> {
+        let mut start = span.start;
+
+        if self.bytes.get(start) == Some(&b';') && self.is_line_start(start) {
+            return start.saturating_sub(1)..span.end;
+        }
+
+        while start > 0 && matches!(self.bytes[start - 1], b' ' | b'\t') {
+            start -= 1;
+        }
+
+        start..span.end
+    }
+
+    fn splice(&mut self, span: Range<usize>, replacement: &[u8]) {
+        self.bytes.splice(span, replacement.iter().copied());
+
+        self.build();
+    }
+
+    /// Finds the spans of the data blocks, items and loops
+    fn build(&mut self) {
+        let lexer = Lexer::new(&self.bytes);
+
+        self.version = lexer.version();
+
+        let base = self.bytes.as_ptr() as usize;
+        let span = |lexeme: &Lexeme| {
+            let start = lexeme.raw.as_ptr() as usize - base;
+
+            start..start + lexeme.raw.len()
+        };
+
+        let mut lexemes = lexer
+            .filter(|lexeme| !matches!(lexeme.token, Token::Comment(_)))
+            .peekable();
+
+        let mut blocks: Vec<BlockNode> = Vec::new();
+        let mut in_save_frame = false;
+
+        while let Some(lexeme) = lexemes.next() {
+            let item = match lexeme.token {
+                Token::DataBlock(name) => {
+                    blocks.push(BlockNode {
+                        name: String::from_utf8_lossy(name).to_string(),
+                        end: span(&lexeme).end,
+                        items: Vec::new(),
+                    });
+
+                    in_save_frame = false;
+
+                    continue;
+                }
+                Token::SaveFrame(name) => {
+                    in_save_frame = !name.is_empty();
+
+                    None
+                }
+                Token::Tag(name) => {
+                    let value = match lexemes.peek().map(|lexeme| lexeme.token) {
+                        Some(Token::Value(..) | Token::ListStart | Token::TableStart) => {
+                            value_span(&mut lexemes, &span)
+                        }
+                        _ => None,
+                    };
+
+                    value.map(|value| ItemNode::Item {
+                        name: String::from_utf8_lossy(name).to_string(),
+                        tag: span(&lexeme),
+                        value,
+                    })
+                }
+                Token::Loop => {
+                    let mut names = Vec::new();
+
+                    while let Some(Token::Tag(name)) = lexemes.peek().map(|lexeme| lexeme.token) {
+                        let tag = lexemes.next().expect("peeked");
+
+                        names.push((String::from_utf8_lossy(name).to_string(), span(&tag)));
+                    }
+
+                    let mut values = Vec::new();
+
+                    while let Some(Token::Value(..) | Token::ListStart | Token::TableStart) =
+                        lexemes.peek().map(|lexeme| lexeme.token)
+                    {
+                        values.extend(value_span(&mut lexemes, &span));
+                    }
+
+                    Some(ItemNode::Loop {
+                        keyword: span(&lexeme),
+                        names,
+                        values,
+                    })
+                }
+                _ => None,
+            };
+
+            let Some(block) = blocks.last_mut() else {
+                continue;
+            };
+
+            match item {
+                Some(item) if !in_save_frame => {
+                    block.end = item.end();
+                    block.items.push(item);
+                }
+                Some(item) => block.end = item.end(),
+                None => block.end = span(&lexeme).end,
+            }
+        }
+
+        self.blocks = blocks;
+    }
+}
+
+/// Span of the next value, including all tokens of a CIF 2.0 list or table
+fn value_span<'a>(
+    lexemes: &mut std::iter::Peekable<impl Iterator<Item = Lexeme<'a>>>,
+    span: &impl Fn(&Lexeme) -> Range<usize>,
+) -> Option<Range<usize>> {
+    let first = lexemes.next()?;
+    let mut range = span(&first);
+    let mut depth = 0;
+
+    let mut lexeme = first;
+
+    loop {
+        match lexeme.token {
+            Token::ListStart | Token::TableStart => depth += 1,
+            Token::ListEnd | Token::TableEnd => depth -= 1,
+            _ => {}
+        }
+
+        range.end = span(&lexeme).end;
+
+        if depth <= 0 {
+            return Some(range);
+        }
+
+        lexeme = lexemes.next()?;
+    }
+}
+
+impl std::fmt::Display for Document {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.bytes))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Document;
+    use crate::value::CifValue;
+
+    const CIF: &str = "\
+#(C) 2024 by FIZ Karlsruhe
+data_test
+_cell_length_a    4.0094(2)  # refined
+_cell_volume      64.45
+_title            'BaTiO3 cubic'
+loop_
+_atom_site_label
+_atom_site_fract_x
+_atom_site_fract_y
+Ba1 0   0
+O1  0.5 0
+#End of data_test
+";
+
+    #[test]
+    fn test_unchanged() {
+        let bytes = std::fs::read(r"assets\BaTiO3.cif").unwrap();
+
+        let document = Document::parse(&bytes).unwrap();
+
+        assert_eq!(document.as_bytes(), bytes.as_slice());
+    }
+
+    #[test]
+    fn test_set_value() {
+        let mut document = Document::parse(CIF.as_bytes()).unwrap();
+
+        document
+            .set_value("test", "_cell_volume", CifValue::Numeric("64.46(1)".into()))
+            .unwrap();
+        document
+            .set_loop_value("test", "_atom_site_fract_y", 1, "a b")
+            .unwrap();
+
+        assert_eq!(
+            document.to_string(),
+            CIF.replace("64.45", "64.46(1)")
+                .replace("O1  0.5 0", "O1  0.5 'a b'")
+        );
+
+        document.set_value("test", "_title", "multi\nline").unwrap();
+
+        assert!(document
+            .to_string()
+            .contains("_title\n;\nmulti\nline\n;\nloop_"));
+
+        assert!(document.set_value("test", "_atom_site_label", "x").is_err());
+        assert!(document.set_value("test", "_missing", "x").is_err());
+    }
+
+    #[test]
+    fn test_insert_and_append() {
+        let mut document = Document::parse(CIF.as_bytes()).unwrap();
+
+        document
+            .insert_item("test", "_audit_creation_method", "edited")
+            .unwrap();
+        document
+            .append_loop_row(
+                "test",
+                "_atom_site_label",
+                vec![
+                    "Ti1".into(),
+                    CifValue::Numeric("0.5".into()),
+                    CifValue::Unknown,
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(
+            document.to_string(),
+            CIF.replace(
+                "O1  0.5 0\n",
+                "O1  0.5 0\nTi1 0.5 ?\n_audit_creation_method edited\n"
+            )
+        );
+
+        assert!(document
+            .append_loop_row("test", "_atom_site_label", vec!["x".into()])
+            .is_err());
+        assert!(document.insert_item("test", "_cell_volume", "1").is_err());
+    }
+
+    #[test]
+    fn test_delete_item() {
+        let mut document = Document::parse(CIF.as_bytes()).unwrap();
+
+        document.delete_item("test", "_cell_volume").unwrap();
+        document.delete_item("test", "_atom_site_fract_x").unwrap();
+
+        assert_eq!(
+            document.to_string(),
+            CIF.replace("_cell_volume      64.45\n", "")
+                .replace("_atom_site_fract_x\n", "")
+                .replace("Ba1 0   0", "Ba1   0")
+                .replace("O1  0.5 0", "O1 0")
+        );
+
+        document.delete_item("test", "_atom_site_label").unwrap();
+        document.delete_item("test", "_atom_site_fract_y").unwrap();
+
+        assert_eq!(
+            document.to_string(),
+            "#(C) 2024 by FIZ Karlsruhe\ndata_test\n_cell_length_a    4.0094(2)  # refined\n_title            'BaTiO3 cubic'\n#End of data_test\n"
+        );
+
+        let cif = document.to_cif();
+
+        assert_eq!(cif.get("test").unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,115 @@
+/// Line and column (both starting at 1) of a token in the input
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CifErrorKind {
+    UnterminatedQuotedString,
+    UnterminatedTextField,
+    UnterminatedList,
+    UnterminatedTable,
+    /// Data name, value or loop before the first `data_` header
+    DataOutsideDataBlock,
+    ValueWithoutDataName,
+    MissingValue,
+    MissingTableValue,
+    LoopWithoutDataNames,
+    /// Number of values in a loop is not a multiple of the number of its data names
+    LoopValueCount {
+        data_names: usize,
+        values: usize,
+    },
+    DuplicateDataBlock,
+    DuplicateSaveFrame,
+    DuplicateDataName,
+    /// `save_<name>` without the closing `save_`
+    UnterminatedSaveFrame,
+    UnexpectedToken,
+}
+
+impl std::fmt::Display for CifErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CifErrorKind::UnterminatedQuotedString => write!(f, "unterminated quoted string"),
+            CifErrorKind::UnterminatedTextField => write!(f, "unterminated text field"),
+            CifErrorKind::UnterminatedList => write!(f, "list is missing its closing `]`"),
+            CifErrorKind::UnterminatedTable => write!(f, "table is missing its closing `}}`"),
+            CifErrorKind::DataOutsideDataBlock => write!(f, "data before the first data block"),
+            CifErrorKind::ValueWithoutDataName => write!(f, "value without a data name"),
+            CifErrorKind::MissingValue => write!(f, "data name without a value"),
+            CifErrorKind::MissingTableValue => write!(f, "table key without a value"),
+            CifErrorKind::LoopWithoutDataNames => write!(f, "loop without data names"),
+            CifErrorKind::LoopValueCount { data_names, values } => write!(
+                f,
+                "number of values ({}) in loop is not a multiple of the number of data names ({})",
+                values, data_names
+            ),
+            CifErrorKind::DuplicateDataBlock => write!(f, "duplicate data block"),
+            CifErrorKind::DuplicateSaveFrame => write!(f, "duplicate save frame"),
+            CifErrorKind::DuplicateDataName => write!(f, "duplicate data name"),
+            CifErrorKind::UnterminatedSaveFrame => {
+                write!(f, "save frame is missing its closing `save_`")
+            }
+            CifErrorKind::UnexpectedToken => write!(f, "unexpected token"),
+        }
+    }
+}
+
+/// Syntax error found while parsing, with the location of the offending token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CifError {
+    pub kind: CifErrorKind,
+    pub position: Position,
+    pub token: String,
+    /// Name of the data block the error was found in
+    pub data_block: Option<String>,
+}
+
+impl CifError {
+    pub fn line(&self) -> usize {
+        self.position.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.position.column
+    }
+}
+
+impl std::fmt::Display for CifError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} at `{}`", self.position, self.kind, self.token)?;
+
+        if let Some(data_block) = &self.data_block {
+            write!(f, " in data block `{}`", data_block)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for CifError {}
+
+/// Short excerpt of a token for error messages
+pub(crate) fn token_text(raw: &[u8]) -> String {
+    const MAX_CHARS: usize = 40;
+
+    let text = String::from_utf8_lossy(raw);
+    let first_line = text.lines().next().unwrap_or_default();
+
+    match first_line.chars().count() > MAX_CHARS || first_line.len() < text.len() {
+        true => format!(
+            "{}...",
+            first_line.chars().take(MAX_CHARS).collect::<String>()
+        ),
+        false => first_line.to_string(),
+    }
+}
@@ -0,0 +1,142 @@
+use crate::parser::DataBlock;
+
+/// Struct read from the single data items of a data block, usually by `#[derive(FromDataBlock)]`
+///
+/// ```ignore
+/// #[derive(FromDataBlock)]
+/// struct CellLengths {
+///     #[cif(tag = "_cell_length_a")]
+///     a: f64,
+///     #[cif(tag = "_cell_length_a", uncertainty)]
+///     a_su: Option<f64>,
+///     #[cif(tag = "_space_group_name_H-M_alt", alias = "_symmetry_space_group_name_H-M")]
+///     space_group: String,
+///     #[cif(tag = "_cell_measurement_temperature", default = "293.0")]
+///     temperature: f64,
+/// }
+/// ```
+pub trait FromDataBlock: Sized {
+    fn from_data_block(data_block: &DataBlock) -> anyhow::Result<Self>;
+}
+
+/// Rows of a loop, usually by `#[derive(FromLoop)]` with the same field attributes as
+/// [`FromDataBlock`]
+pub trait FromLoop: Sized {
+    fn from_loop(data_block: &DataBlock) -> anyhow::Result<Vec<Self>>;
+}
+
+impl DataBlock {
+    pub fn extract<T: FromDataBlock>(&self) -> anyhow::Result<T> {
+        T::from_data_block(self)
+    }
+
+    pub fn extract_rows<T: FromLoop>(&self) -> anyhow::Result<Vec<T>> {
+        T::from_loop(self)
+    }
+}
+
+/// Used by the code generated by the derive macros
+#[doc(hidden)]
+pub mod __private {
+    use std::str::FromStr;
+
+    use anyhow::Context;
+
+    pub use anyhow;
+
+    use crate::{
+        measured::Measured,
+        parser::{DataBlock, Loop},
+        value::CifValue,
+    };
+
+    /// Values of the first of `tags` that exists in the data block
+    pub fn column<'a>(
+        data_block: &'a DataBlock,
+        tags: &[&'static str],
+    ) -> Option<(&'static str, &'a [CifValue])> {
+        tags.iter()
+            .find_map(|tag| data_block.get(tag).map(|values| (*tag, values.as_slice())))
+    }
+
+    pub fn first<'a>(
+        data_block: &'a DataBlock,
+        tags: &[&'static str],
+    ) -> Option<(&'static str, &'a CifValue)> {
+        column(data_block, tags).and_then(|(tag, values)| values.first().map(|value| (tag, value)))
+    }
+
+    /// Number of rows of the loop that holds the columns, or 1 for single data items. Fails if the
+    /// columns come from different loops, whose rows do not belong together.
+    pub fn row_count(data_block: &DataBlock, columns: &[&[&'static str]]) -> anyhow::Result<usize> {
+        let tags = columns
+            .iter()
+            .filter_map(|tags| column(data_block, tags))
+            .map(|(tag, _)| tag)
+            .collect::<Vec<_>>();
+
+        let mut found: Option<(&str, Option<Loop>)> = None;
+
+        for tag in tags {
+            let loop_ = data_block.find_loop(tag);
+
+            match &found {
+                None => found = Some((tag, loop_)),
+                Some((first, first_loop)) => {
+                    let same = match (first_loop, &loop_) {
+                        (Some(first_loop), Some(loop_)) => first_loop.names() == loop_.names(),
+                        (None, None) => true,
+                        _ => false,
+                    };
+
+                    if !same {
+                        anyhow::bail!(
+                            "Data names `{}` and `{}` are not in the same loop",
+                            first,
+                            tag
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(match found {
+            Some((_, Some(loop_))) => loop_.len(),
+            Some((_, None)) => 1,
+            None => 0,
+        })
+    }
+
+    pub fn required<T: FromStr>(
+        value: Option<(&str, &CifValue)>,
+        tags: &[&'static str],
+    ) -> anyhow::Result<T>
+    where
+        <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+    {
+        let (tag, value) =
+            value.with_context(|| format!("Data name `{}` not found", tags.join("` or `")))?;
+
+        value
+            .parse::<T>()
+            .with_context(|| format!("Failed to parse value `{}` of `{}`", value, tag))
+    }
+
+    /// `None` if the data item is missing, `?` or `.`
+    pub fn optional<T: FromStr>(value: Option<(&str, &CifValue)>) -> anyhow::Result<Option<T>>
+    where
+        <T as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+    {
+        match value {
+            Some((tag, value)) => value
+                .parse_optional::<T>()
+                .with_context(|| format!("Failed to parse value `{}` of `{}`", value, tag)),
+            None => Ok(None),
+        }
+    }
+
+    /// Standard uncertainty of a number like `4.0094(2)`
+    pub fn uncertainty(value: Option<(&str, &CifValue)>) -> anyhow::Result<Option<f64>> {
+        Ok(optional::<Measured>(value)?.and_then(|measured| measured.su))
+    }
+}
@@ -0,0 +1,510 @@
+// https://www.iucr.org/resources/cif/spec/version1.1/cifsyntax
+// https://doi.org/10.1107/S1600576715021871 (CIF 2.0)
+// Tokenizer for the CIF 1.1 and CIF 2.0 grammars. Works on bytes so that the tokens can borrow from the input.
+
+use crate::error::{token_text, CifError, CifErrorKind, Position};
+
+pub(crate) const CIF_2_MAGIC: &[u8] = b"#\\#CIF_2.0";
+pub(crate) const BYTE_ORDER_MARK: &[u8] = b"\xEF\xBB\xBF";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CifVersion {
+    #[default]
+    V1_1,
+    /// Selected by the `#\#CIF_2.0` magic code at the start of the file
+    V2_0,
+}
+
+/// How a value was delimited in the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quoting {
+    None,
+    Single,
+    Double,
+    /// CIF 2.0 `'''` or `"""`
+    Triple,
+    TextField,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Token<'a> {
+    /// `data_<name>`
+    DataBlock(&'a [u8]),
+    /// `save_<name>`, or an empty name for the closing `save_`
+    SaveFrame(&'a [u8]),
+    Loop,
+    Global,
+    Stop,
+    /// `_<name>`
+    Tag(&'a [u8]),
+    /// Value without its delimiters
+    Value(&'a [u8], Quoting),
+    /// Comment text without the leading `#`
+    Comment(&'a [u8]),
+    /// CIF 2.0 `[`
+    ListStart,
+    /// CIF 2.0 `]`
+    ListEnd,
+    /// CIF 2.0 `{`
+    TableStart,
+    /// CIF 2.0 `}`
+    TableEnd,
+    /// CIF 2.0 quoted table key directly followed by `:`
+    TableKey(&'a [u8]),
+}
+
+/// Token together with its location and its text in the input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Lexeme<'a> {
+    pub token: Token<'a>,
+    pub position: Position,
+    pub raw: &'a [u8],
+}
+
+pub(crate) struct Lexer<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    version: CifVersion,
+    line: usize,
+    line_start: usize,
+    /// Offset up to which `line` and `line_start` have been counted
+    counted: usize,
+    token_start: usize,
+    errors: Vec<CifError>,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        let bytes = bytes.strip_prefix(BYTE_ORDER_MARK).unwrap_or(bytes);
+
+        let version = match bytes.starts_with(CIF_2_MAGIC) {
+            true => CifVersion::V2_0,
+            false => CifVersion::V1_1,
+        };
+
+        Self::with_version(bytes, version)
+    }
+
+    /// Lexer for a part of a file whose version was already detected
+    pub fn with_version(bytes: &'a [u8], version: CifVersion) -> Self {
+        let bytes = bytes.strip_prefix(BYTE_ORDER_MARK).unwrap_or(bytes);
+
+        Self {
+            bytes,
+            offset: 0,
+            version,
+            line: 1,
+            line_start: 0,
+            counted: 0,
+            token_start: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn version(&self) -> CifVersion {
+        self.version
+    }
+
+    /// Errors found since the last call
+    pub fn take_errors(&mut self) -> Vec<CifError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Position of `offset`, which must not be before the last counted offset
+    fn position_of(&mut self, offset: usize) -> Position {
+        for index in self.counted..offset {
+            if self.bytes[index] == b'\n'
+                || (self.bytes[index] == b'\r' && self.bytes.get(index + 1) != Some(&b'\n'))
+            {
+                self.line += 1;
+                self.line_start = index + 1;
+            }
+        }
+
+        self.counted = offset;
+
+        Position {
+            line: self.line,
+            column: String::from_utf8_lossy(&self.bytes[self.line_start..offset])
+                .chars()
+                .count()
+                + 1,
+        }
+    }
+
+    fn error(&mut self, kind: CifErrorKind) {
+        let position = self.position_of(self.token_start);
+
+        self.errors.push(CifError {
+            kind,
+            position,
+            token: token_text(&self.bytes[self.token_start..self.offset]),
+            data_block: None,
+        });
+    }
+
+    fn is_cif2(&self) -> bool {
+        self.version == CifVersion::V2_0
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.offset).copied()
+    }
+
+    fn is_line_start(&self) -> bool {
+        self.offset == 0 || matches!(self.bytes[self.offset - 1], b'\n' | b'\r')
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(is_whitespace) {
+            self.offset += 1;
+        }
+    }
+
+    fn take_until_whitespace(&mut self) -> &'a [u8] {
+        let start = self.offset;
+
+        while self.peek().is_some_and(|byte| !is_whitespace(byte)) {
+            self.offset += 1;
+        }
+
+        &self.bytes[start..self.offset]
+    }
+
+    /// In CIF 2.0 the list and table brackets also end an unquoted value
+    fn take_unquoted(&mut self) -> &'a [u8] {
+        if !self.is_cif2() {
+            return self.take_until_whitespace();
+        }
+
+        let start = self.offset;
+
+        while self
+            .peek()
+            .is_some_and(|byte| !is_whitespace(byte) && !is_bracket(byte))
+        {
+            self.offset += 1;
+        }
+
+        &self.bytes[start..self.offset]
+    }
+
+    fn take_until_line_end(&mut self) -> &'a [u8] {
+        let start = self.offset;
+
+        while self
+            .peek()
+            .is_some_and(|byte| byte != b'\n' && byte != b'\r')
+        {
+            self.offset += 1;
+        }
+
+        &self.bytes[start..self.offset]
+    }
+
+    /// In CIF 1.1 a quoted string ends at the first matching quote that is followed by whitespace,
+    /// so `'O'Brien'` is read as `O'Brien`. In CIF 2.0 it ends at the first matching quote.
+    fn quoted(&mut self, quote: u8) -> &'a [u8] {
+        self.offset += 1;
+
+        let start = self.offset;
+
+        loop {
+            match self.peek() {
+                Some(byte) if byte == quote => {
+                    let next = self.bytes.get(self.offset + 1).copied();
+
+                    if self.is_cif2() || next.is_none_or(is_whitespace) {
+                        let value = &self.bytes[start..self.offset];
+                        self.offset += 1;
+                        return value;
+                    }
+
+                    self.offset += 1;
+                }
+                Some(b'\n') | Some(b'\r') | None => {
+                    self.error(CifErrorKind::UnterminatedQuotedString);
+
+                    return &self.bytes[start..self.offset];
+                }
+                Some(_) => self.offset += 1,
+            }
+        }
+    }
+
+    /// CIF 2.0 triple quoted strings may span multiple lines and end at the next triple quote
+    fn triple_quoted(&mut self, quote: u8) -> &'a [u8] {
+        self.offset += 3;
+
+        let start = self.offset;
+        let delimiter = [quote; 3];
+
+        while self.offset < self.bytes.len() {
+            if self.bytes[self.offset..].starts_with(&delimiter) {
+                let value = &self.bytes[start..self.offset];
+                self.offset += 3;
+                return value;
+            }
+
+            self.offset += 1;
+        }
+
+        self.error(CifErrorKind::UnterminatedQuotedString);
+
+        &self.bytes[start..]
+    }
+
+    fn quoted_token(&mut self, quote: u8) -> Token<'a> {
+        let (value, quoting) =
+            match self.is_cif2() && self.bytes[self.offset..].starts_with(&[quote; 3]) {
+                true => (self.triple_quoted(quote), Quoting::Triple),
+                false => {
+                    let quoting = match quote {
+                        b'\'' => Quoting::Single,
+                        _ => Quoting::Double,
+                    };
+
+                    (self.quoted(quote), quoting)
+                }
+            };
+
+        if self.is_cif2() && self.peek() == Some(b':') {
+            self.offset += 1;
+            return Token::TableKey(value);
+        }
+
+        Token::Value(value, quoting)
+    }
+
+    /// A text field runs from a `;` at the start of a line up to the next line that starts with `;`.
+    /// The returned value excludes the delimiters and the line break before the closing `;`.
+    fn text_field(&mut self) -> &'a [u8] {
+        self.offset += 1;
+
+        let start = self.offset;
+
+        loop {
+            self.take_until_line_end();
+
+            let end = self.offset;
+
+            if self.peek() == Some(b'\r') {
+                self.offset += 1;
+            }
+
+            if self.peek() == Some(b'\n') {
+                self.offset += 1;
+            }
+
+            match self.peek() {
+                Some(b';') => {
+                    self.offset += 1;
+                    return &self.bytes[start..end];
+                }
+                Some(_) => continue,
+                None => {
+                    self.error(CifErrorKind::UnterminatedTextField);
+                    return &self.bytes[start..end];
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Lexeme<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.skip_whitespace();
+
+        let byte = self.peek()?;
+
+        self.token_start = self.offset;
+
+        let token = match byte {
+            b'#' => {
+                self.offset += 1;
+                Token::Comment(self.take_until_line_end())
+            }
+            b';' if self.is_line_start() => Token::Value(self.text_field(), Quoting::TextField),
+            b'\'' | b'"' => self.quoted_token(byte),
+            b'[' | b']' | b'{' | b'}' if self.is_cif2() => {
+                self.offset += 1;
+
+                match byte {
+                    b'[' => Token::ListStart,
+                    b']' => Token::ListEnd,
+                    b'{' => Token::TableStart,
+                    _ => Token::TableEnd,
+                }
+            }
+            b'_' => Token::Tag(self.take_until_whitespace()),
+            _ => {
+                let word = self.take_unquoted();
+
+                reserved_word(word).unwrap_or(Token::Value(word, Quoting::None))
+            }
+        };
+
+        let start = self.token_start;
+
+        Some(Lexeme {
+            token,
+            position: self.position_of(start),
+            raw: &self.bytes[start..self.offset],
+        })
+    }
+}
+
+fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+fn is_bracket(byte: u8) -> bool {
+    matches!(byte, b'[' | b']' | b'{' | b'}')
+}
+
+fn strip_prefix_ignore_case<'a>(word: &'a [u8], prefix: &[u8]) -> Option<&'a [u8]> {
+    (word.len() >= prefix.len() && word[..prefix.len()].eq_ignore_ascii_case(prefix))
+        .then(|| &word[prefix.len()..])
+}
+
+/// `data_`, `save_`, `loop_`, `global_` or `stop_`, which can not be written as unquoted values
+pub(crate) fn is_reserved_word(word: &[u8]) -> bool {
+    reserved_word(word).is_some()
+}
+
+fn reserved_word(word: &[u8]) -> Option<Token<'_>> {
+    if let Some(name) = strip_prefix_ignore_case(word, b"data_") {
+        return Some(Token::DataBlock(name));
+    }
+
+    if let Some(name) = strip_prefix_ignore_case(word, b"save_") {
+        return Some(Token::SaveFrame(name));
+    }
+
+    if word.eq_ignore_ascii_case(b"loop_") {
+        return Some(Token::Loop);
+    }
+
+    if word.eq_ignore_ascii_case(b"global_") {
+        return Some(Token::Global);
+    }
+
+    if word.eq_ignore_ascii_case(b"stop_") {
+        return Some(Token::Stop);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CifVersion, Lexer, Quoting, Token};
+    use crate::error::{CifErrorKind, Position};
+
+    fn values(input: &[u8]) -> Vec<(String, Quoting)> {
+        Lexer::new(input)
+            .filter_map(|lexeme| match lexeme.token {
+                Token::Value(value, quoting) => {
+                    Some((String::from_utf8_lossy(value).to_string(), quoting))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_quote_terminated_by_whitespace() {
+        let values = values(b"_name 'O'Brien' \"say \"hi\"\"\n_b 'a\tb'");
+
+        assert_eq!(values[0], ("O'Brien".to_string(), Quoting::Single));
+        assert_eq!(values[1], ("say \"hi\"".to_string(), Quoting::Double));
+        assert_eq!(values[2], ("a\tb".to_string(), Quoting::Single));
+    }
+
+    #[test]
+    fn test_text_field() {
+        let values = values(b"_a\n;first\nsecond\n;\n_b x;y\n_c ;z");
+
+        assert_eq!(values[0], ("first\nsecond".to_string(), Quoting::TextField));
+        assert_eq!(values[1], ("x;y".to_string(), Quoting::None));
+        assert_eq!(values[2], (";z".to_string(), Quoting::None));
+    }
+
+    #[test]
+    fn test_reserved_words_and_comments() {
+        let tokens =
+            Lexer::new(b"DATA_test # comment\nLoop_ _a 1#2\nsave_frame\nsave_\nstop_ global_")
+                .map(|lexeme| lexeme.token)
+                .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::DataBlock(b"test"),
+                Token::Comment(b" comment"),
+                Token::Loop,
+                Token::Tag(b"_a"),
+                Token::Value(b"1#2", Quoting::None),
+                Token::SaveFrame(b"frame"),
+                Token::SaveFrame(b""),
+                Token::Stop,
+                Token::Global,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cif2() {
+        let lexer =
+            Lexer::new(b"#\\#CIF_2.0\ndata_x\n_a [1 'b'{\"k\":v}]\n_b '''multi\n\"line\"''' 'O'");
+
+        assert_eq!(lexer.version(), CifVersion::V2_0);
+
+        let tokens = lexer
+            .map(|lexeme| lexeme.token)
+            .filter(|token| !matches!(token, Token::Comment(_)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::DataBlock(b"x"),
+                Token::Tag(b"_a"),
+                Token::ListStart,
+                Token::Value(b"1", Quoting::None),
+                Token::Value(b"b", Quoting::Single),
+                Token::TableStart,
+                Token::TableKey(b"k"),
+                Token::Value(b"v", Quoting::None),
+                Token::TableEnd,
+                Token::ListEnd,
+                Token::Tag(b"_b"),
+                Token::Value(b"multi\n\"line\"", Quoting::Triple),
+                Token::Value(b"O", Quoting::Single),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_positions_and_errors() {
+        let mut lexer = Lexer::new("data_a\r\n_b\t'unterminated\n_c \u{E4} x".as_bytes());
+
+        let positions = lexer
+            .by_ref()
+            .map(|lexeme| lexeme.position)
+            .collect::<Vec<_>>();
+
+        assert_eq!(positions[1], Position { line: 2, column: 1 });
+        assert_eq!(positions[2], Position { line: 2, column: 4 });
+        assert_eq!(positions[5], Position { line: 3, column: 6 });
+
+        let errors = lexer.take_errors();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, CifErrorKind::UnterminatedQuotedString);
+        assert_eq!(errors[0].token, "'unterminated");
+        assert_eq!(errors[0].position, Position { line: 2, column: 4 });
+    }
+}
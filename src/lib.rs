@@ -1,13 +1,50 @@
+#[cfg(feature = "serde")]
+pub mod de;
+mod document;
+mod error;
+mod extract;
+mod lexer;
+mod measured;
 pub(crate) mod parse;
 mod parser;
 pub mod phase;
+mod reader;
+#[cfg(feature = "serde")]
+pub mod ser;
+mod text_field;
+mod value;
+mod writer;
 
 #[cfg(feature = "symmetry")]
 pub mod symmetry;
 
+#[cfg(feature = "derive")]
+pub use cif_derive::{FromDataBlock, FromLoop};
 pub use crystallib::Phase;
+#[cfg(feature = "serde")]
+pub use de::{from_bytes, from_data_block, from_str, Error};
+pub use document::Document;
+pub use error::{CifError, CifErrorKind, Position};
+#[doc(hidden)]
+pub use extract::__private;
+pub use extract::{FromDataBlock, FromLoop};
+pub use lexer::{CifVersion, Quoting};
+pub use measured::{Measured, ParseMeasuredError};
+pub use parse::GetAndParse;
 pub use parser::read_cif;
+pub use parser::try_read_cif;
 pub use parser::Cif;
+pub use parser::DataBlock;
+pub use parser::Event;
+pub use parser::Events;
+pub use parser::Loop;
+pub use parser::LoopRow;
 pub use parser::Parser;
+pub use phase::write_phase;
+pub use reader::DataBlockReader;
+#[cfg(feature = "serde")]
+pub use ser::{to_data_block, to_string};
+pub use value::CifValue;
+pub use writer::{write_cif, WriteOptions, MAX_LINE_LENGTH};
 
 pub use parser::try_phase_from_cif_bytes;
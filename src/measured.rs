@@ -0,0 +1,164 @@
+use std::str::FromStr;
+
+use crate::value::is_number;
+
+/// Struct name that [`Measured`] is serialized under, which the serializer of data blocks writes as
+/// a number like `4.0094(2)`. It has to match the `serde(rename)` below.
+#[cfg(feature = "serde")]
+pub(crate) const SERDE_NAME: &str = "$cif::private::Measured";
+
+/// Number with its standard uncertainty, as written in `4.0094(2)`
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename = "$cif::private::Measured"))]
+pub struct Measured {
+    pub value: f64,
+    /// Standard uncertainty, already scaled to the last digit of the value
+    pub su: Option<f64>,
+}
+
+impl Measured {
+    pub fn new(value: f64, su: Option<f64>) -> Self {
+        Self { value, su }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseMeasuredError(String);
+
+impl std::fmt::Display for ParseMeasuredError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` is not a number with standard uncertainty", self.0)
+    }
+}
+
+impl std::error::Error for ParseMeasuredError {}
+
+impl FromStr for Measured {
+    type Err = ParseMeasuredError;
+
+    /// Accepts the standard uncertainty before or after the exponent: `1.23(12)e-3` and `1.23e-3(12)`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseMeasuredError(s.to_string());
+
+        let text = s.trim();
+
+        if !is_number(text) {
+            return Err(error());
+        }
+
+        let (number, su_digits) = match text.split_once('(') {
+            Some((before, rest)) => {
+                let (su_digits, after) = rest.split_once(')').ok_or_else(error)?;
+                (format!("{}{}", before, after), Some(su_digits))
+            }
+            None => (text.to_string(), None),
+        };
+
+        let value = number.parse::<f64>().map_err(|_| error())?;
+
+        let su = match su_digits {
+            Some(su_digits) => {
+                let (mantissa, exponent) = match number.split_once(['e', 'E']) {
+                    Some((mantissa, exponent)) => {
+                        (mantissa, exponent.parse::<i32>().map_err(|_| error())?)
+                    }
+                    None => (number.as_str(), 0),
+                };
+
+                let decimals = mantissa
+                    .split_once('.')
+                    .map_or(0, |(_, decimals)| decimals.len() as i32);
+
+                let su_digits = su_digits.parse::<f64>().map_err(|_| error())?;
+
+                Some(su_digits * 10f64.powi(exponent - decimals))
+            }
+            None => None,
+        };
+
+        Ok(Self { value, su })
+    }
+}
+
+impl std::fmt::Display for Measured {
+    /// Writes the standard uncertainty with one digit, or two if they are at most 19
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let su = match self.su {
+            Some(su) if su > 0.0 && su.is_finite() => su,
+            _ => return write!(f, "{}", self.value),
+        };
+
+        // the two leading digits of the uncertainty
+        let exponent = su.log10().floor() as i32;
+        let leading = (su * 10f64.powi(1 - exponent)).round();
+
+        let decimals = match leading <= 19.0 {
+            true => 1 - exponent,
+            false => -exponent,
+        };
+
+        // uncertainties of 10 or more are written without decimals
+        let decimals = decimals.max(0);
+
+        let su_digits = (su * 10f64.powi(decimals)).round();
+
+        write!(
+            f,
+            "{:.*}({})",
+            decimals as usize, self.value, su_digits as u64
+        )
+    }
+}
+
+impl From<f64> for Measured {
+    fn from(value: f64) -> Self {
+        Self { value, su: None }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Measured;
+
+    fn assert_measured(text: &str, value: f64, su: Option<f64>) {
+        let measured = text.parse::<Measured>().unwrap();
+
+        assert!((measured.value - value).abs() < 1e-12, "{}", text);
+
+        match (measured.su, su) {
+            (Some(parsed), Some(expected)) => {
+                assert!((parsed - expected).abs() < 1e-12, "{}", text)
+            }
+            (parsed, expected) => assert_eq!(parsed, expected, "{}", text),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_measured("4.0094(2)", 4.0094, Some(0.0002));
+        assert_measured("1.23(12)e-3", 1.23e-3, Some(0.12e-3));
+        assert_measured("1.23e-3(12)", 1.23e-3, Some(0.12e-3));
+        assert_measured("90", 90.0, None);
+        assert_measured("-0.5(3)", -0.5, Some(0.3));
+        assert_measured("1234(56)", 1234.0, Some(56.0));
+        assert_measured("1.(2)", 1.0, Some(2.0));
+
+        assert!("x".parse::<Measured>().is_err());
+        assert!("1(2".parse::<Measured>().is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Measured::new(4.0094, Some(0.0002)).to_string(), "4.0094(2)");
+        assert_eq!(Measured::new(0.005, Some(0.0012)).to_string(), "0.0050(12)");
+        assert_eq!(Measured::new(1234.0, Some(56.0)).to_string(), "1234(56)");
+        assert_eq!(
+            Measured::new(4.0094, Some(0.0018)).to_string(),
+            "4.0094(18)"
+        );
+        assert_eq!(Measured::new(4.0094, Some(0.0025)).to_string(), "4.009(3)");
+        assert_eq!(Measured::new(0.5, Some(0.3)).to_string(), "0.5(3)");
+        assert_eq!(Measured::new(90.0, None).to_string(), "90");
+    }
+}
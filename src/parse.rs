@@ -1,7 +1,10 @@
-use std::{collections::BTreeMap, str::FromStr};
+use std::str::FromStr;
 
+use crate::parser::DataBlock;
 use anyhow::Context;
-pub(crate) trait GetAndParse {
+
+/// Lookup of data items by name that parses their values, e.g. as `f64` or [`crate::Measured`]
+pub trait GetAndParse {
     fn get_and_parse_first<T: FromStr>(&self, key: &str) -> anyhow::Result<T>
     where
         <T as FromStr>::Err: Send,
@@ -15,9 +18,17 @@ pub(crate) trait GetAndParse {
         <T as FromStr>::Err: Sync,
         Result<T, <T as FromStr>::Err>: Context<T, <T as FromStr>::Err>,
         <T as FromStr>::Err: 'static;
+
+    /// Like [`GetAndParse::get_and_parse_all`], but `?` and `.` are `None` instead of an error
+    fn get_and_parse_all_optional<T: FromStr>(&self, key: &str) -> anyhow::Result<Vec<Option<T>>>
+    where
+        <T as FromStr>::Err: Send,
+        <T as FromStr>::Err: Sync,
+        Result<T, <T as FromStr>::Err>: Context<T, <T as FromStr>::Err>,
+        <T as FromStr>::Err: 'static;
 }
 
-impl GetAndParse for BTreeMap<String, Vec<String>> {
+impl GetAndParse for DataBlock {
     fn get_and_parse_first<T: FromStr>(&self, key: &str) -> anyhow::Result<T>
     where
         <T as FromStr>::Err: Send,
@@ -31,7 +42,7 @@ impl GetAndParse for BTreeMap<String, Vec<String>> {
             .first()
             .context(format!("Key: `{}` does not have a value", key))?;
 
-        value.parse_without_uncertainty::<T>().context(format!(
+        value.parse::<T>().context(format!(
             "Failed to parse value `{}` for key: `{}`",
             value, key
         ))
@@ -48,44 +59,31 @@ impl GetAndParse for BTreeMap<String, Vec<String>> {
             .context(format!("Key: `{}` does not exist", key))?
             .iter()
             .map(|value| {
-                value.parse_without_uncertainty::<T>().context(format!(
+                value.parse::<T>().context(format!(
                     "Failed to parse value `{}` for key: `{}`",
                     value, key
                 ))
             })
             .collect()
     }
-}
-
-trait ParseWithoutUncertainty {
-    fn parse_without_uncertainty<T>(self) -> anyhow::Result<T>
-    where
-        T: FromStr,
-        <T as FromStr>::Err: Send,
-        <T as FromStr>::Err: Sync,
-        Result<T, <T as FromStr>::Err>: Context<T, <T as FromStr>::Err>,
-        <T as FromStr>::Err: 'static;
-}
 
-impl ParseWithoutUncertainty for &String {
-    fn parse_without_uncertainty<T>(self) -> anyhow::Result<T>
+    fn get_and_parse_all_optional<T: FromStr>(&self, key: &str) -> anyhow::Result<Vec<Option<T>>>
     where
-        T: FromStr,
         <T as FromStr>::Err: Send,
         <T as FromStr>::Err: Sync,
         Result<T, <T as FromStr>::Err>: Context<T, <T as FromStr>::Err>,
         <T as FromStr>::Err: 'static,
     {
-        let stripped = self
-            .as_bytes()
+        self.get(key)
+            .context(format!("Key: `{}` does not exist", key))?
             .iter()
-            .take_while(|&byte| byte != &b'(')
-            .copied()
-            .collect::<Vec<u8>>();
-
-        String::from_utf8(stripped)
-            .unwrap()
-            .parse::<T>()
-            .context("Failed to parse value")
+            .map(|value| match value.is_null() {
+                true => Ok(None),
+                false => value.parse::<T>().map(Some).context(format!(
+                    "Failed to parse value `{}` for key: `{}`",
+                    value, key
+                )),
+            })
+            .collect()
     }
 }
@@ -1,416 +1,537 @@
-use std::collections::BTreeMap;
+use std::borrow::Cow;
+
+use indexmap::IndexMap;
 
 use anyhow::Context;
 use crystallib::Phase;
 
-#[derive(Debug)]
-struct GlobalFlags {
-    is_loop: bool,
-    is_string_multiline: bool,
-    is_string_with_spaces: bool,
-    is_new_line: bool,
-    is_comment: bool,
-}
-
-impl Default for GlobalFlags {
-    fn default() -> Self {
-        Self {
-            is_loop: false,
-            is_string_multiline: false,
-            is_string_with_spaces: false,
-            is_new_line: true,
-            is_comment: false,
-        }
-    }
-}
-
-#[derive(Default, Debug)]
-struct LocalFlags {
-    has_new_line_byte: bool,
-    is_loop_chunk: bool,
-    is_data_item: bool,
-}
-
-#[derive(Default, Debug)]
-struct TempData {
-    names: Vec<String>,
-    values: Vec<String>,
-}
+use crate::{
+    error::{token_text, CifError, CifErrorKind, Position},
+    lexer::{CifVersion, Lexeme, Lexer, Quoting, Token},
+    reader::DataBlockReader,
+    text_field::read_text_field,
+    value::CifValue,
+};
 
 pub struct Parser<'a> {
-    chunks: Box<dyn Iterator<Item = &'a [u8]> + 'a>,
-    chunk: Option<&'a [u8]>,
-    data_blocks: BTreeMap<String, DataBlock>,
+    version: CifVersion,
+    lexer: Lexer<'a>,
+    peeked: Option<Lexeme<'a>>,
+    data_blocks: IndexMap<String, DataBlock>,
     current_block: Option<String>,
-    string_multiline: Vec<u8>,
-    string_with_spaces: Vec<u8>,
-    temp_data: TempData,
-    values_cleared_this_loop: usize,
-    global_flags: GlobalFlags,
-    local_flags: LocalFlags,
+    /// `save_<name>` of the save frame that is currently open
+    current_frame: Option<Lexeme<'a>>,
+    errors: Vec<CifError>,
 }
 
 impl std::fmt::Debug for Parser<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Parser")
-            .field("chunk", &self.chunk)
+            .field("version", &self.version)
             .field("data", &self.data_blocks)
-            .field("string_multiline", &self.string_multiline)
-            .field("string_with_spaces", &self.string_with_spaces)
-            .field("temp_data", &self.temp_data)
-            .field("values_cleared_this_loop", &self.values_cleared_this_loop)
-            .field("global_flags", &self.global_flags)
-            .field("local_flags", &self.local_flags)
+            .field("current_block", &self.current_block)
+            .field("current_frame", &self.current_frame)
+            .field("errors", &self.errors)
             .finish()
     }
 }
 
 impl<'a> Parser<'a> {
     pub fn new(bytes: &'a [u8]) -> Self {
-        let chunks = Box::new(split_input_into_chunks(bytes));
+        Self::with_lexer(Lexer::new(bytes))
+    }
 
+    pub(crate) fn with_version(bytes: &'a [u8], version: CifVersion) -> Self {
+        Self::with_lexer(Lexer::with_version(bytes, version))
+    }
+
+    fn with_lexer(lexer: Lexer<'a>) -> Self {
         Self {
-            chunks,
-            chunk: None,
-            data_blocks: BTreeMap::new(),
+            version: lexer.version(),
+            lexer,
+            peeked: None,
+            data_blocks: IndexMap::new(),
             current_block: None,
-            string_multiline: Vec::new(),
-            string_with_spaces: Vec::new(),
-            temp_data: TempData::default(),
-            values_cleared_this_loop: 0,
-            global_flags: GlobalFlags::default(),
-            local_flags: LocalFlags::default(),
+            current_frame: None,
+            errors: Vec::new(),
         }
     }
 
+    pub fn version(&self) -> CifVersion {
+        self.version
+    }
+
+    /// Errors found while parsing. [`Parser::parse`] skips over them and only logs them.
+    pub fn errors(&self) -> &[CifError] {
+        &self.errors
+    }
+
     pub fn parse(&mut self) -> Cif {
-        log::debug!("Parsing CIF file");
-
-        self.skip_until_first_data_block();
-
-        while self.chunk.is_some() {
-            self.update_data_from_temp_data()
-                .and_then(|()| self.check_is_empty())
-                .and_then(|()| self.check_has_new_line_byte())
-                .and_then(|()| self.check_comment_byte())
-                .and_then(|()| self.check_comment_ends())
-                .and_then(|()| self.check_is_comment())
-                .and_then(|()| self.chunk_trim_ascii_end())
-                .and_then(|()| self.check_is_empty())
-                .and_then(|()| self.handle_is_start_data_block())
-                .and_then(|()| self.check_is_start_or_end_of_multi_line_string())
-                .and_then(|()| self.handle_if_multi_line_string())
-                .and_then(|()| self.handle_multi_line_string_end())
-                .and_then(|()| self.check_is_string_with_spaces())
-                .and_then(|()| self.handle_if_string_with_spaces())
-                .and_then(|()| self.check_loop_byte())
-                .and_then(|()| self.check_is_data_item())
-                .and_then(|()| self.check_loop_end())
-                .and_then(|()| self.handle_if_data_item())
-                .and_then(|()| self.handle_if_value_chunk())
-                .unwrap_or(());
-
-            log::trace!("------{:?}", self.global_flags);
-            log::trace!("------{:?}", self.local_flags);
-            log::trace!("------No. Names {:?}", self.temp_data.names.len());
-            log::trace!("------No. Values {:?}", self.temp_data.values.len());
-
-            self.next();
-        }
-
-        self.update_data_from_temp_data();
-
-        if !self.temp_data.values.is_empty() {
-            log::warn!("Parsing was not finished correctly. Some temp data could not be added to the data map.");
-            log::warn!("Names: {:?}", self.temp_data.names);
-            log::warn!("Values: {:?}", self.temp_data.values);
-        }
-
-        Cif(self.data_blocks.clone())
-    }
-
-    fn next(&mut self) {
-        self.chunk = self.chunks.next();
-        self.reset_flags_for_chunk();
-    }
-
-    // TODO: a little bit convoluted
-    fn skip_until_first_data_block(&mut self) {
-        self.next();
-        self.check_has_new_line_byte();
-        self.reset_flags_for_chunk();
-
-        while self.chunk.is_some() {
-            if self.handle_is_start_data_block().is_none() {
-                self.next();
-                break;
+        log::debug!("Parsing CIF {:?} file", self.version);
+
+        while let Some(lexeme) = self.next_lexeme() {
+            match lexeme.token {
+                Token::DataBlock(name) => self.handle_data_block(name, lexeme),
+                Token::SaveFrame(name) => self.handle_save_frame(name, lexeme),
+                Token::Loop => self.handle_loop(lexeme),
+                Token::Tag(name) => self.handle_data_item(name, lexeme),
+                Token::Value(..) | Token::ListStart | Token::TableStart => {
+                    self.value_from_lexeme(lexeme);
+                    self.error(CifErrorKind::ValueWithoutDataName, &lexeme);
+                }
+                Token::ListEnd
+                | Token::TableEnd
+                | Token::TableKey(_)
+                | Token::Global
+                | Token::Stop => self.error(CifErrorKind::UnexpectedToken, &lexeme),
+                Token::Comment(_) => {}
             }
-
-            self.next();
-            self.check_has_new_line_byte();
-            self.reset_flags_for_chunk();
         }
-    }
 
-    fn check_is_empty(&mut self) -> Option<()> {
-        (!self.chunk.unwrap().is_empty()).then_some(())
+        self.close_save_frame();
+
+        Cif(std::mem::take(&mut self.data_blocks))
     }
 
-    fn reset_flags_for_chunk(&mut self) {
-        self.global_flags.is_new_line = self.local_flags.has_new_line_byte;
-        self.local_flags = LocalFlags::default();
+    /// Like [`Parser::parse`], but fails on the first syntax error instead of skipping over it
+    pub fn try_parse(&mut self) -> Result<Cif, CifError> {
+        let cif = self.parse();
+
+        match self.errors.first() {
+            Some(error) => Err(error.clone()),
+            None => Ok(cif),
+        }
     }
 
-    fn check_has_new_line_byte(&mut self) -> Option<()> {
-        self.local_flags.has_new_line_byte = self.chunk.unwrap().last() == Some(&b'\n')
-            || self.chunk.unwrap().last() == Some(&b'\r');
+    fn error(&mut self, kind: CifErrorKind, lexeme: &Lexeme) {
+        let error = CifError {
+            kind,
+            position: lexeme.position,
+            token: token_text(lexeme.raw),
+            data_block: self.current_block.clone(),
+        };
 
-        Some(())
+        log::warn!("{}", error);
+
+        self.errors.push(error);
     }
 
-    fn check_comment_byte(&mut self) -> Option<()> {
-        if self.chunk.unwrap().starts_with(b"#") {
-            self.global_flags.is_comment = true;
+    fn next_lexeme(&mut self) -> Option<Lexeme<'a>> {
+        let lexeme = self.peeked.take().or_else(|| self.lexer.next());
+
+        for mut error in self.lexer.take_errors() {
+            error.data_block = self.current_block.clone();
+
+            log::warn!("{}", error);
+
+            self.errors.push(error);
         }
 
-        Some(())
+        lexeme
     }
 
-    fn check_comment_ends(&mut self) -> Option<()> {
-        if self.global_flags.is_comment && self.local_flags.has_new_line_byte {
-            self.global_flags.is_comment = false;
-            return None;
+    fn peek(&mut self) -> Option<&Token<'a>> {
+        if self.peeked.is_none() {
+            self.peeked = self.lexer.next();
         }
 
-        Some(())
+        self.peeked.as_ref().map(|lexeme| &lexeme.token)
     }
 
-    fn check_is_comment(&mut self) -> Option<()> {
-        if self.global_flags.is_comment {
-            return None;
+    fn skip_comments(&mut self) {
+        while let Some(Token::Comment(_)) = self.peek() {
+            self.next_lexeme();
         }
-
-        Some(())
     }
 
-    fn chunk_trim_ascii_end(&mut self) -> Option<()> {
-        self.chunk = Some(self.chunk.unwrap().trim_ascii_end());
+    fn next_value(&mut self) -> Option<CifValue> {
+        self.skip_comments();
 
-        log::trace!("{}", String::from_utf8_lossy(self.chunk.unwrap()));
+        match self.peek()? {
+            Token::Value(..) | Token::ListStart | Token::TableStart => {
+                let lexeme = self.next_lexeme()?;
+                Some(self.value_from_lexeme(lexeme))
+            }
+            _ => None,
+        }
+    }
 
-        Some(())
+    fn value_from_lexeme(&mut self, lexeme: Lexeme<'a>) -> CifValue {
+        match lexeme.token {
+            Token::ListStart => CifValue::List(self.handle_list(lexeme)),
+            Token::TableStart => CifValue::Table(self.handle_table(lexeme)),
+            Token::Value(value, quoting) => value_from_token(value, quoting),
+            _ => unreachable!("Token {:?} is not a value", lexeme.token),
+        }
     }
 
-    fn check_is_start_or_end_of_multi_line_string(&mut self) -> Option<()> {
-        if self.global_flags.is_new_line {
-            self.global_flags.is_string_multiline ^= self.chunk.unwrap()[0] == b';';
+    fn handle_list(&mut self, start: Lexeme<'a>) -> Vec<CifValue> {
+        let mut list = Vec::new();
+
+        while let Some(value) = self.next_value() {
+            list.push(value);
+        }
+
+        match self.peek() {
+            Some(Token::ListEnd) => {
+                self.next_lexeme();
+            }
+            _ => self.error(CifErrorKind::UnterminatedList, &start),
         }
 
-        Some(())
+        list
     }
 
-    fn handle_if_multi_line_string(&mut self) -> Option<()> {
-        if self.global_flags.is_string_multiline {
-            let mut string_chunk = self.chunk.unwrap();
+    fn handle_table(&mut self, start: Lexeme<'a>) -> IndexMap<String, CifValue> {
+        let mut table = IndexMap::new();
 
-            string_chunk = string_chunk.strip_prefix(b";").unwrap_or(string_chunk);
+        loop {
+            self.skip_comments();
 
-            string_chunk = string_chunk
-                .strip_suffix(b"\n")
-                .unwrap_or(string_chunk)
-                .strip_suffix(b"\r")
-                .unwrap_or(string_chunk);
+            match self.peek() {
+                Some(Token::TableKey(_)) => {
+                    let Some(lexeme) = self.next_lexeme() else {
+                        break;
+                    };
 
-            self.string_multiline.extend(string_chunk);
-            self.string_multiline.push(b' ');
+                    let Token::TableKey(key) = lexeme.token else {
+                        break;
+                    };
 
-            return None;
+                    match self.next_value() {
+                        Some(value) => {
+                            table.insert(String::from_utf8_lossy(key).to_string(), value);
+                        }
+                        None => self.error(CifErrorKind::MissingTableValue, &lexeme),
+                    }
+                }
+                Some(Token::TableEnd) => {
+                    self.next_lexeme();
+                    break;
+                }
+                _ => {
+                    self.error(CifErrorKind::UnterminatedTable, &start);
+                    break;
+                }
+            }
         }
 
-        Some(())
+        table
     }
 
-    fn handle_multi_line_string_end(&mut self) -> Option<()> {
-        if !self.global_flags.is_string_multiline && !self.string_multiline.is_empty() {
-            self.temp_data.values.push(
-                String::from_utf8_lossy(&self.string_multiline)
-                    .trim_ascii()
-                    .to_string(),
-            );
+    fn next_tag(&mut self) -> Option<String> {
+        self.skip_comments();
+
+        match self.peek()? {
+            Token::Tag(name) => {
+                let name = String::from_utf8_lossy(name).to_string();
+                self.next_lexeme();
+                Some(name)
+            }
+            _ => None,
+        }
+    }
 
-            self.string_multiline.clear();
+    fn handle_data_block(&mut self, name: &[u8], lexeme: Lexeme<'a>) {
+        let name = String::from_utf8_lossy(name).to_string();
 
-            return None;
+        log::debug!("Found new data block: {:?}", name);
+
+        self.close_save_frame();
+        self.current_block = Some(name.clone());
+
+        if index_of(&self.data_blocks, &name).is_some() {
+            self.error(CifErrorKind::DuplicateDataBlock, &lexeme);
         }
 
-        Some(())
+        self.data_blocks.insert(name, DataBlock::default());
     }
 
-    fn check_is_string_with_spaces(&mut self) -> Option<()> {
-        if self.chunk.unwrap().first() == Some(&b"'"[0])
-            || self.chunk.unwrap().first() == Some(&b"\""[0])
-        {
-            self.global_flags.is_string_with_spaces = true;
+    /// `save_<name>` opens a save frame in the current data block and `save_` closes it
+    fn handle_save_frame(&mut self, name: &[u8], lexeme: Lexeme<'a>) {
+        if name.is_empty() {
+            match self.current_frame.take() {
+                Some(_) => {}
+                None => self.error(CifErrorKind::UnexpectedToken, &lexeme),
+            }
+
+            return;
         }
 
-        Some(())
-    }
+        self.close_save_frame();
 
-    fn handle_if_string_with_spaces(&mut self) -> Option<()> {
-        if self.global_flags.is_string_with_spaces {
-            let string_chunk = self
-                .chunk
-                .unwrap()
-                .iter()
-                .filter(|&&byte| byte != b'\'' && byte != b'\"' && byte != b'\r' && byte != b'\n')
-                .collect::<Vec<&u8>>();
+        let name = String::from_utf8_lossy(name).to_string();
 
-            self.string_with_spaces.extend(string_chunk);
-            self.string_with_spaces.push(b' ');
+        log::debug!("Found new save frame: {:?}", name);
 
-            match self.chunk.unwrap().last() == Some(&b'\'')
-                || self.chunk.unwrap().last() == Some(&b'\"')
-            {
-                true => {
-                    self.temp_data.values.push(
-                        String::from_utf8_lossy(
-                            self.string_with_spaces.strip_suffix(b" ").unwrap(),
-                        )
-                        .to_string(),
-                    );
+        let Some(data_block) = self.current_block_mut(&lexeme) else {
+            return;
+        };
 
-                    self.global_flags.is_string_with_spaces = false;
+        let is_duplicate = index_of(&data_block.save_frames, &name).is_some();
 
-                    self.string_with_spaces.clear();
+        data_block.save_frames.insert(name, DataBlock::default());
 
-                    return None;
-                }
-                false => {
-                    return None;
-                }
-            }
+        if is_duplicate {
+            self.error(CifErrorKind::DuplicateSaveFrame, &lexeme);
         }
 
-        Some(())
+        self.current_frame = Some(lexeme);
     }
 
-    fn check_loop_byte(&mut self) -> Option<()> {
-        if self.chunk.unwrap().starts_with(b"loop_") {
-            self.global_flags.is_loop = true;
-            self.local_flags.is_loop_chunk = true;
-            self.temp_data.names.clear();
-            self.values_cleared_this_loop = 0;
+    /// Save frames that are still open at a new data block or at the end of the file are
+    /// missing their closing `save_`
+    fn close_save_frame(&mut self) {
+        if let Some(frame) = self.current_frame.take() {
+            self.error(CifErrorKind::UnterminatedSaveFrame, &frame);
         }
-
-        Some(())
     }
 
-    fn check_loop_end(&mut self) -> Option<()> {
-        if self.global_flags.is_loop
-            && self.local_flags.is_data_item
-            && self.values_cleared_this_loop > 0
-        {
-            self.global_flags.is_loop = false;
-            self.temp_data.names.clear();
-            self.values_cleared_this_loop = 0;
+    /// Data block or the save frame that is currently open in it
+    fn current_block_mut(&mut self, lexeme: &Lexeme<'a>) -> Option<&mut DataBlock> {
+        if self.current_block.is_none() {
+            self.error(CifErrorKind::DataOutsideDataBlock, lexeme);
         }
 
-        Some(())
+        let data_block = self
+            .current_block
+            .as_ref()
+            .and_then(|name| self.data_blocks.get_mut(name))?;
+
+        match self.current_frame.map(|frame| frame.token) {
+            Some(Token::SaveFrame(name)) => data_block
+                .save_frames
+                .get_mut(String::from_utf8_lossy(name).as_ref()),
+            _ => Some(data_block),
+        }
     }
 
-    fn check_is_data_item(&mut self) -> Option<()> {
-        if self.chunk.unwrap().starts_with(b"_") {
-            self.local_flags.is_data_item = true;
+    fn handle_data_item(&mut self, name: &[u8], lexeme: Lexeme<'a>) {
+        let name = String::from_utf8_lossy(name).to_string();
+
+        let Some(value) = self.next_value() else {
+            self.error(CifErrorKind::MissingValue, &lexeme);
+            return;
+        };
+
+        let Some(data_block) = self.current_block_mut(&lexeme) else {
+            return;
+        };
+
+        let is_duplicate = data_block.contains_key(&name);
+
+        match data_block.get_mut(&name) {
+            Some(values) => values.push(value),
+            None => {
+                data_block.items.insert(name, vec![value]);
+            }
         }
 
-        Some(())
+        if is_duplicate {
+            self.error(CifErrorKind::DuplicateDataName, &lexeme);
+        }
     }
 
-    fn handle_if_data_item(&mut self) -> Option<()> {
-        if self.local_flags.is_data_item {
-            let string = String::from_utf8_lossy(self.chunk.unwrap()).to_string();
+    fn handle_loop(&mut self, lexeme: Lexeme<'a>) {
+        let mut names = Vec::new();
 
-            self.temp_data.names.push(string);
+        while let Some(name) = self.next_tag() {
+            names.push(name);
+        }
+
+        let mut values = Vec::new();
 
-            return None;
+        while let Some(value) = self.next_value() {
+            values.push(value);
         }
 
-        Some(())
-    }
+        if names.is_empty() {
+            self.error(CifErrorKind::LoopWithoutDataNames, &lexeme);
+            return;
+        }
 
-    fn handle_is_start_data_block(&mut self) -> Option<()> {
-        if self.global_flags.is_new_line && self.chunk.unwrap().starts_with(b"data_") {
-            log::debug!(
-                "Found new data block: {:?}",
-                String::from_utf8_lossy(self.chunk.unwrap())
+        if values.len() % names.len() != 0 {
+            self.error(
+                CifErrorKind::LoopValueCount {
+                    data_names: names.len(),
+                    values: values.len(),
+                },
+                &lexeme,
             );
+        }
 
-            let string = String::from_utf8_lossy(self.chunk.unwrap()).to_string();
+        let Some(data_block) = self.current_block_mut(&lexeme) else {
+            return;
+        };
 
-            let data_block_name = string.trim_start_matches("data_").trim();
+        let is_duplicate = names.iter().any(|name| data_block.contains_key(name));
 
-            self.data_blocks
-                .insert(data_block_name.to_string(), DataBlock::default());
+        for name in &names {
+            if !data_block.contains_key(name) {
+                data_block.items.insert(name.clone(), Vec::new());
+            }
+        }
 
-            self.current_block = Some(data_block_name.to_string());
+        for (index, value) in values.into_iter().enumerate() {
+            data_block
+                .get_mut(&names[index % names.len()])
+                .unwrap()
+                .push(value);
+        }
 
-            return None;
+        data_block.push_loop_names(names);
+
+        if is_duplicate {
+            self.error(CifErrorKind::DuplicateDataName, &lexeme);
         }
+    }
+}
 
-        Some(())
+fn value_from_token(value: &[u8], quoting: Quoting) -> CifValue {
+    let value = String::from_utf8_lossy(value);
+
+    match quoting {
+        Quoting::None => CifValue::from_unquoted(&value),
+        Quoting::TextField => CifValue::Text(read_text_field(&value)),
+        _ => CifValue::Text(value.to_string()),
     }
+}
 
-    fn handle_if_value_chunk(&mut self) -> Option<()> {
-        if !self.local_flags.is_data_item && !self.local_flags.is_loop_chunk {
-            let string = String::from_utf8_lossy(self.chunk.unwrap()).to_string();
+/// Low level event of a CIF file, borrowing from the input where it is valid UTF-8
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<'a> {
+    /// `data_<name>`
+    DataBlock(Cow<'a, str>),
+    /// `save_<name>`, or an empty name for the closing `save_`
+    SaveFrame(Cow<'a, str>),
+    /// `loop_`, followed by the [`Event::Tag`]s of the loop and then its values
+    LoopStart,
+    Global,
+    Stop,
+    /// Data name including the leading `_`
+    Tag(Cow<'a, str>),
+    /// Value without its delimiters. Text fields are not unfolded.
+    Value(Cow<'a, str>, Quoting),
+    /// Comment text without the leading `#`
+    Comment(Cow<'a, str>),
+    /// CIF 2.0 `[`
+    ListStart,
+    /// CIF 2.0 `]`
+    ListEnd,
+    /// CIF 2.0 `{`
+    TableStart,
+    /// CIF 2.0 `}`
+    TableEnd,
+    /// CIF 2.0 table key, followed by the value of the entry
+    TableKey(Cow<'a, str>),
+}
 
-            self.temp_data.values.push(string);
+impl Event<'_> {
+    /// Typed value of an [`Event::Value`], read like the values of [`Parser::parse`]
+    pub fn to_value(&self) -> Option<CifValue> {
+        match self {
+            Event::Value(value, quoting) => Some(value_from_token(value.as_bytes(), *quoting)),
+            _ => None,
         }
-
-        Some(())
     }
+}
 
-    fn update_data_from_temp_data(&mut self) -> Option<()> {
-        if !self.temp_data.names.is_empty()
-            && (self.temp_data.names.len() == self.temp_data.values.len())
-        {
-            for (name, value) in self
-                .temp_data
-                .names
-                .iter()
-                .zip(self.temp_data.values.iter())
-            {
-                self.data_blocks
-                    .get_mut(self.current_block.as_ref().unwrap().as_str())
-                    .unwrap()
-                    .entry(name.clone())
-                    .or_default()
-                    .push(value.clone());
-            }
+/// Pull parser over the [`Event`]s of a file. Unlike [`Parser::parse`] nothing is collected,
+/// and the grammar of data blocks and loops is left to the consumer.
+pub struct Events<'a> {
+    lexer: Lexer<'a>,
+    position: Position,
+}
 
-            self.temp_data.values.clear();
+impl<'a> Events<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            lexer: Lexer::new(bytes),
+            position: Position::default(),
+        }
+    }
 
-            if self.global_flags.is_loop {
-                self.values_cleared_this_loop += 1;
-            }
+    pub fn version(&self) -> CifVersion {
+        self.lexer.version()
+    }
 
-            if !self.global_flags.is_loop || self.local_flags.is_loop_chunk {
-                self.temp_data.names.clear();
-            }
-        }
+    /// Position of the last returned event
+    pub fn position(&self) -> Position {
+        self.position
+    }
 
-        Some(())
+    /// Syntax errors of the tokens found since the last call, e.g. unterminated strings
+    pub fn take_errors(&mut self) -> Vec<CifError> {
+        self.lexer.take_errors()
+    }
+}
+
+impl<'a> Iterator for Events<'a> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let lexeme = self.lexer.next()?;
+
+        self.position = lexeme.position;
+
+        let text = String::from_utf8_lossy;
+
+        Some(match lexeme.token {
+            Token::DataBlock(name) => Event::DataBlock(text(name)),
+            Token::SaveFrame(name) => Event::SaveFrame(text(name)),
+            Token::Loop => Event::LoopStart,
+            Token::Global => Event::Global,
+            Token::Stop => Event::Stop,
+            Token::Tag(name) => Event::Tag(text(name)),
+            Token::Value(value, quoting) => Event::Value(text(value), quoting),
+            Token::Comment(comment) => Event::Comment(text(comment)),
+            Token::ListStart => Event::ListStart,
+            Token::ListEnd => Event::ListEnd,
+            Token::TableStart => Event::TableStart,
+            Token::TableEnd => Event::TableEnd,
+            Token::TableKey(key) => Event::TableKey(text(key)),
+        })
     }
 }
 
 #[derive(Debug, Default, Clone)]
-pub struct DataBlock(pub BTreeMap<String, Vec<String>>);
+pub struct DataBlock {
+    items: IndexMap<String, Vec<CifValue>>,
+    /// Data names of every `loop_`, in the order they were declared
+    loops: Vec<Vec<String>>,
+    /// `save_<name>` frames, which hold items and loops like a data block
+    save_frames: IndexMap<String, DataBlock>,
+}
 
 impl DataBlock {
     pub fn try_into_phase(&self) -> anyhow::Result<Phase> {
         Phase::try_from(self).context("Failed to parse phase")
     }
 
+    /// Like [`DataBlock::try_into_phase`], but also returns the standard uncertainties of the values
+    pub fn try_into_phase_with_uncertainties(
+        &self,
+    ) -> anyhow::Result<(Phase, crate::phase::PhaseUncertainties)> {
+        crate::phase::phase_with_uncertainties(self).context("Failed to parse phase")
+    }
+
+    /// Like [`DataBlock::try_into_phase`], but also returns the Wyckoff positions of the atoms in
+    /// the order of `Phase::atoms`, see [`DataBlock::wyckoff_positions`]
+    #[cfg(feature = "symmetry")]
+    pub fn try_into_phase_with_wyckoff_positions(
+        &self,
+    ) -> anyhow::Result<(Phase, Vec<crate::symmetry::WyckoffPosition>)> {
+        let phase = self.try_into_phase()?;
+
+        let positions = self
+            .wyckoff_positions()
+            .context("Failed to assign Wyckoff positions")?;
+
+        Ok((phase, positions))
+    }
+
+    /// Operations of the `_space_group_symop_operation_xyz` or `_symmetry_equiv_pos_as_xyz` loop,
+    /// else generated from the Hall symbol, Hermann–Mauguin symbol or number of the space group
     #[cfg(feature = "symmetry")]
     pub fn symmetry_equiv_pos_as_xyz(
         &self,
@@ -418,49 +539,335 @@ impl DataBlock {
         crate::symmetry::SymmetryEquivPosAsXYZ::try_from(self)
             .context("Failed to parse symmetry equiv")
     }
+
+    /// Phase with every atom of the unit cell in `P 1`, see
+    /// [`crate::symmetry::SymmetryEquivPosAsXYZ::expand_to_p1`]
+    #[cfg(feature = "symmetry")]
+    pub fn try_into_p1_phase(&self, tolerance_angstrom: f64) -> anyhow::Result<Phase> {
+        self.symmetry_equiv_pos_as_xyz()?
+            .expand_to_p1(&self.try_into_phase()?, tolerance_angstrom)
+            .context("Failed to expand phase to P 1")
+    }
+
+    pub fn loops(&self) -> impl Iterator<Item = Loop<'_>> {
+        self.loops.iter().map(|names| Loop {
+            data_block: self,
+            names,
+        })
+    }
+
+    /// Loop that contains the data name `name`
+    pub fn find_loop(&self, name: &str) -> Option<Loop<'_>> {
+        self.loops().find(|loop_| loop_.contains(name))
+    }
+
+    pub fn is_looped(&self, name: &str) -> bool {
+        self.find_loop(name).is_some()
+    }
+
+    /// Values of the data item `name`. Like all data names it is compared without regard to case.
+    pub fn get(&self, name: &str) -> Option<&Vec<CifValue>> {
+        index_of(&self.items, name).map(|index| &self.items[index])
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Vec<CifValue>> {
+        index_of(&self.items, name).map(|index| &mut self.items[index])
+    }
+
+    /// Data name as spelled in the file, together with its values
+    pub fn get_key_value(&self, name: &str) -> Option<(&String, &Vec<CifValue>)> {
+        index_of(&self.items, name).and_then(|index| self.items.get_index(index))
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        index_of(&self.items, name).is_some()
+    }
+
+    /// Save frames in the order of the file
+    pub fn save_frames(&self) -> &IndexMap<String, DataBlock> {
+        &self.save_frames
+    }
+
+    pub fn save_frame(&self, name: &str) -> Option<&DataBlock> {
+        index_of(&self.save_frames, name).map(|index| &self.save_frames[index])
+    }
+
+    pub fn save_frame_mut(&mut self, name: &str) -> Option<&mut DataBlock> {
+        index_of(&self.save_frames, name).map(|index| &mut self.save_frames[index])
+    }
+
+    /// Adds a save frame, replacing any previous save frame of the same name
+    pub fn insert_save_frame(&mut self, name: impl Into<String>, save_frame: DataBlock) {
+        self.save_frames.insert(name.into(), save_frame);
+    }
+
+    /// Adds a single data item, replacing any previous item or loop column of the same name
+    pub fn insert_item(&mut self, name: impl Into<String>, value: impl Into<CifValue>) {
+        let name = name.into();
+
+        self.remove_from_loops(&name);
+
+        match self.get_mut(&name) {
+            Some(values) => *values = vec![value.into()],
+            None => {
+                self.items.insert(name, vec![value.into()]);
+            }
+        }
+    }
+
+    /// Adds a loop from its rows, replacing any previous items or loop columns of the same names
+    pub fn insert_loop(
+        &mut self,
+        names: Vec<String>,
+        rows: Vec<Vec<CifValue>>,
+    ) -> anyhow::Result<()> {
+        if names.is_empty() {
+            return Err(anyhow::anyhow!("Loop has no data names"));
+        }
+
+        if let Some(row) = rows.iter().find(|row| row.len() != names.len()) {
+            return Err(anyhow::anyhow!(
+                "Loop has {} data names but a row has {} values",
+                names.len(),
+                row.len()
+            ));
+        }
+
+        let mut columns = vec![Vec::with_capacity(rows.len()); names.len()];
+
+        for row in rows {
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+
+        for (name, column) in names.iter().zip(columns) {
+            self.remove_from_loops(name);
+
+            // the loop refers to the columns by its own spelling of the data names
+            if let Some(index) = index_of(&self.items, name) {
+                self.items.shift_remove_index(index);
+            }
+
+            self.items.insert(name.clone(), column);
+        }
+
+        self.loops.push(names);
+
+        Ok(())
+    }
+
+    pub(crate) fn push_loop_names(&mut self, names: Vec<String>) {
+        self.loops.push(names);
+    }
+
+    fn remove_from_loops(&mut self, name: &str) {
+        for names in self.loops.iter_mut() {
+            names.retain(|loop_name| !names_match(loop_name, name));
+        }
+
+        self.loops.retain(|names| !names.is_empty());
+    }
+}
+
+impl std::ops::Index<&str> for DataBlock {
+    type Output = Vec<CifValue>;
+
+    /// # Panics
+    ///
+    /// If the data block has no data item `name`
+    fn index(&self, name: &str) -> &Self::Output {
+        self.get(name)
+            .unwrap_or_else(|| panic!("Data name `{}` not found", name))
+    }
 }
 
 impl std::ops::Deref for DataBlock {
-    type Target = BTreeMap<String, Vec<String>>;
+    type Target = IndexMap<String, Vec<CifValue>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.items
     }
 }
 
 impl std::ops::DerefMut for DataBlock {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.items
+    }
+}
+
+/// View of a `loop_` in a [`DataBlock`]. The values are stored as columns in the data block.
+#[derive(Debug, Clone, Copy)]
+pub struct Loop<'a> {
+    data_block: &'a DataBlock,
+    names: &'a [String],
+}
+
+impl<'a> Loop<'a> {
+    /// Data names in the order of the loop header
+    pub fn names(&self) -> &'a [String] {
+        self.names
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.names
+            .iter()
+            .any(|loop_name| names_match(loop_name, name))
+    }
+
+    pub fn column(&self, name: &str) -> Option<&'a [CifValue]> {
+        match self.contains(name) {
+            true => self.data_block.get(name).map(|column| column.as_slice()),
+            false => None,
+        }
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.names
+            .iter()
+            .map(|name| self.data_block.get(name).map_or(0, |column| column.len()))
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn row(&self, index: usize) -> Option<LoopRow<'a>> {
+        (index < self.len()).then_some(LoopRow {
+            loop_: *self,
+            index,
+        })
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = LoopRow<'a>> {
+        let loop_ = *self;
+
+        (0..self.len()).map(move |index| LoopRow { loop_, index })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LoopRow<'a> {
+    loop_: Loop<'a>,
+    index: usize,
+}
+
+impl<'a> LoopRow<'a> {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn get(&self, name: &str) -> Option<&'a CifValue> {
+        self.loop_.column(name)?.get(self.index)
+    }
+
+    /// Values in the order of the loop header. Missing values of incomplete loops are `None`.
+    pub fn values(&self) -> impl Iterator<Item = Option<&'a CifValue>> + '_ {
+        self.loop_.names.iter().map(|name| self.get(name))
     }
 }
 
+/// Data blocks in the order of the file
 #[derive(Debug, Clone)]
-pub struct Cif(BTreeMap<String, DataBlock>);
+pub struct Cif(IndexMap<String, DataBlock>);
 
 impl Cif {
+    /// First data block of the file
+    pub fn first_key_value(&self) -> Option<(&String, &DataBlock)> {
+        self.0.first()
+    }
+
+    pub fn last_key_value(&self) -> Option<(&String, &DataBlock)> {
+        self.0.last()
+    }
+
+    /// Data block with the block code `name`, compared without regard to case
+    pub fn get(&self, name: &str) -> Option<&DataBlock> {
+        index_of(&self.0, name).map(|index| &self.0[index])
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut DataBlock> {
+        index_of(&self.0, name).map(|index| &mut self.0[index])
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        index_of(&self.0, name).is_some()
+    }
+
     pub fn from_bytes(bytes: &[u8]) -> Self {
         read_cif(bytes)
     }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, CifError> {
+        try_read_cif(bytes)
+    }
+
+    /// Reads the data blocks one at a time, so the input is never held in memory as a whole.
+    /// Syntax errors are skipped over like in [`Cif::from_bytes`].
+    pub fn from_reader<R: std::io::Read>(reader: R) -> anyhow::Result<Self> {
+        let mut data_blocks = IndexMap::new();
+
+        for data_block in DataBlockReader::new(std::io::BufReader::new(reader)) {
+            let (name, data_block) = data_block?;
+
+            data_blocks.insert(name, data_block);
+        }
+
+        Ok(Self(data_blocks))
+    }
+
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open `{}`", path.display()))?;
+
+        Self::from_reader(file)
+    }
+}
+
+impl IntoIterator for Cif {
+    type Item = (String, DataBlock);
+    type IntoIter = indexmap::map::IntoIter<String, DataBlock>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
 }
 
 impl std::ops::Deref for Cif {
-    type Target = BTreeMap<String, DataBlock>;
+    type Target = IndexMap<String, DataBlock>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
+/// Data names, block codes and frame codes are case-insensitive. Non-ASCII names of CIF 2.0 are
+/// compared by their lowercase form.
+pub(crate) fn names_match(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b) || (!a.is_ascii() && a.to_lowercase() == b.to_lowercase())
+}
+
+/// Index of the key matching `name`, preferring the exact spelling
+fn index_of<V>(map: &IndexMap<String, V>, name: &str) -> Option<usize> {
+    map.get_index_of(name)
+        .or_else(|| map.keys().position(|key| names_match(key, name)))
+}
+
 pub fn read_cif<'a>(bytes: &'a [u8]) -> Cif {
     let mut parser = Parser::<'a>::new(bytes);
 
     parser.parse()
 }
 
-fn split_input_into_chunks(bytes: &[u8]) -> impl Iterator<Item = &'_ [u8]> {
-    bytes
-        .split_inclusive(|&byte| byte == b'\n' || byte == b'\t' || byte == b' ')
-        .filter(|&chunk| !chunk.is_empty() && chunk != [b' '] && chunk != [b'\t'])
+pub fn try_read_cif<'a>(bytes: &'a [u8]) -> Result<Cif, CifError> {
+    let mut parser = Parser::<'a>::new(bytes);
+
+    parser.try_parse()
 }
 
 pub fn try_phase_from_cif_bytes(bytes: &[u8]) -> Option<(String, Phase)> {
@@ -469,7 +876,7 @@ pub fn try_phase_from_cif_bytes(bytes: &[u8]) -> Option<(String, Phase)> {
     cif.iter()
         .fold(None, |acc: Option<(String, Phase)>, (name, data_block)| {
             if let Some((name, phase)) = acc {
-                return Some((name, phase));
+                Some((name, phase))
             } else {
                 match data_block.try_into_phase() {
                     Ok(phase) => Some((name.clone(), phase)),
@@ -478,3 +885,254 @@ pub fn try_phase_from_cif_bytes(bytes: &[u8]) -> Option<(String, Phase)> {
             }
         })
 }
+
+#[cfg(test)]
+mod test {
+    use super::{read_cif, try_read_cif, DataBlock, Event, Events};
+    use crate::{
+        error::{CifErrorKind, Position},
+        lexer::Quoting,
+        value::CifValue,
+    };
+
+    #[test]
+    fn test_events() {
+        let events = Events::new(b"data_x # note\n_a 1\nloop_\n_b\n'?' ?\n").collect::<Vec<_>>();
+
+        assert_eq!(
+            events,
+            [
+                Event::DataBlock("x".into()),
+                Event::Comment(" note".into()),
+                Event::Tag("_a".into()),
+                Event::Value("1".into(), Quoting::None),
+                Event::LoopStart,
+                Event::Tag("_b".into()),
+                Event::Value("?".into(), Quoting::Single),
+                Event::Value("?".into(), Quoting::None),
+            ]
+        );
+
+        assert_eq!(events[3].to_value(), Some(CifValue::Numeric("1".into())));
+        assert_eq!(events[6].to_value(), Some(CifValue::Text("?".into())));
+        assert_eq!(events[7].to_value(), Some(CifValue::Unknown));
+        assert_eq!(events[0].to_value(), None);
+    }
+
+    #[test]
+    fn test_loop_and_items() {
+        let cif = read_cif(
+            b"data_test\n_name 'O'Brien'\nloop_\n_a\n_b\n1 'x y'\n2 ;z\n_text\n;\nline one\nline two\n;\n",
+        );
+
+        let data_block = cif.get("test").unwrap();
+
+        assert_eq!(data_block["_name"], vec!["O'Brien"]);
+        assert_eq!(data_block["_a"], vec!["1", "2"]);
+        assert_eq!(data_block["_b"], vec!["x y", ";z"]);
+        assert_eq!(data_block["_text"], vec!["line one\nline two"]);
+    }
+
+    #[test]
+    fn test_cif2_compound_values() {
+        let cif = read_cif(
+            b"#\\#CIF_2.0\ndata_test\nloop_\n_list\n_table\n[1 [2 3]] {'a':x \"b\":[]}\n[] {}\n",
+        );
+
+        let data_block = cif.get("test").unwrap();
+
+        let list = data_block["_list"][0].as_list().unwrap();
+
+        assert_eq!(list[0], "1");
+        assert_eq!(list[1].as_list().unwrap(), ["2", "3"]);
+        assert_eq!(data_block["_list"][1], CifValue::List(vec![]));
+
+        let table = data_block["_table"][0].as_table().unwrap();
+
+        assert_eq!(table["a"], "x");
+        assert_eq!(table["b"], CifValue::List(vec![]));
+        assert_eq!(data_block["_table"].len(), 2);
+    }
+
+    #[test]
+    fn test_try_parse_errors() {
+        let error = try_read_cif(b"# header\n_cell_length_a 1.0\ndata_x\n").unwrap_err();
+
+        assert_eq!(error.kind, CifErrorKind::DataOutsideDataBlock);
+        assert_eq!(error.position, Position { line: 2, column: 1 });
+        assert_eq!(error.token, "_cell_length_a");
+        assert_eq!(error.data_block, None);
+
+        let error = try_read_cif(b"data_x\nloop_\n_a\n_b\n1 2 3\n").unwrap_err();
+
+        assert_eq!(
+            error.kind,
+            CifErrorKind::LoopValueCount {
+                data_names: 2,
+                values: 3
+            }
+        );
+        assert_eq!(error.line(), 2);
+        assert_eq!(error.data_block.as_deref(), Some("x"));
+
+        let error = try_read_cif(b"data_x\n_a 'b\n").unwrap_err();
+
+        assert_eq!(error.kind, CifErrorKind::UnterminatedQuotedString);
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: unterminated quoted string at `'b` in data block `x`"
+        );
+
+        assert!(try_read_cif(b"data_x\n_a b\n").is_ok());
+    }
+
+    #[test]
+    fn test_loops() {
+        let cif = read_cif(
+            b"data_x\n_single 1\nloop_\n_site_label\n_site_x\nA 0.1\nB 0.2\nC 0.3\nloop_\n_aniso_label\n_aniso_u\nB 0.01\n",
+        );
+
+        let data_block = cif.get("x").unwrap();
+
+        assert_eq!(data_block.loops().count(), 2);
+        assert!(!data_block.is_looped("_single"));
+
+        let sites = data_block.find_loop("_site_x").unwrap();
+
+        assert_eq!(sites.names(), ["_site_label", "_site_x"]);
+        assert_eq!(sites.len(), 3);
+        assert_eq!(sites.column("_site_label").unwrap(), ["A", "B", "C"]);
+        assert_eq!(sites.column("_aniso_u"), None);
+        assert_eq!(sites.row(1).unwrap().get("_site_x").unwrap(), "0.2");
+
+        let aniso = data_block.find_loop("_aniso_label").unwrap();
+
+        assert_eq!(aniso.len(), 1);
+        assert_eq!(
+            aniso.rows().next().unwrap().values().collect::<Vec<_>>(),
+            [
+                Some(&CifValue::Text("B".into())),
+                Some(&CifValue::Numeric("0.01".into()))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_typed_values() {
+        let cif = read_cif(b"data_x\nloop_\n_v\n? . '?' 1.5(2) \"1.5\" 1/4\n");
+
+        assert_eq!(
+            cif["x"]["_v"],
+            [
+                CifValue::Unknown,
+                CifValue::Inapplicable,
+                CifValue::Text("?".into()),
+                CifValue::Numeric("1.5(2)".into()),
+                CifValue::Text("1.5".into()),
+                CifValue::Text("1/4".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_file_order() {
+        let cif =
+            read_cif(b"data_zeta\n_z 1\n_a 2\nloop_\n_m\n_b\n3 4\ndata_alpha\n_x 1\ndata_mid\n");
+
+        assert_eq!(cif.first_key_value().unwrap().0, "zeta");
+        assert_eq!(cif.last_key_value().unwrap().0, "mid");
+        assert_eq!(cif.keys().collect::<Vec<_>>(), ["zeta", "alpha", "mid"]);
+
+        let data_block = cif.first_key_value().unwrap().1;
+
+        assert_eq!(
+            data_block.keys().collect::<Vec<_>>(),
+            ["_z", "_a", "_m", "_b"]
+        );
+    }
+
+    #[test]
+    fn test_save_frames() {
+        let cif = read_cif(
+            b"data_dic\n_title core\nsave_cell\n_name '_cell'\nloop_\n_ref\na b\nsave_\n_after 1\nsave_open\n_x 1\ndata_next\n",
+        );
+
+        let data_block = cif.get("dic").unwrap();
+
+        assert_eq!(data_block.keys().collect::<Vec<_>>(), ["_title", "_after"]);
+        assert_eq!(
+            data_block.save_frames().keys().collect::<Vec<_>>(),
+            ["cell", "open"]
+        );
+
+        let save_frame = data_block.save_frame("cell").unwrap();
+
+        assert_eq!(save_frame["_name"], vec!["_cell"]);
+        assert_eq!(save_frame.find_loop("_ref").unwrap().len(), 2);
+        assert_eq!(data_block.save_frame("open").unwrap()["_x"], vec!["1"]);
+
+        let error = try_read_cif(b"data_dic\nsave_open\n_x 1\n").unwrap_err();
+
+        assert_eq!(error.kind, CifErrorKind::UnterminatedSaveFrame);
+        assert_eq!(error.position, Position { line: 2, column: 1 });
+
+        let error = try_read_cif(b"data_dic\n_x 1\nsave_\n").unwrap_err();
+
+        assert_eq!(error.kind, CifErrorKind::UnexpectedToken);
+    }
+
+    #[test]
+    fn test_case_insensitive_names() {
+        let cif = read_cif(b"data_Test\n_Cell_Length_A 4.0\nloop_\n_Atom_Site_Label\nBa1\nsave_Frame\n_x 1\nsave_\n");
+
+        let data_block = cif.get("TEST").unwrap();
+
+        assert!(cif.contains_key("test"));
+        assert_eq!(data_block["_cell_length_a"], vec!["4.0"]);
+        assert_eq!(
+            data_block.get_key_value("_CELL_LENGTH_A").unwrap().0,
+            "_Cell_Length_A"
+        );
+        assert!(data_block.is_looped("_atom_site_label"));
+        assert_eq!(
+            data_block.find_loop("_atom_site_label").unwrap().names(),
+            ["_Atom_Site_Label"]
+        );
+        assert!(data_block.save_frame("frame").is_some());
+
+        let error = try_read_cif(b"data_a\n_x 1\n_X 2\ndata_A\n").unwrap_err();
+
+        assert_eq!(error.kind, CifErrorKind::DuplicateDataName);
+
+        let mut data_block = DataBlock::default();
+
+        data_block.insert_item("_Title", "a");
+        data_block.insert_item("_title", "b");
+
+        assert_eq!(data_block.keys().collect::<Vec<_>>(), ["_Title"]);
+        assert_eq!(data_block["_TITLE"], vec!["b"]);
+    }
+
+    #[test]
+    fn test_insert_loop() {
+        let mut data_block = DataBlock::default();
+
+        data_block.insert_item("_a", "1");
+        data_block
+            .insert_loop(
+                vec!["_a".to_string(), "_b".to_string()],
+                vec![vec!["2".into(), "3".into()], vec!["4".into(), "5".into()]],
+            )
+            .unwrap();
+
+        assert_eq!(data_block["_a"], ["2", "4"]);
+        assert_eq!(data_block.find_loop("_b").unwrap().len(), 2);
+
+        data_block.insert_item("_b", "6");
+
+        assert_eq!(data_block.find_loop("_a").unwrap().names(), ["_a"]);
+        assert!(data_block
+            .insert_loop(vec!["_c".to_string()], vec![vec![]])
+            .is_err());
+    }
+}
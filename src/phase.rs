@@ -1,7 +1,16 @@
 use anyhow::Context;
 use crystallib::{AdpType, Atom, Atoms, Cell, IntoSpaceGroupNumber, IntoSpaceGroupSymbol, Phase};
 
-use crate::{parse::GetAndParse, parser::DataBlock};
+use crate::{
+    measured::Measured,
+    parse::GetAndParse,
+    parser::DataBlock,
+    value::CifValue,
+    writer::{write_data_block_to, write_header, WriteOptions},
+};
+
+#[cfg(feature = "symmetry")]
+use crate::symmetry::SpaceGroupSetting;
 
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -20,6 +29,44 @@ pub struct Uaniso {
     pub u23: f64,
 }
 
+/// Standard uncertainties of the cell parameters, `None` where the file gives none
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CellUncertainties {
+    pub a: Option<f64>,
+    pub b: Option<f64>,
+    pub c: Option<f64>,
+    pub alpha: Option<f64>,
+    pub beta: Option<f64>,
+    pub gamma: Option<f64>,
+    pub volume: Option<f64>,
+}
+
+/// Standard uncertainties of the atom site parameters, `None` where the file gives none
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AtomUncertainties {
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    pub z: Option<f64>,
+    pub occupancy: Option<f64>,
+    pub u_iso_or_equiv: Option<f64>,
+    pub u11: Option<f64>,
+    pub u22: Option<f64>,
+    pub u33: Option<f64>,
+    pub u12: Option<f64>,
+    pub u13: Option<f64>,
+    pub u23: Option<f64>,
+}
+
+/// Standard uncertainties of a [`Phase`]. `atoms` has the same order as `Phase::atoms`.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PhaseUncertainties {
+    pub cell: CellUncertainties,
+    pub atoms: Vec<AtomUncertainties>,
+}
+
 impl TryFrom<&DataBlock> for Phase {
     type Error = anyhow::Error;
 
@@ -31,163 +78,484 @@ impl TryFrom<&DataBlock> for Phase {
     }
 }
 
+pub(crate) fn phase_with_uncertainties(
+    map: &DataBlock,
+) -> anyhow::Result<(Phase, PhaseUncertainties)> {
+    let (cell, cell_uncertainties) =
+        cell_with_uncertainties(map).context("Failed to parse cell")?;
+    let (atoms, atom_uncertainties) =
+        atoms_with_uncertainties(map).context("Failed to parse atoms")?;
+
+    Ok((
+        Phase { cell, atoms },
+        PhaseUncertainties {
+            cell: cell_uncertainties,
+            atoms: atom_uncertainties,
+        },
+    ))
+}
+
 impl TryFrom<&DataBlock> for Cell {
     type Error = anyhow::Error;
 
     fn try_from(map: &DataBlock) -> anyhow::Result<Self> {
-        let values = [
-            "_cell_length_a",
-            "_cell_length_b",
-            "_cell_length_c",
-            "_cell_angle_alpha",
-            "_cell_angle_beta",
-            "_cell_angle_gamma",
-            "_cell_volume",
-        ]
-        .map(|key| map.get_and_parse_first::<f64>(key))
-        .into_iter()
-        .collect::<Result<Vec<f64>, _>>()?;
+        cell_with_uncertainties(map).map(|(cell, _)| cell)
+    }
+}
 
-        let mut space_group = map
-            .get_and_parse_first::<String>("_symmetry_space_group_name_H-M")
-            .ok();
+/// Data names of the Hermann–Mauguin symbol of the space group, in the order they are looked up
+pub(crate) const SPACE_GROUP_SYMBOL_NAMES: [&str; 2] = [
+    "_symmetry_space_group_name_H-M",
+    "_space_group_name_H-M_alt",
+];
+
+/// Data names of the space group number, in the order they are looked up
+pub(crate) const SPACE_GROUP_NUMBER_NAMES: [&str; 2] =
+    ["_symmetry_Int_Tables_number", "_space_group_IT_number"];
+
+/// First value of the first data item in `names` that parses as `T`
+pub(crate) fn first_parsed<T: std::str::FromStr>(map: &DataBlock, names: [&str; 2]) -> Option<T>
+where
+    Result<T, T::Err>: Context<T, T::Err>,
+    T::Err: Send + Sync + 'static,
+{
+    names
+        .into_iter()
+        .find_map(|name| map.get_and_parse_first::<T>(name).ok())
+}
 
-        if space_group.is_none() {
-            space_group = map
-                .get_and_parse_first::<String>("_space_group_name_H-M_alt")
-                .ok();
-        }
+fn cell_with_uncertainties(map: &DataBlock) -> anyhow::Result<(Cell, CellUncertainties)> {
+    let values = [
+        "_cell_length_a",
+        "_cell_length_b",
+        "_cell_length_c",
+        "_cell_angle_alpha",
+        "_cell_angle_beta",
+        "_cell_angle_gamma",
+        "_cell_volume",
+    ]
+    .map(|key| map.get_and_parse_first::<Measured>(key))
+    .into_iter()
+    .collect::<Result<Vec<Measured>, _>>()?;
+
+    let mut space_group = first_parsed::<String>(map, SPACE_GROUP_SYMBOL_NAMES);
+
+    let mut space_group_number = first_parsed::<u8>(map, SPACE_GROUP_NUMBER_NAMES);
+
+    if space_group_number.is_none() && space_group.is_none() {
+        return Err(anyhow::anyhow!(
+            "Could not find space group symbol or number in the data block"
+        ));
+    }
 
-        let mut space_group_number = map
-            .get_and_parse_first::<u8>("_symmetry_Int_Tables_number")
-            .ok();
+    if space_group_number.is_none() {
+        let symbol = space_group.clone().unwrap();
 
-        if space_group_number.is_none() {
-            space_group_number = map.get_and_parse_first::<u8>("_space_group_IT_number").ok();
-        }
-
-        if space_group_number.is_none() && space_group.is_none() {
-            return Err(anyhow::anyhow!(
-                "Could not find space group symbol or number in the data block"
-            ));
+        #[cfg(feature = "symmetry")]
+        {
+            space_group_number =
+                SpaceGroupSetting::from_hermann_mauguin(&symbol).map(|setting| setting.number);
         }
 
         if space_group_number.is_none() {
             space_group_number = Some(
-                space_group
-                    .clone()
-                    .unwrap()
+                symbol
                     .into_space_group_number()
                     .context("Failed to convert space group symbol to space group number")?,
             );
         }
+    }
+
+    if space_group.is_none() {
+        let number = space_group_number.unwrap();
+
+        #[cfg(feature = "symmetry")]
+        {
+            space_group = SpaceGroupSetting::from_number(number)
+                .map(|setting| setting.hermann_mauguin.to_string());
+        }
 
         if space_group.is_none() {
             space_group = Some(
-                space_group_number
-                    .unwrap()
+                number
                     .into_space_group_symbol()
                     .context("Failed to convert space group number to space group name")?
                     .to_string(),
             );
         }
-
-        let space_group = space_group.unwrap();
-        let space_group_number = space_group_number.unwrap();
-
-        Ok(Self {
-            a: values[0],
-            b: values[1],
-            c: values[2],
-            alpha: values[3],
-            beta: values[4],
-            gamma: values[5],
-            volume: values[6],
-            space_group,
-            space_group_number,
-        })
     }
+
+    let space_group = space_group.unwrap();
+    let space_group_number = space_group_number.unwrap();
+
+    let cell = Cell {
+        a: values[0].value,
+        b: values[1].value,
+        c: values[2].value,
+        alpha: values[3].value,
+        beta: values[4].value,
+        gamma: values[5].value,
+        volume: values[6].value,
+        space_group,
+        space_group_number,
+    };
+
+    let uncertainties = CellUncertainties {
+        a: values[0].su,
+        b: values[1].su,
+        c: values[2].su,
+        alpha: values[3].su,
+        beta: values[4].su,
+        gamma: values[5].su,
+        volume: values[6].su,
+    };
+
+    Ok((cell, uncertainties))
 }
 
 impl TryFrom<&DataBlock> for Atoms {
     type Error = anyhow::Error;
 
     fn try_from(map: &DataBlock) -> anyhow::Result<Self> {
-        let label = map.get_and_parse_all::<String>("_atom_site_label")?;
-        let type_ = map.get_and_parse_all::<String>("_atom_site_type_symbol")?;
+        atoms_with_uncertainties(map).map(|(atoms, _)| atoms)
+    }
+}
+
+fn atoms_with_uncertainties(map: &DataBlock) -> anyhow::Result<(Atoms, Vec<AtomUncertainties>)> {
+    let label = map.get_and_parse_all::<String>("_atom_site_label")?;
+    let type_ = map.get_and_parse_all::<String>("_atom_site_type_symbol")?;
+
+    let x = map.get_and_parse_all::<Measured>("_atom_site_fract_x")?;
+    let y = map.get_and_parse_all::<Measured>("_atom_site_fract_y")?;
+    let z = map.get_and_parse_all::<Measured>("_atom_site_fract_z")?;
+
+    let occupancy = map.get_and_parse_all_optional::<Measured>("_atom_site_occupancy")?;
+
+    let mut multiplicity =
+        map.get_and_parse_all_optional::<f64>("_atom_site_symmetry_multiplicity");
+
+    if multiplicity.is_err() {
+        multiplicity =
+            map.get_and_parse_all_optional::<f64>("_atom_site_site_symmetry_multiplicity");
+    }
+
+    let multiplicity = multiplicity.ok();
+
+    // without the column, or with unknown values, the multiplicity follows from the operations
+    #[cfg(feature = "symmetry")]
+    let operations = match &multiplicity {
+        Some(multiplicity) if multiplicity.iter().all(Option::is_some) => None,
+        _ => crate::symmetry::SymmetryEquivPosAsXYZ::try_from(map).ok(),
+    };
+
+    let u_iso_or_equiv = map
+        .get_and_parse_all_optional::<Measured>("_atom_site_U_iso_or_equiv")
+        .unwrap_or_else(|_| {
+            map.get_and_parse_all_optional::<Measured>("_atom_site_B_iso_or_equiv")
+                .unwrap_or_default()
+                .into_iter()
+                .map(|b_iso| {
+                    b_iso.map(|b_iso| Measured {
+                        value: convert_b_iso_to_u_iso(b_iso.value),
+                        su: b_iso.su.map(convert_b_iso_to_u_iso),
+                    })
+                })
+                .collect::<Vec<Option<Measured>>>()
+        });
+
+    let adp_type = map
+        .get_and_parse_all_optional::<AdpType>("_atom_site_adp_type")
+        .unwrap_or(vec![None; label.len()]);
+
+    let [u11, u22, u33, u12, u13, u23] = [
+        "_atom_site_aniso_U_11",
+        "_atom_site_aniso_U_22",
+        "_atom_site_aniso_U_33",
+        "_atom_site_aniso_U_12",
+        "_atom_site_aniso_U_13",
+        "_atom_site_aniso_U_23",
+    ]
+    .map(|key| {
+        map.get_and_parse_all_optional::<Measured>(key)
+            .unwrap_or_default()
+    });
+
+    // the aniso loop usually only lists a subset of the atoms
+    let aniso_label = map
+        .get_and_parse_all::<String>("_atom_site_aniso_label")
+        .ok();
+
+    let mut atoms = Vec::new();
+    let mut uncertainties = Vec::new();
+
+    for (index, label) in label.into_iter().enumerate() {
+        let aniso_index = match &aniso_label {
+            Some(aniso_label) => aniso_label
+                .iter()
+                .position(|aniso_label| aniso_label == &label),
+            None => Some(index),
+        };
+
+        let aniso = |values: &Vec<Option<Measured>>| {
+            aniso_index
+                .and_then(|index| values.get(index).copied().flatten())
+                .unwrap_or_default()
+        };
+
+        let occupancy = occupancy[index];
+
+        let site_multiplicity = multiplicity.as_ref().and_then(|m| m[index]);
+
+        #[cfg(feature = "symmetry")]
+        let site_multiplicity = site_multiplicity.or_else(|| {
+            operations
+                .as_ref()?
+                .site_symmetry(
+                    [x[index].value, y[index].value, z[index].value],
+                    crate::symmetry::DEFAULT_SITE_TOLERANCE_FRACTIONAL,
+                )
+                .ok()
+                .map(|site| site.multiplicity as f64)
+        });
+
+        let u_iso_or_equiv = u_iso_or_equiv.get(index).copied().flatten();
+
+        let atom = Atom {
+            label,
+            type_: type_[index].clone(),
+            x: x[index].value,
+            y: y[index].value,
+            z: z[index].value,
+            // the dictionary default for the occupancy is 1
+            occupancy: occupancy.map_or(1.0, |occupancy| occupancy.value),
+            multiplicity: site_multiplicity,
+            adp_type: adp_type
+                .get(index)
+                .cloned()
+                .flatten()
+                .unwrap_or(AdpType::Uiso),
+            u_iso_or_equiv: u_iso_or_equiv.unwrap_or_default().value,
+            u11: aniso(&u11).value,
+            u22: aniso(&u22).value,
+            u33: aniso(&u33).value,
+            u12: aniso(&u12).value,
+            u13: aniso(&u13).value,
+            u23: aniso(&u23).value,
+        };
+
+        atoms.push(atom);
+
+        uncertainties.push(AtomUncertainties {
+            x: x[index].su,
+            y: y[index].su,
+            z: z[index].su,
+            occupancy: occupancy.and_then(|occupancy| occupancy.su),
+            u_iso_or_equiv: u_iso_or_equiv.and_then(|u_iso| u_iso.su),
+            u11: aniso(&u11).su,
+            u22: aniso(&u22).su,
+            u33: aniso(&u33).su,
+            u12: aniso(&u12).su,
+            u13: aniso(&u13).su,
+            u23: aniso(&u23).su,
+        });
+    }
+
+    Ok((Atoms(atoms), uncertainties))
+}
 
-        let x = map.get_and_parse_all::<f64>("_atom_site_fract_x")?;
-        let y = map.get_and_parse_all::<f64>("_atom_site_fract_y")?;
-        let z = map.get_and_parse_all::<f64>("_atom_site_fract_z")?;
+impl DataBlock {
+    /// Cell, space group, `_atom_site_*` loop and, for atoms with [`AdpType::Uani`], the
+    /// `_atom_site_aniso_*` loop of `phase`. With the `symmetry` feature the operations of the
+    /// space group are added as the `_space_group_symop_*` loop.
+    pub fn from_phase(phase: &Phase) -> Self {
+        let mut data_block = DataBlock::default();
+
+        let cell = &phase.cell;
+
+        for (name, value) in [
+            ("_cell_length_a", cell.a),
+            ("_cell_length_b", cell.b),
+            ("_cell_length_c", cell.c),
+            ("_cell_angle_alpha", cell.alpha),
+            ("_cell_angle_beta", cell.beta),
+            ("_cell_angle_gamma", cell.gamma),
+            ("_cell_volume", cell.volume),
+        ] {
+            data_block.insert_item(name, number(value));
+        }
+
+        data_block.insert_item("_space_group_name_H-M_alt", cell.space_group.as_str());
+        data_block.insert_item(
+            "_space_group_IT_number",
+            CifValue::Numeric(cell.space_group_number.to_string()),
+        );
+
+        #[cfg(feature = "symmetry")]
+        match SpaceGroupSetting::from_hermann_mauguin(&cell.space_group)
+            .or_else(|| SpaceGroupSetting::from_number(cell.space_group_number))
+        {
+            Some(setting) => data_block.insert_symmetry_operations(
+                setting
+                    .operations()
+                    .0
+                    .iter()
+                    .map(|operation| operation.to_string()),
+            ),
+            None => log::warn!(
+                "No symmetry operations for the space group `{}`",
+                cell.space_group
+            ),
+        }
 
-        let occupancy = map.get_and_parse_all::<f64>("_atom_site_occupancy")?;
+        let has_multiplicity = phase.atoms.iter().any(|atom| atom.multiplicity.is_some());
+
+        let mut names = vec![
+            "_atom_site_label",
+            "_atom_site_type_symbol",
+            "_atom_site_fract_x",
+            "_atom_site_fract_y",
+            "_atom_site_fract_z",
+            "_atom_site_occupancy",
+        ];
+
+        if has_multiplicity {
+            names.push("_atom_site_symmetry_multiplicity");
+        }
 
-        let mut multiplicity = map.get_and_parse_all::<f64>("_atom_site_symmetry_multiplicity");
+        names.extend(["_atom_site_adp_type", "_atom_site_U_iso_or_equiv"]);
 
-        if multiplicity.is_err() {
-            multiplicity = map.get_and_parse_all::<f64>("_atom_site_site_symmetry_multiplicity");
+        let rows = phase
+            .atoms
+            .iter()
+            .map(|atom| {
+                let mut row = vec![
+                    CifValue::from(atom.label.as_str()),
+                    CifValue::from(atom.type_.as_str()),
+                    number(atom.x),
+                    number(atom.y),
+                    number(atom.z),
+                    number(atom.occupancy),
+                ];
+
+                if has_multiplicity {
+                    row.push(atom.multiplicity.map_or(CifValue::Unknown, number));
+                }
+
+                row.extend([
+                    CifValue::from(adp_type_code(&atom.adp_type)),
+                    number(atom.u_iso_or_equiv),
+                ]);
+
+                row
+            })
+            .collect::<Vec<_>>();
+
+        if !rows.is_empty() {
+            data_block
+                .insert_loop(names.into_iter().map(String::from).collect(), rows)
+                .expect("every row has a value for each data name");
         }
 
-        let multiplicity = multiplicity.ok();
-
-        let u_iso_or_equiv = map
-            .get_and_parse_all::<f64>("_atom_site_U_iso_or_equiv")
-            .unwrap_or_else(|_| {
-                map.get_and_parse_all::<f64>("_atom_site_B_iso_or_equiv")
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|b_iso| convert_b_iso_to_u_iso(b_iso))
-                    .collect::<Vec<f64>>()
-            });
-
-        let adp_type = map
-            .get_and_parse_all::<AdpType>("_atom_site_adp_type")
-            .unwrap_or(vec![AdpType::Uiso; label.len()]);
-
-        let u11 = map
-            .get_and_parse_all::<f64>("_atom_site_aniso_U_11")
-            .unwrap_or_default();
-        let u22 = map
-            .get_and_parse_all::<f64>("_atom_site_aniso_U_22")
-            .unwrap_or_default();
-        let u33 = map
-            .get_and_parse_all::<f64>("_atom_site_aniso_U_33")
-            .unwrap_or_default();
-        let u12 = map
-            .get_and_parse_all::<f64>("_atom_site_aniso_U_12")
-            .unwrap_or_default();
-        let u13 = map
-            .get_and_parse_all::<f64>("_atom_site_aniso_U_13")
-            .unwrap_or_default();
-        let u23 = map
-            .get_and_parse_all::<f64>("_atom_site_aniso_U_23")
-            .unwrap_or_default();
-
-        let mut atoms = Vec::new();
-
-        for (index, label) in label.into_iter().enumerate() {
-            let atom = Atom {
-                label,
-                type_: type_[index].clone(),
-                x: x[index],
-                y: y[index],
-                z: z[index],
-                occupancy: occupancy[index],
-                multiplicity: multiplicity.as_ref().map(|m| m[index]),
-                adp_type: adp_type[index].clone(),
-                u_iso_or_equiv: u_iso_or_equiv.get(index).cloned().unwrap_or_default(),
-                u11: u11.get(index).cloned().unwrap_or_default(),
-                u22: u22.get(index).cloned().unwrap_or_default(),
-                u33: u33.get(index).cloned().unwrap_or_default(),
-                u12: u12.get(index).cloned().unwrap_or_default(),
-                u13: u13.get(index).cloned().unwrap_or_default(),
-                u23: u23.get(index).cloned().unwrap_or_default(),
-            };
-
-            atoms.push(atom);
+        let aniso_rows = phase
+            .atoms
+            .iter()
+            .filter(|atom| atom.adp_type == AdpType::Uani)
+            .map(|atom| {
+                vec![
+                    CifValue::from(atom.label.as_str()),
+                    number(atom.u11),
+                    number(atom.u22),
+                    number(atom.u33),
+                    number(atom.u12),
+                    number(atom.u13),
+                    number(atom.u23),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        if !aniso_rows.is_empty() {
+            let names = [
+                "_atom_site_aniso_label",
+                "_atom_site_aniso_U_11",
+                "_atom_site_aniso_U_22",
+                "_atom_site_aniso_U_33",
+                "_atom_site_aniso_U_12",
+                "_atom_site_aniso_U_13",
+                "_atom_site_aniso_U_23",
+            ];
+
+            data_block
+                .insert_loop(names.map(String::from).to_vec(), aniso_rows)
+                .expect("every row has a value for each data name");
         }
 
-        Ok(Self(atoms))
+        data_block
+    }
+
+    /// Adds the `_space_group_symop_*` loop with operations like `-y,x-y,z`, replacing any previous one
+    pub fn insert_symmetry_operations<S: Into<String>>(
+        &mut self,
+        operations: impl IntoIterator<Item = S>,
+    ) {
+        let rows = operations
+            .into_iter()
+            .enumerate()
+            .map(|(index, operation)| {
+                vec![
+                    CifValue::Numeric((index + 1).to_string()),
+                    CifValue::Text(operation.into()),
+                ]
+            })
+            .collect();
+
+        self.insert_loop(
+            vec![
+                "_space_group_symop_id".to_string(),
+                "_space_group_symop_operation_xyz".to_string(),
+            ],
+            rows,
+        )
+        .expect("every row has a value for each data name");
+    }
+}
+
+impl From<&Phase> for DataBlock {
+    fn from(phase: &Phase) -> Self {
+        DataBlock::from_phase(phase)
+    }
+}
+
+/// CIF 1.1 text of `phase` as a single data block, see [`DataBlock::from_phase`]
+pub fn write_phase(name: &str, phase: &Phase) -> String {
+    let mut text = String::new();
+    let options = WriteOptions::default();
+
+    write_header(&mut text, &options).expect("writing to a String never fails");
+    text.push('\n');
+    write_data_block_to(&mut text, name, &DataBlock::from_phase(phase), &options)
+        .expect("writing to a String never fails");
+
+    text
+}
+
+/// `?` for NaN and infinities, which are no CIF numbers
+fn number(value: f64) -> CifValue {
+    match value.is_finite() {
+        true => CifValue::Numeric(value.to_string()),
+        false => CifValue::Unknown,
+    }
+}
+
+/// Code of `_atom_site_adp_type`
+fn adp_type_code(adp_type: &AdpType) -> &'static str {
+    match adp_type {
+        AdpType::Uiso => "Uiso",
+        AdpType::Uani => "Uani",
+        AdpType::Uovl => "Uovl",
+        AdpType::Umpe => "Umpe",
+        AdpType::Biso => "Biso",
+        AdpType::Bani => "Bani",
+        AdpType::Bovl => "Bovl",
     }
 }
 
@@ -198,7 +566,9 @@ fn convert_b_iso_to_u_iso(b_iso: f64) -> f64 {
 
 #[cfg(test)]
 mod test {
-    use crate::Parser;
+    use crystallib::{AdpType, Phase};
+
+    use crate::{read_cif, CifValue, DataBlock, Parser};
 
     #[test]
     fn test_b_iso_to_u_iso() {
@@ -234,4 +604,60 @@ mod test {
         assert!(atoms[0] - expected_u_isos[0] < 1e-3);
         assert!(atoms[1] - expected_u_isos[1] < 1e-3);
     }
+
+    #[test]
+    fn test_from_phase() {
+        let bytes = std::fs::read(r"assets\BaTiO3.cif").unwrap();
+
+        let mut phase: Phase = read_cif(&bytes)
+            .first_key_value()
+            .unwrap()
+            .1
+            .try_into()
+            .unwrap();
+
+        phase.atoms[1].adp_type = AdpType::Uani;
+        phase.atoms[1].u11 = 0.01;
+        phase.atoms[1].u12 = -0.002;
+
+        let mut data_block = DataBlock::from_phase(&phase);
+
+        assert_eq!(data_block["_atom_site_aniso_label"], vec!["Ti1"]);
+        assert_eq!(data_block["_atom_site_adp_type"][1], "Uani");
+
+        #[cfg(feature = "symmetry")]
+        {
+            let operations = &data_block["_space_group_symop_operation_xyz"];
+
+            assert_eq!(operations.len(), 48);
+            assert_eq!(operations[0], "x,y,z");
+            assert!(operations.iter().any(|operation| operation == "-y,x,z"));
+        }
+
+        let u_iso_or_equiv = phase.atoms[2].u_iso_or_equiv;
+        phase.atoms[2].u_iso_or_equiv = f64::NAN;
+
+        assert_eq!(
+            DataBlock::from_phase(&phase)["_atom_site_U_iso_or_equiv"][2],
+            CifValue::Unknown
+        );
+
+        phase.atoms[2].u_iso_or_equiv = u_iso_or_equiv;
+
+        data_block.insert_symmetry_operations(["x,y,z", "-x,-y,-z"]);
+
+        assert_eq!(
+            data_block["_space_group_symop_operation_xyz"][1],
+            "-x,-y,-z"
+        );
+
+        let text = super::write_phase("BaTiO3", &phase);
+
+        #[cfg(feature = "symmetry")]
+        assert!(text.contains("_space_group_symop_operation_xyz"));
+
+        let cif = crate::try_read_cif(text.as_bytes()).unwrap();
+
+        assert_eq!(cif.get("BaTiO3").unwrap().try_into_phase().unwrap(), phase);
+    }
 }
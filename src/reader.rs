@@ -0,0 +1,303 @@
+use std::{collections::VecDeque, io::BufRead};
+
+use crate::{
+    error::CifError,
+    lexer::{CifVersion, BYTE_ORDER_MARK, CIF_2_MAGIC},
+    parser::{DataBlock, Parser},
+};
+
+/// Reads data blocks one at a time from a [`BufRead`], so only a single data block has to be held
+/// in memory. Useful for very large files and bulk dumps with many data blocks.
+///
+/// The input is split at lines starting with a `data_` header outside of text fields and CIF 2.0
+/// triple-quoted strings, and every part is parsed on its own.
+/// Duplicate data block names are therefore not detected.
+pub struct DataBlockReader<R> {
+    reader: R,
+    strict: bool,
+    version: Option<CifVersion>,
+    /// Lines of the data block that is currently read
+    chunk: Vec<u8>,
+    /// Line number of the first line of `chunk`
+    chunk_line: usize,
+    line: Vec<u8>,
+    lines_read: usize,
+    in_text_field: bool,
+    /// Delimiter of a CIF 2.0 triple-quoted string that continues on the next line
+    triple_quote: Option<u8>,
+    done: bool,
+    /// Data blocks of the last chunk that were not returned yet
+    pending: VecDeque<Result<(String, DataBlock), CifError>>,
+    errors: Vec<CifError>,
+}
+
+impl<R: BufRead> DataBlockReader<R> {
+    /// Syntax errors are skipped over and logged like in [`Parser::parse`]
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            strict: false,
+            version: None,
+            chunk: Vec::new(),
+            chunk_line: 1,
+            line: Vec::new(),
+            lines_read: 0,
+            in_text_field: false,
+            triple_quote: None,
+            done: false,
+            pending: VecDeque::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Data blocks with syntax errors are returned as the first [`CifError`] found in them,
+    /// like in [`Parser::try_parse`]. Reading continues with the next data block.
+    pub fn strict(reader: R) -> Self {
+        Self {
+            strict: true,
+            ..Self::new(reader)
+        }
+    }
+
+    /// Version detected from the first line. `None` before the first data block was read.
+    pub fn version(&self) -> Option<CifVersion> {
+        self.version
+    }
+
+    /// Syntax errors found in the data blocks read so far
+    pub fn errors(&self) -> &[CifError] {
+        &self.errors
+    }
+
+    /// Reads lines until the next data block header, which starts the next chunk.
+    /// Returns `false` at the end of the input.
+    fn read_chunk(&mut self) -> std::io::Result<bool> {
+        let mut has_header = !self.chunk.is_empty() && is_data_block_header(&self.chunk);
+
+        loop {
+            self.line.clear();
+
+            if self.reader.read_until(b'\n', &mut self.line)? == 0 {
+                return Ok(!self.chunk.is_empty());
+            }
+
+            self.lines_read += 1;
+
+            if self.version.is_none() {
+                let line = self
+                    .line
+                    .strip_prefix(BYTE_ORDER_MARK)
+                    .unwrap_or(&self.line);
+
+                self.version = Some(match line.starts_with(CIF_2_MAGIC) {
+                    true => CifVersion::V2_0,
+                    false => CifVersion::V1_1,
+                });
+            }
+
+            if let Some(quote) = self.triple_quote {
+                self.triple_quote = open_triple_quote(&self.line, Some(quote));
+            } else if self.line.first() == Some(&b';') {
+                self.in_text_field = !self.in_text_field;
+            } else if !self.in_text_field {
+                let is_header = is_data_block_header(&self.line);
+
+                if self.version == Some(CifVersion::V2_0) {
+                    self.triple_quote = open_triple_quote(&self.line, None);
+                }
+
+                if is_header {
+                    if has_header {
+                        return Ok(true);
+                    }
+
+                    has_header = true;
+                }
+            }
+
+            self.chunk.extend_from_slice(&self.line);
+        }
+    }
+
+    fn parse_chunk(&mut self) {
+        let mut parser = Parser::with_version(&self.chunk, self.version.unwrap_or_default());
+
+        let cif = parser.parse();
+
+        let mut errors = parser.errors().to_vec();
+
+        for error in &mut errors {
+            error.position.line += self.chunk_line - 1;
+        }
+
+        for (name, data_block) in cif {
+            let first_error = errors
+                .iter()
+                .find(|error| error.data_block.as_ref() == Some(&name))
+                .cloned();
+
+            match first_error {
+                Some(error) if self.strict => self.pending.push_back(Err(error)),
+                _ => self.pending.push_back(Ok((name, data_block))),
+            }
+        }
+
+        if self.strict && self.pending.is_empty() {
+            if let Some(error) = errors.first() {
+                self.pending.push_back(Err(error.clone()));
+            }
+        }
+
+        self.errors.extend(errors);
+
+        // the header line that ended the chunk starts the next one
+        self.chunk.clear();
+        self.chunk.extend_from_slice(&self.line);
+        self.chunk_line = self.lines_read;
+    }
+}
+
+impl<R: BufRead> Iterator for DataBlockReader<R> {
+    type Item = anyhow::Result<(String, DataBlock)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(data_block) = self.pending.pop_front() {
+                return Some(data_block.map_err(anyhow::Error::from));
+            }
+
+            if self.done {
+                return None;
+            }
+
+            match self.read_chunk() {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.done = true;
+                    continue;
+                }
+                Err(error) => {
+                    self.done = true;
+                    return Some(Err(anyhow::Error::from(error).context("Failed to read CIF")));
+                }
+            }
+
+            let at_end = self.line.is_empty();
+
+            self.parse_chunk();
+
+            self.done = at_end;
+        }
+    }
+}
+
+/// `data_` at the start of a line, ignoring leading whitespace
+fn is_data_block_header(line: &[u8]) -> bool {
+    let start = line
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(line.len());
+
+    line[start..]
+        .get(..5)
+        .is_some_and(|keyword| keyword.eq_ignore_ascii_case(b"data_"))
+}
+
+/// Delimiter of a triple-quoted string that is still open at the end of `line`, which starts
+/// inside a triple-quoted string delimited by `open`
+fn open_triple_quote(line: &[u8], mut open: Option<u8>) -> Option<u8> {
+    let mut index = 0;
+    let mut token_start = true;
+
+    while index < line.len() {
+        let rest = &line[index..];
+        let byte = rest[0];
+
+        match open {
+            Some(quote) if rest.starts_with(&[quote; 3]) => {
+                open = None;
+                index += 3;
+                token_start = false;
+            }
+            Some(_) => index += 1,
+            None if token_start && (byte == b'\'' || byte == b'"') => {
+                if rest.starts_with(&[byte; 3]) {
+                    open = Some(byte);
+                    index += 3;
+                } else {
+                    // other quoted strings end on the same line
+                    index += rest[1..]
+                        .iter()
+                        .position(|other| *other == byte)
+                        .map_or(rest.len(), |end| end + 2);
+                    token_start = false;
+                }
+            }
+            None if token_start && byte == b'#' => break,
+            None => {
+                token_start = byte.is_ascii_whitespace() || matches!(byte, b'[' | b'{' | b':');
+                index += 1;
+            }
+        }
+    }
+
+    open
+}
+
+#[cfg(test)]
+mod test {
+    use super::DataBlockReader;
+    use crate::error::{CifError, CifErrorKind};
+
+    const CIF: &[u8] =
+        b"# comment\ndata_a\n_x 1\n_text\n;\ndata_not_a_header\n;\nDATA_b\nloop_\n_y\n1 2\ndata_c\n_z 'z\n";
+
+    #[test]
+    fn test_data_blocks() {
+        let data_blocks = DataBlockReader::new(CIF)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+
+        let names = data_blocks
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, ["a", "b", "c"]);
+        assert_eq!(data_blocks[0].1["_text"], vec!["data_not_a_header"]);
+        assert_eq!(data_blocks[1].1["_y"], vec!["1", "2"]);
+    }
+
+    #[test]
+    fn test_triple_quoted_strings() {
+        let cif = b"#\\#CIF_2.0\ndata_a\n_x '''\ndata_not_a_header\n;\n'''\n_y \"\"\"a\"\"\" # '''\ndata_b\n_z [ 'z' \"\"\"\n;\n\"\"\" ]\n";
+
+        let data_blocks = DataBlockReader::new(&cif[..])
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+
+        let names = data_blocks
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, ["a", "b"]);
+        assert_eq!(data_blocks[0].1["_x"], vec!["\ndata_not_a_header\n;\n"]);
+        assert_eq!(data_blocks[0].1["_y"], vec!["a"]);
+    }
+
+    #[test]
+    fn test_strict() {
+        let mut reader = DataBlockReader::strict(CIF);
+
+        assert_eq!(reader.next().unwrap().unwrap().0, "a");
+        assert_eq!(reader.next().unwrap().unwrap().0, "b");
+
+        let error = reader.next().unwrap().unwrap_err();
+        let error = error.downcast_ref::<CifError>().unwrap();
+
+        assert_eq!(error.kind, CifErrorKind::UnterminatedQuotedString);
+        assert_eq!(error.line(), 13);
+        assert!(reader.next().is_none());
+    }
+}
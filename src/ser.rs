@@ -0,0 +1,609 @@
+// Serde serializer for data blocks, the reverse of `de`. The fields of a struct become data items
+// named `_<field>`, a sequence of structs or maps becomes a loop with the data names
+// `_<field>_<key>`, and a sequence of single values becomes a loop with the single data name
+// `_<field>`. Anything else that does not fit into CIF 1.1 is written as a CIF 2.0 list or table.
+
+use indexmap::IndexMap;
+use serde::ser::{self, Error as _, Serialize};
+
+use crate::{
+    de::Error,
+    lexer::CifVersion,
+    parser::DataBlock,
+    value::CifValue,
+    writer::{write_data_block_to, write_header, WriteOptions},
+};
+
+/// Writes `value` as a CIF file with a single data block named after its type
+pub fn to_string<T: Serialize>(value: &T) -> Result<String, Error> {
+    let data_block = to_data_block(value)?;
+
+    let name = std::any::type_name::<T>()
+        .split('<')
+        .next()
+        .and_then(|path| path.rsplit("::").next())
+        .unwrap_or("data");
+
+    let version = match data_block
+        .values()
+        .flatten()
+        .any(|value| matches!(value, CifValue::List(_) | CifValue::Table(_)))
+    {
+        true => CifVersion::V2_0,
+        false => CifVersion::V1_1,
+    };
+
+    let options = WriteOptions {
+        version,
+        ..Default::default()
+    };
+
+    let mut text = String::new();
+
+    write_header(&mut text, &options)
+        .and_then(|_| write_data_block_to(&mut text, name, &data_block, &options))
+        .expect("writing to a String never fails");
+
+    Ok(text)
+}
+
+/// Data block of a struct or map
+pub fn to_data_block<T: Serialize>(value: &T) -> Result<DataBlock, Error> {
+    let CifValue::Table(table) = value.serialize(ValueSerializer)? else {
+        return Err(Error::custom(
+            "Only structs and maps can be written as a data block",
+        ));
+    };
+
+    let mut data_block = DataBlock::default();
+
+    for (key, value) in table {
+        let name = match key.starts_with('_') {
+            true => key,
+            false => format!("_{}", key),
+        };
+
+        match value {
+            // empty sequences can not be written as a loop and are left out
+            CifValue::List(list) if list.is_empty() => {}
+            CifValue::List(list) if list.iter().all(|value| value.as_table().is_some()) => {
+                let mut keys = Vec::<String>::new();
+
+                for row in &list {
+                    for key in row.as_table().into_iter().flat_map(|row| row.keys()) {
+                        if !keys.contains(key) {
+                            keys.push(key.clone());
+                        }
+                    }
+                }
+
+                let rows = list
+                    .into_iter()
+                    .map(|row| match row {
+                        CifValue::Table(mut row) => keys
+                            .iter()
+                            .map(|key| row.shift_remove(key).unwrap_or(CifValue::Unknown))
+                            .collect(),
+                        _ => unreachable!(),
+                    })
+                    .collect();
+
+                let names = keys.iter().map(|key| format!("{}_{}", name, key)).collect();
+
+                data_block.insert_loop(names, rows).map_err(Error::custom)?;
+            }
+            CifValue::List(list)
+                if !list
+                    .iter()
+                    .any(|value| matches!(value, CifValue::List(_) | CifValue::Table(_))) =>
+            {
+                let rows = list.into_iter().map(|value| vec![value]).collect();
+
+                data_block
+                    .insert_loop(vec![name], rows)
+                    .map_err(Error::custom)?;
+            }
+            value => data_block.insert_item(name, value),
+        }
+    }
+
+    Ok(data_block)
+}
+
+/// Serializer of a single value
+struct ValueSerializer;
+
+fn number(value: impl ToString) -> CifValue {
+    CifValue::Numeric(value.to_string())
+}
+
+fn float(value: f64) -> CifValue {
+    match value.is_finite() {
+        true => number(value),
+        false => CifValue::Text(value.to_string()),
+    }
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = CifValue;
+    type Error = Error;
+
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = VariantSerializer<SeqSerializer>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = StructSerializer;
+    type SerializeStructVariant = VariantSerializer<StructSerializer>;
+
+    fn serialize_bool(self, v: bool) -> Result<CifValue, Error> {
+        Ok(CifValue::Text(v.to_string()))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<CifValue, Error> {
+        Ok(number(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<CifValue, Error> {
+        Ok(number(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<CifValue, Error> {
+        Ok(number(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<CifValue, Error> {
+        Ok(number(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<CifValue, Error> {
+        Ok(number(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<CifValue, Error> {
+        Ok(number(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<CifValue, Error> {
+        Ok(number(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<CifValue, Error> {
+        Ok(number(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<CifValue, Error> {
+        Ok(number(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<CifValue, Error> {
+        Ok(number(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<CifValue, Error> {
+        Ok(float(v as f64))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<CifValue, Error> {
+        Ok(float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<CifValue, Error> {
+        Ok(CifValue::Text(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<CifValue, Error> {
+        Ok(CifValue::Text(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<CifValue, Error> {
+        String::from_utf8(v.to_vec())
+            .map(CifValue::Text)
+            .map_err(Error::custom)
+    }
+
+    fn serialize_none(self) -> Result<CifValue, Error> {
+        Ok(CifValue::Unknown)
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<CifValue, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<CifValue, Error> {
+        Ok(CifValue::Inapplicable)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<CifValue, Error> {
+        Ok(CifValue::Inapplicable)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<CifValue, Error> {
+        Ok(CifValue::Text(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<CifValue, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<CifValue, Error> {
+        Ok(CifValue::Table(IndexMap::from([(
+            variant.to_string(),
+            value.serialize(self)?,
+        )])))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, Error> {
+        Ok(SeqSerializer(Vec::with_capacity(len.unwrap_or_default())))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer, Error> {
+        Ok(MapSerializer {
+            table: IndexMap::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<StructSerializer, Error> {
+        Ok(StructSerializer {
+            measured: name == crate::measured::SERDE_NAME,
+            table: IndexMap::new(),
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_struct(variant, len)?,
+        })
+    }
+}
+
+struct SeqSerializer(Vec<CifValue>);
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = CifValue;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        self.0.push(value.serialize(ValueSerializer)?);
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<CifValue, Error> {
+        Ok(CifValue::List(self.0))
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = CifValue;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<CifValue, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = CifValue;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<CifValue, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct MapSerializer {
+    table: IndexMap<String, CifValue>,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = CifValue;
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        // table keys are always strings, numbers are written as they are
+        let key = match key.serialize(ValueSerializer)? {
+            CifValue::Numeric(key) | CifValue::Text(key) => key,
+            key => return Err(Error::custom(format!("Key `{}` is not a string", key))),
+        };
+
+        self.key = Some(key);
+
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::custom("value serialized before its key"))?;
+
+        self.table.insert(key, value.serialize(ValueSerializer)?);
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<CifValue, Error> {
+        Ok(CifValue::Table(self.table))
+    }
+}
+
+/// Fields of a struct as a table. [`crate::Measured`] is written as a number like `4.0094(2)`.
+struct StructSerializer {
+    measured: bool,
+    table: IndexMap<String, CifValue>,
+}
+
+impl ser::SerializeStruct for StructSerializer {
+    type Ok = CifValue;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.table
+            .insert(key.to_string(), value.serialize(ValueSerializer)?);
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<CifValue, Error> {
+        if self.measured {
+            let value = self.table.get("value").and_then(CifValue::as_f64);
+            let su = self.table.get("su").and_then(CifValue::as_f64);
+
+            if let Some(value) = value {
+                return Ok(number(crate::measured::Measured::new(value, su)));
+            }
+        }
+
+        Ok(CifValue::Table(self.table))
+    }
+}
+
+/// Variant with fields as a table with the variant name as its single key
+struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl ser::SerializeTupleVariant for VariantSerializer<SeqSerializer> {
+    type Ok = CifValue;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<CifValue, Error> {
+        Ok(CifValue::Table(IndexMap::from([(
+            self.variant.to_string(),
+            ser::SerializeSeq::end(self.inner)?,
+        )])))
+    }
+}
+
+impl ser::SerializeStructVariant for VariantSerializer<StructSerializer> {
+    type Ok = CifValue;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<CifValue, Error> {
+        Ok(CifValue::Table(IndexMap::from([(
+            self.variant.to_string(),
+            ser::SerializeStruct::end(self.inner)?,
+        )])))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use indexmap::IndexMap;
+    use serde::Serialize;
+
+    use crate::{measured::Measured, value::CifValue};
+
+    use super::{to_data_block, ValueSerializer};
+
+    fn value<T: Serialize>(value: &T) -> CifValue {
+        value.serialize(ValueSerializer).unwrap()
+    }
+
+    fn numeric(text: &str) -> CifValue {
+        CifValue::Numeric(text.to_string())
+    }
+
+    #[derive(Serialize)]
+    enum Shape {
+        Point,
+        Sphere(f64),
+        Box(f64, f64),
+        Ellipse { a: f64, b: f64 },
+    }
+
+    #[test]
+    fn test_values() {
+        assert_eq!(value(&Measured::new(0.5, Some(0.3))), numeric("0.5(3)"));
+        assert_eq!(value(&Measured::new(0.5, None)), numeric("0.5"));
+        assert_eq!(
+            value(&Some(Measured::new(4.0094, Some(0.0002)))),
+            numeric("4.0094(2)")
+        );
+
+        // tuples stay lists, even if they look like a value and its uncertainty
+        assert_eq!(
+            value(&(0.5, 0.3)),
+            CifValue::List(vec![numeric("0.5"), numeric("0.3")])
+        );
+        assert_eq!(
+            value(&(0.5, None::<f64>)),
+            CifValue::List(vec![numeric("0.5"), CifValue::Unknown])
+        );
+
+        assert_eq!(value(&Some(1)), numeric("1"));
+        assert_eq!(value(&None::<f64>), CifValue::Unknown);
+        assert_eq!(value(&()), CifValue::Inapplicable);
+        assert_eq!(value(&f64::NAN), CifValue::Text("NaN".to_string()));
+
+        assert_eq!(value(&Shape::Point), CifValue::Text("Point".to_string()));
+        assert_eq!(
+            value(&Shape::Sphere(1.0)),
+            CifValue::Table(IndexMap::from([("Sphere".to_string(), numeric("1"))]))
+        );
+        assert_eq!(
+            value(&Shape::Box(1.0, 2.0)),
+            CifValue::Table(IndexMap::from([(
+                "Box".to_string(),
+                CifValue::List(vec![numeric("1"), numeric("2")])
+            )]))
+        );
+        assert_eq!(
+            value(&Shape::Ellipse { a: 1.0, b: 2.0 }),
+            CifValue::Table(IndexMap::from([(
+                "Ellipse".to_string(),
+                CifValue::Table(IndexMap::from([
+                    ("a".to_string(), numeric("1")),
+                    ("b".to_string(), numeric("2"))
+                ]))
+            )]))
+        );
+    }
+
+    #[test]
+    fn test_to_data_block() {
+        #[derive(Serialize)]
+        struct Site {
+            label: &'static str,
+            occupancy: Option<f64>,
+        }
+
+        #[derive(Serialize)]
+        struct Block {
+            cell_length_a: Measured,
+            cell_length_b: Option<Measured>,
+            range: (f64, f64),
+            atom_site: Vec<Site>,
+            angles: Vec<f64>,
+            empty: Vec<f64>,
+        }
+
+        let data_block = to_data_block(&Block {
+            cell_length_a: Measured::new(4.0094, Some(0.0002)),
+            cell_length_b: None,
+            range: (0.5, 0.3),
+            atom_site: vec![
+                Site {
+                    label: "Ba1",
+                    occupancy: Some(1.0),
+                },
+                Site {
+                    label: "O1",
+                    occupancy: None,
+                },
+            ],
+            angles: vec![90.0, 120.0],
+            empty: vec![],
+        })
+        .unwrap();
+
+        assert_eq!(
+            data_block.get("_cell_length_a").unwrap(),
+            &[numeric("4.0094(2)")]
+        );
+        assert_eq!(
+            data_block.get("_cell_length_b").unwrap(),
+            &[CifValue::Unknown]
+        );
+        // a sequence of single values is a loop with one data name
+        assert_eq!(
+            data_block
+                .find_loop("_range")
+                .unwrap()
+                .column("_range")
+                .unwrap(),
+            [numeric("0.5"), numeric("0.3")]
+        );
+
+        let sites = data_block.find_loop("_atom_site_label").unwrap();
+
+        assert_eq!(sites.names(), ["_atom_site_label", "_atom_site_occupancy"]);
+        assert_eq!(
+            sites.column("_atom_site_occupancy").unwrap(),
+            [numeric("1"), CifValue::Unknown]
+        );
+
+        assert_eq!(
+            data_block.find_loop("_angles").unwrap().names(),
+            ["_angles"]
+        );
+        assert!(data_block.get("_empty").is_none());
+
+        assert!(to_data_block(&1.0).is_err());
+    }
+}
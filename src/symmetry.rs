@@ -1,107 +1,487 @@
 use std::str::FromStr;
 
 use anyhow::Context;
-use fraction::GenericFraction;
-use fraction::ToPrimitive;
+use fraction::{Fraction, Sign, ToPrimitive};
 
 use crate::parse::GetAndParse;
 use crate::parser::DataBlock;
 
-#[derive(Debug, Clone, PartialEq)]
+mod expand;
+mod hall;
+mod identify;
+mod site;
+mod space_groups;
+mod wyckoff;
+
+pub use expand::DEFAULT_MERGE_TOLERANCE_ANGSTROM;
+pub use identify::{SpaceGroupIdentification, SpaceGroupMismatch};
+pub use site::{SiteSymmetry, DEFAULT_SITE_TOLERANCE_FRACTIONAL};
+pub use space_groups::{SpaceGroupSetting, SPACE_GROUPS};
+pub use wyckoff::WyckoffPosition;
+
+/// Symmetry operation as the Seitz matrix `{W|w}` with exact rational entries, mapping the
+/// fractional coordinates `x` to `W x + w`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub enum Axis {
-    X,
-    Y,
-    Z,
+pub struct SymmetryEquivTransform {
+    pub rotation: [[Fraction; 3]; 3],
+    pub translation: [Fraction; 3],
 }
 
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub struct SymmetryEquivTransformColumn {
-    axis: Axis,
-    sign: i8,
-    translation: GenericFraction<u64>,
+impl Default for SymmetryEquivTransform {
+    fn default() -> Self {
+        Self::identity()
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub struct SymmetryEquivTransform(pub [SymmetryEquivTransformColumn; 3]);
+impl SymmetryEquivTransform {
+    pub fn identity() -> Self {
+        let mut rotation = [[Fraction::from(0); 3]; 3];
 
-impl std::ops::Deref for SymmetryEquivTransform {
-    type Target = [SymmetryEquivTransformColumn; 3];
+        for (index, row) in rotation.iter_mut().enumerate() {
+            row[index] = Fraction::from(1);
+        }
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+        Self {
+            rotation,
+            translation: [Fraction::from(0); 3],
+        }
     }
-}
 
-impl SymmetryEquivTransform {
     pub fn transform_point<T: num_traits::Float>(&self, point: [T; 3]) -> anyhow::Result<[T; 3]> {
         let mut new_point = [T::zero(); 3];
 
-        for (index, column) in self.0.iter().enumerate() {
-            let value = match column.axis {
-                Axis::X => point[0],
-                Axis::Y => point[1],
-                Axis::Z => point[2],
-            };
+        for (index, (row, translation)) in self.rotation.iter().zip(&self.translation).enumerate() {
+            let mut value = to_float::<T>(translation).context("Failed to convert translation")?;
+
+            for (coefficient, coordinate) in row.iter().zip(point) {
+                value = value
+                    + to_float::<T>(coefficient).context("Failed to convert rotation")?
+                        * coordinate;
+            }
 
-            let translation: f64 = column
-                .translation
-                .to_f64()
-                .context("Failed to convert translation to f64")?;
+            new_point[index] = value;
+        }
 
-            let translation = T::from(translation).context("Failed to convert translation to T")?;
+        Ok(new_point)
+    }
 
-            let sign = T::from(column.sign).context("Failed to convert sign to T")?;
+    /// `self` applied after `other`, `{W1|w1}{W2|w2} = {W1 W2|W1 w2 + w1}`. Follow with
+    /// [`SymmetryEquivTransform::normalized`] to compare operations modulo lattice translations.
+    pub fn compose(&self, other: &Self) -> Self {
+        let mut rotation = [[Fraction::from(0); 3]; 3];
+        let mut translation = self.translation;
 
-            new_point[index] = value * sign + translation;
+        for (i, row) in self.rotation.iter().enumerate() {
+            for (j, value) in rotation[i].iter_mut().enumerate() {
+                for (k, coefficient) in row.iter().enumerate() {
+                    *value += *coefficient * other.rotation[k][j];
+                }
+            }
+
+            for (coefficient, component) in row.iter().zip(&other.translation) {
+                translation[i] += *coefficient * *component;
+            }
         }
 
-        Ok(new_point)
+        Self {
+            rotation,
+            translation,
+        }
+    }
+
+    /// The same operation with its translation reduced to `[0, 1)`
+    pub fn normalized(&self) -> Self {
+        Self {
+            rotation: self.rotation,
+            translation: self.translation.map(|value| value - value.floor()),
+        }
+    }
+
+    /// `{W|w}⁻¹ = {W⁻¹|-W⁻¹ w}`, for rotations with the determinant 1 or -1
+    pub fn inverse(&self) -> anyhow::Result<Self> {
+        let determinant = self.determinant();
+
+        if determinant != Fraction::from(1) && determinant != Fraction::from(-1) {
+            anyhow::bail!("Operation `{}` has the determinant {}", self, determinant);
+        }
+
+        let m = &self.rotation;
+
+        // the adjugate divided by the determinant
+        let mut rotation = [[Fraction::from(0); 3]; 3];
+
+        for (i, row) in rotation.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                let (r1, r2) = ((j + 1) % 3, (j + 2) % 3);
+                let (c1, c2) = ((i + 1) % 3, (i + 2) % 3);
+
+                *value = (m[r1][c1] * m[r2][c2] - m[r1][c2] * m[r2][c1]) / determinant;
+            }
+        }
+
+        let mut translation = [Fraction::from(0); 3];
+
+        for (value, row) in translation.iter_mut().zip(&rotation) {
+            for (coefficient, component) in row.iter().zip(&self.translation) {
+                *value -= *coefficient * *component;
+            }
+        }
+
+        Ok(Self {
+            rotation,
+            translation,
+        })
+    }
+
+    pub fn determinant(&self) -> Fraction {
+        let m = &self.rotation;
+
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+}
+
+fn to_float<T: num_traits::Float>(value: &Fraction) -> Option<T> {
+    T::from(value.to_f64()?)
+}
+
+/// Parses operations like `-y, x-y, z+1/2` as written in `_space_group_symop_operation_xyz`.
+/// Coefficients and translations can be integers, fractions or decimals (`2x`, `1/2+x`, `0.5-y`),
+/// and spaces and the case of `x`, `y` and `z` are ignored.
+impl FromStr for SymmetryEquivTransform {
+    type Err = anyhow::Error;
+
+    fn from_str(operation: &str) -> anyhow::Result<Self> {
+        let mut rotation = [[Fraction::from(0); 3]; 3];
+        let mut translation = [Fraction::from(0); 3];
+
+        let mut components = operation.trim().trim_matches('\'').split(',');
+
+        for (row, translation) in rotation.iter_mut().zip(translation.iter_mut()) {
+            let component = components
+                .next()
+                .with_context(|| format!("Operation `{}` has less than 3 components", operation))?;
+
+            parse_component(component, row, translation)
+                .with_context(|| format!("Failed to parse operation `{}`", operation))?;
+        }
+
+        if components.next().is_some() {
+            return Err(anyhow::anyhow!(
+                "Operation `{}` has more than 3 components",
+                operation
+            ));
+        }
+
+        Ok(Self {
+            rotation,
+            translation,
+        })
+    }
+}
+
+impl TryFrom<&str> for SymmetryEquivTransform {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Adds the terms of a component like `x-y+1/2` to a row of the rotation and the translation
+fn parse_component(
+    component: &str,
+    row: &mut [Fraction; 3],
+    translation: &mut Fraction,
+) -> anyhow::Result<()> {
+    let chars = component
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .collect::<Vec<_>>();
+
+    if chars.is_empty() {
+        return Err(anyhow::anyhow!("Empty component"));
+    }
+
+    let mut index = 0;
+
+    while index < chars.len() {
+        let sign = match chars[index] {
+            '+' => 1,
+            '-' => -1,
+            _ if index == 0 => 1,
+            c => return Err(anyhow::anyhow!("Expected `+` or `-` before `{}`", c)),
+        };
+
+        if matches!(chars[index], '+' | '-') {
+            index += 1;
+        }
+
+        let start = index;
+
+        while index < chars.len() && (chars[index].is_ascii_digit() || "./".contains(chars[index]))
+        {
+            index += 1;
+        }
+
+        let number = chars[start..index].iter().collect::<String>();
+
+        let number = match number.is_empty() {
+            true => None,
+            false => Some(parse_number(&number)?),
+        };
+
+        if number.is_some() && chars.get(index) == Some(&'*') {
+            index += 1;
+        }
+
+        let axis = match chars.get(index) {
+            Some('x') => Some(0),
+            Some('y') => Some(1),
+            Some('z') => Some(2),
+            _ => None,
+        };
+
+        if axis.is_some() {
+            index += 1;
+        }
+
+        let value = Fraction::from(sign) * number.unwrap_or(Fraction::from(1));
+
+        match (axis, number) {
+            (Some(axis), _) => row[axis] += value,
+            (None, Some(_)) => *translation += value,
+            (None, None) => match chars.get(index) {
+                Some(c) => return Err(anyhow::anyhow!("Unexpected `{}`", c)),
+                None => return Err(anyhow::anyhow!("Missing term after sign")),
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// Integer, fraction like `1/3` or decimal like `0.3333`. Decimals close to a multiple of 1/24 are
+/// taken as that multiple, so rounded values like `0.3333` or `0.1667` are exact again.
+fn parse_number(number: &str) -> anyhow::Result<Fraction> {
+    if let Some((numerator, denominator)) = number.split_once('/') {
+        let numerator = numerator
+            .parse::<u64>()
+            .with_context(|| format!("Invalid number `{}`", number))?;
+        let denominator = denominator
+            .parse::<u64>()
+            .with_context(|| format!("Invalid number `{}`", number))?;
+
+        if denominator == 0 {
+            return Err(anyhow::anyhow!("Division by zero in `{}`", number));
+        }
+
+        return Ok(Fraction::new(numerator, denominator));
+    }
+
+    if !number.contains('.') {
+        return number
+            .parse::<u64>()
+            .map(Fraction::from)
+            .with_context(|| format!("Invalid number `{}`", number));
+    }
+
+    let value = Fraction::from_str(number)
+        .ok()
+        .filter(|value| value.to_f64().is_some())
+        .with_context(|| format!("Invalid number `{}`", number))?;
+
+    let float = value.to_f64().unwrap_or_default();
+    let multiple = (float * 24.0).round();
+
+    match (float - multiple / 24.0).abs() < 1e-3 {
+        true => Ok(Fraction::new(multiple as u64, 24u64)),
+        false => Ok(value),
+    }
+}
+
+/// Writes the operation like `-y,x-y,z+1/2`
+impl std::fmt::Display for SymmetryEquivTransform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, (row, translation)) in self.rotation.iter().zip(&self.translation).enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+
+            let mut component = String::new();
+
+            for (coefficient, axis) in row.iter().zip(["x", "y", "z"]) {
+                let (negative, magnitude) = split_sign(coefficient);
+
+                if magnitude.is_empty() {
+                    continue;
+                }
+
+                match (negative, component.is_empty()) {
+                    (true, _) => component.push('-'),
+                    (false, false) => component.push('+'),
+                    (false, true) => {}
+                }
+
+                if magnitude != "1" {
+                    component.push_str(&magnitude);
+                }
+
+                component.push_str(axis);
+            }
+
+            let (negative, magnitude) = split_sign(translation);
+
+            if !magnitude.is_empty() {
+                match (negative, component.is_empty()) {
+                    (true, _) => component.push('-'),
+                    (false, false) => component.push('+'),
+                    (false, true) => {}
+                }
+
+                component.push_str(&magnitude);
+            }
+
+            match component.is_empty() {
+                true => write!(f, "0")?,
+                false => write!(f, "{}", component)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sign and magnitude like `1/2`, with an empty magnitude for zero
+fn split_sign(value: &Fraction) -> (bool, String) {
+    match value {
+        Fraction::Rational(sign, ratio) if *ratio.numer() != 0 => {
+            let magnitude = match *ratio.denom() {
+                1 => ratio.numer().to_string(),
+                denominator => format!("{}/{}", ratio.numer(), denominator),
+            };
+
+            (*sign == Sign::Minus, magnitude)
+        }
+        Fraction::Rational(..) => (false, String::new()),
+        value => (false, value.to_string()),
     }
 }
 
 #[cfg(test)]
 mod test_symmetry_equiv_transform {
+    use fraction::Fraction;
+
+    use crate::symmetry::SymmetryEquivTransform;
+
     #[test]
     fn test_transform_point() {
-        use fraction::Ratio;
-
-        use crate::symmetry::{Axis, SymmetryEquivTransform, SymmetryEquivTransformColumn};
-
-        let transform = SymmetryEquivTransform([
-            SymmetryEquivTransformColumn {
-                axis: Axis::Z,
-                sign: 1,
-                translation: fraction::GenericFraction::Rational(
-                    fraction::Sign::Plus,
-                    Ratio::new(1, 4),
-                ),
-            },
-            SymmetryEquivTransformColumn {
-                axis: Axis::X,
-                sign: 1,
-                translation: fraction::GenericFraction::Rational(
-                    fraction::Sign::Plus,
-                    Ratio::new(1, 4),
-                ),
-            },
-            SymmetryEquivTransformColumn {
-                axis: Axis::Y,
-                sign: 1,
-                translation: fraction::GenericFraction::Rational(
-                    fraction::Sign::Plus,
-                    Ratio::new(0, 1),
-                ),
-            },
-        ]);
+        let transform: SymmetryEquivTransform = "z+1/4, x+1/4, y".parse().unwrap();
 
         let point = [0.0, 1.0, 1.0];
 
         let new_point = transform.transform_point(point).unwrap();
 
         assert_eq!(new_point, [1.25, 0.25, 1.0]);
+
+        let transform: SymmetryEquivTransform = "-y,x-y,z+1/2".parse().unwrap();
+
+        let new_point = transform.transform_point([0.25, 0.5, 0.0]).unwrap();
+
+        assert_eq!(new_point, [-0.5, -0.25, 0.5]);
+    }
+
+    #[test]
+    fn test_compose_and_inverse() {
+        let a: SymmetryEquivTransform = "-y,x-y,z+1/3".parse().unwrap();
+        let b: SymmetryEquivTransform = "-x+1/2,-y,z+1/2".parse().unwrap();
+
+        assert_eq!(a.compose(&b).to_string(), "y,-x+y+1/2,z+5/6");
+
+        let cube = a.compose(&a).compose(&a);
+        assert_eq!(cube.to_string(), "x,y,z+1");
+        assert_eq!(cube.normalized(), SymmetryEquivTransform::identity());
+
+        for operation in [a, b] {
+            let inverse = operation.inverse().unwrap();
+
+            assert_eq!(
+                operation.compose(&inverse),
+                SymmetryEquivTransform::identity()
+            );
+            assert_eq!(
+                inverse.compose(&operation),
+                SymmetryEquivTransform::identity()
+            );
+        }
+
+        assert_eq!(
+            "-y,x-y,z+1/3"
+                .parse::<SymmetryEquivTransform>()
+                .unwrap()
+                .inverse()
+                .unwrap()
+                .normalized()
+                .to_string(),
+            "-x+y,-x,z+2/3"
+        );
+
+        assert!("x,x,z"
+            .parse::<SymmetryEquivTransform>()
+            .unwrap()
+            .inverse()
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        let half = Fraction::new(1u64, 2u64);
+        let one = Fraction::from(1);
+        let zero = Fraction::from(0);
+
+        let transform: SymmetryEquivTransform = "x-y, -X+Y, 2z".parse().unwrap();
+
+        assert_eq!(
+            transform.rotation,
+            [
+                [one, -one, zero],
+                [-one, one, zero],
+                [zero, zero, Fraction::from(2)]
+            ]
+        );
+        assert_eq!(transform.translation, [zero; 3]);
+
+        for (text, expected) in [
+            ("1/2+x,0.5-y,+z-1/2", "x+1/2,-y+1/2,z-1/2"),
+            (" -y , x - y , z + 1/3 ", "-y,x-y,z+1/3"),
+            ("y+0.3333,x+0.6667,-z+0.1667", "y+1/3,x+2/3,-z+1/6"),
+            ("2*x,1/2,-x+y", "2x,1/2,-x+y"),
+        ] {
+            let transform: SymmetryEquivTransform = text.parse().unwrap();
+
+            assert_eq!(transform.to_string(), expected);
+            assert_eq!(
+                expected.parse::<SymmetryEquivTransform>().unwrap(),
+                transform
+            );
+        }
+
+        assert_eq!(
+            "x+1/2,y,z"
+                .parse::<SymmetryEquivTransform>()
+                .unwrap()
+                .translation,
+            [half, zero, zero]
+        );
+
+        for text in [
+            "x,y", "x,y,z,x", "x,,z", "x,y,zz", "x,y,z+", "a,b,c", "x,y,1/0",
+        ] {
+            assert!(text.parse::<SymmetryEquivTransform>().is_err(), "{}", text);
+        }
     }
 }
 
@@ -113,6 +493,114 @@ mod test_symmetry_equiv_transform {
 pub struct SymmetryEquivPosAsXYZ(pub Vec<SymmetryEquivTransform>);
 
 impl SymmetryEquivPosAsXYZ {
+    /// Group generated by `generators` modulo lattice translations, starting with the identity
+    pub fn closure(generators: &[SymmetryEquivTransform]) -> anyhow::Result<Self> {
+        for generator in generators {
+            generator.inverse()?;
+        }
+
+        let mut operations = vec![SymmetryEquivTransform::identity()];
+        let mut index = 0;
+
+        while index < operations.len() {
+            for generator in generators {
+                let product = operations[index].compose(generator).normalized();
+
+                if !operations.contains(&product) {
+                    operations.push(product);
+                }
+            }
+
+            // a space group has at most 192 operations modulo the lattice translations
+            if operations.len() > 192 {
+                anyhow::bail!("The generators do not form a crystallographic group");
+            }
+
+            index += 1;
+        }
+
+        Ok(Self(operations))
+    }
+
+    /// Checks that the operations form a group modulo lattice translations: integer rotations
+    /// with the determinant 1 or -1, the identity, no duplicates and every product included
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let mut problems = Vec::new();
+
+        let operations = self
+            .0
+            .iter()
+            .map(SymmetryEquivTransform::normalized)
+            .collect::<Vec<_>>();
+
+        for operation in &operations {
+            let integer = operation
+                .rotation
+                .iter()
+                .flatten()
+                .all(|value| value.floor() == *value);
+
+            if !integer || operation.inverse().is_err() {
+                problems.push(format!(
+                    "`{}` is not a crystallographic operation",
+                    operation
+                ));
+            }
+        }
+
+        if !operations.contains(&SymmetryEquivTransform::identity()) {
+            problems.push("the identity `x,y,z` is missing".to_string());
+        }
+
+        for (index, operation) in operations.iter().enumerate() {
+            if operations[..index].contains(operation) {
+                problems.push(format!("`{}` is duplicated", operation));
+            }
+        }
+
+        let known = operations.iter().collect::<std::collections::HashSet<_>>();
+
+        let mut missing = Vec::new();
+
+        for a in &operations {
+            for b in &operations {
+                let product = a.compose(b).normalized();
+
+                if !known.contains(&product) && !missing.contains(&product) {
+                    missing.push(product);
+                }
+            }
+        }
+
+        if !missing.is_empty() {
+            problems.push(format!(
+                "the products {} are missing",
+                missing
+                    .iter()
+                    .map(|operation| format!("`{}`", operation))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if !problems.is_empty() {
+            anyhow::bail!(
+                "Symmetry operations do not form a group: {}",
+                problems.join("; ")
+            );
+        }
+
+        Ok(())
+    }
+
+    /// All operations of a Hall symbol like `-P 2ybc` or `P 31 2c (0 0 1)`, expanded from its
+    /// generators and lattice centring
+    pub fn from_hall(symbol: &str) -> anyhow::Result<Self> {
+        hall::operations_from_hall(symbol)
+            .map(Self)
+            .with_context(|| format!("Invalid Hall symbol `{}`", symbol))
+    }
+
     pub fn generate_equiv_positions<T: num_traits::Float>(
         &self,
         point: [T; 3],
@@ -140,103 +628,42 @@ impl TryFrom<&DataBlock> for SymmetryEquivPosAsXYZ {
             raw = map.get_and_parse_all::<String>("_symmetry_equiv_pos_as_xyz");
         }
 
-        let raw = raw.context("Failed to get symmetry equiv pos")?;
-
-        let mut symmetry_equiv_pos_as_xyz = Vec::new();
-
-        for pos in raw {
-            let mut split = pos.split(",");
-
-            let first: SymmetryEquivTransformColumn = split.next().unwrap().try_into()?;
-            let second: SymmetryEquivTransformColumn = split.next().unwrap().try_into()?;
-            let third: SymmetryEquivTransformColumn = split.next().unwrap().try_into()?;
+        let raw = match raw {
+            Ok(raw) => raw,
+            Err(error) => {
+                // Without an operations loop, generate them from the Hall symbol or the tables
+                let hall = ["_space_group_name_Hall", "_symmetry_space_group_name_Hall"]
+                    .into_iter()
+                    .find_map(|key| map.get_and_parse_first::<String>(key).ok());
+
+                if let Some(hall) = hall {
+                    match Self::from_hall(&hall) {
+                        Ok(operations) => return Ok(operations),
+                        Err(error) => log::warn!("{:#}", error),
+                    }
+                }
 
-            if split.next().is_some() {
-                return Err(anyhow::anyhow!("Got more than 3 columns"));
+                return SpaceGroupSetting::from_data_block(map)
+                    .map(SpaceGroupSetting::operations)
+                    .ok_or(error)
+                    .context("Failed to get symmetry equiv pos");
             }
+        };
 
-            symmetry_equiv_pos_as_xyz.push(SymmetryEquivTransform([first, second, third]));
-        }
+        let symmetry_equiv_pos_as_xyz = raw
+            .iter()
+            .map(|pos| pos.parse())
+            .collect::<anyhow::Result<Vec<_>>>()?;
 
         Ok(Self(symmetry_equiv_pos_as_xyz))
     }
 }
 
-impl TryFrom<&str> for SymmetryEquivTransformColumn {
-    type Error = anyhow::Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let parts = TranslationSplit::new(value.trim());
-
-        let mut axis: Option<Axis> = None;
-        let mut sign: Option<i8> = None;
-        let mut add = None;
-
-        parts.into_iter().for_each(|operation| {
-            match operation.to_lowercase().as_str() {
-                // TODO: could probably be done nicer
-                "+x" => {
-                    axis = Some(Axis::X);
-                    sign = Some(1);
-                }
-                "x" => {
-                    axis = Some(Axis::X);
-                    sign = Some(1);
-                }
-                "-x" => {
-                    axis = Some(Axis::X);
-                    sign = Some(-1);
-                }
-                "+y" => {
-                    axis = Some(Axis::Y);
-                    sign = Some(1);
-                }
-                "y" => {
-                    axis = Some(Axis::Y);
-                    sign = Some(1);
-                }
-                "-y" => {
-                    axis = Some(Axis::Y);
-                    sign = Some(-1);
-                }
-                "+z" => {
-                    axis = Some(Axis::Z);
-                    sign = Some(1);
-                }
-                "z" => {
-                    axis = Some(Axis::Z);
-                    sign = Some(1);
-                }
-                "-z" => {
-                    axis = Some(Axis::Z);
-                    sign = Some(-1);
-                }
-                _ => {
-                    add = Some(fraction::Fraction::from_str(operation).unwrap());
-                }
-            }
-        });
-
-        let axis = axis.context("Got no axis")?;
-        let sign = sign.context("Got no sign")?;
-        let translation = add.unwrap_or_default();
-
-        Ok(SymmetryEquivTransformColumn {
-            axis,
-            sign,
-            translation,
-        })
-    }
-}
-
 #[cfg(test)]
 mod test_symmetry_equiv_pos_as_xyz {
-    use fraction::Ratio;
+    use fraction::Fraction;
 
-    use crate::{
-        symmetry::{Axis, SymmetryEquivTransformColumn},
-        Parser,
-    };
+    use crate::{symmetry::SymmetryEquivTransform, Parser};
 
     use super::SymmetryEquivPosAsXYZ;
 
@@ -248,26 +675,15 @@ mod test_symmetry_equiv_pos_as_xyz {
 
         let sym: SymmetryEquivPosAsXYZ = data.first_key_value().unwrap().1.try_into().unwrap();
 
-        let expected_first = SymmetryEquivTransformColumn {
-            axis: Axis::Z,
-            sign: 1,
-            translation: fraction::GenericFraction::Rational(
-                fraction::Sign::Plus,
-                Ratio::new(1, 4),
-            ),
-        };
-
-        let expected_last = SymmetryEquivTransformColumn {
-            axis: Axis::Z,
-            sign: 1,
-            translation: fraction::GenericFraction::Rational(
-                fraction::Sign::Plus,
-                Ratio::new(0, 1),
-            ),
-        };
+        assert_eq!(sym.0.len(), 192);
 
-        assert_eq!(sym.0.first().unwrap()[0], expected_first);
-        assert_eq!(sym.0.last().unwrap()[2], expected_last);
+        assert_eq!(
+            sym.0.first().unwrap(),
+            &"z+1/4,y+1/4,-x+1/4"
+                .parse::<SymmetryEquivTransform>()
+                .unwrap()
+        );
+        assert_eq!(sym.0.last().unwrap().to_string(), "x+1/2,y+1/2,z");
     }
 
     #[test]
@@ -299,48 +715,96 @@ mod test_symmetry_equiv_pos_as_xyz {
             assert!(expected.contains(&point));
         }
     }
-}
 
-struct TranslationSplit<'a> {
-    char_indices: std::str::CharIndices<'a>,
-    chunk_start: usize,
-    s: &'a str,
-}
+    #[test]
+    fn test_from_space_group() {
+        let data = Parser::new(b"data_a\n_space_group_IT_number 14\n").parse();
+        let sym: SymmetryEquivPosAsXYZ = data.first_key_value().unwrap().1.try_into().unwrap();
+        assert_eq!(sym.0.len(), 4);
+        assert!(sym.0.contains(&"-x,y+1/2,-z+1/2".parse().unwrap()));
 
-impl<'a> TranslationSplit<'a> {
-    pub fn new(s: &'a str) -> Self {
-        let mut char_indices = s.char_indices();
+        let data = Parser::new(b"data_a\n_space_group_name_Hall '-P 2yn'\n").parse();
+        let sym: SymmetryEquivPosAsXYZ = data.first_key_value().unwrap().1.try_into().unwrap();
+        assert!(sym.0.contains(&"-x+1/2,y+1/2,-z+1/2".parse().unwrap()));
 
-        char_indices.next();
-        Self {
-            char_indices,
-            chunk_start: 0,
-            s,
-        }
+        let data = Parser::new(b"data_a\n_symmetry_space_group_name_H-M 'F d -3 m :2'\n").parse();
+        let sym: SymmetryEquivPosAsXYZ = data.first_key_value().unwrap().1.try_into().unwrap();
+        assert_eq!(sym.0.len(), 192);
+        assert!(sym.0.contains(&"-x,-y,-z".parse().unwrap()));
+
+        let data = Parser::new(b"data_a\n_cell_length_a 1\n").parse();
+        assert!(SymmetryEquivPosAsXYZ::try_from(data.first_key_value().unwrap().1).is_err());
     }
-}
 
-impl<'a> Iterator for TranslationSplit<'a> {
-    type Item = &'a str;
+    #[test]
+    fn test_from_hall() {
+        // not in the tables, so only the Hall symbol parser gives the operations
+        let data = Parser::new(b"data_a\n_space_group_name_Hall 'P 2y (0 0 3)'\n").parse();
+        let sym = data
+            .first_key_value()
+            .unwrap()
+            .1
+            .symmetry_equiv_pos_as_xyz()
+            .unwrap();
+        assert_eq!(
+            sym,
+            SymmetryEquivPosAsXYZ::from_hall("P 2y (0 0 3)").unwrap()
+        );
+        assert_eq!(sym.0.len(), 2);
+        assert!(sym.0.contains(&"-x,y,-z+1/2".parse().unwrap()));
+
+        let data = Parser::new(b"data_a\n_symmetry_space_group_name_Hall '-P 2ac 2n'\n").parse();
+        let sym: SymmetryEquivPosAsXYZ = data.first_key_value().unwrap().1.try_into().unwrap();
+        assert_eq!(sym.0.len(), 8);
+        assert!(sym.0.contains(&"-x+1/2,-y,z+1/2".parse().unwrap()));
 
-    fn next(&mut self) -> Option<Self::Item> {
-        // The input is exhausted
-        if self.chunk_start == self.s.len() {
-            return None;
-        }
-        // Find the next uppercase letter position OR the end of the string
-        let chunk_end = if let Some((chunk_end, _)) = self
-            .char_indices
-            .by_ref()
-            .skip_while(|(_, c)| !(c == &'+' || c == &'-'))
-            .next()
-        {
-            chunk_end
-        } else {
-            self.s.len()
-        };
-        let chunk = &self.s[self.chunk_start..chunk_end];
-        self.chunk_start = chunk_end;
-        Some(chunk)
+        // an invalid Hall symbol falls back to the number
+        let data = Parser::new(b"data_a\n_space_group_name_Hall 'Q 2'\n_space_group_IT_number 2\n")
+            .parse();
+        let sym: SymmetryEquivPosAsXYZ = data.first_key_value().unwrap().1.try_into().unwrap();
+        assert_eq!(sym.0.len(), 2);
+        assert!(SymmetryEquivPosAsXYZ::from_hall("Q 2").is_err());
+    }
+
+    #[test]
+    fn test_closure_and_validate() {
+        let generators =
+            ["-x,-y,-z", "-x,y+1/2,-z+1/2"].map(|operation| operation.parse().unwrap());
+
+        let group = SymmetryEquivPosAsXYZ::closure(&generators).unwrap();
+
+        assert_eq!(
+            group
+                .0
+                .iter()
+                .map(|operation| operation.to_string())
+                .collect::<Vec<_>>(),
+            ["x,y,z", "-x,-y,-z", "-x,y+1/2,-z+1/2", "x,-y+1/2,z+1/2"]
+        );
+        group.validate().unwrap();
+
+        let bytes = std::fs::read(r"assets\diamond.cif").unwrap();
+        let data = Parser::new(&bytes).parse();
+        let mut sym: SymmetryEquivPosAsXYZ = data.first_key_value().unwrap().1.try_into().unwrap();
+
+        sym.validate().unwrap();
+
+        let removed = sym.0.remove(5);
+        let error = sym.validate().unwrap_err().to_string();
+        assert!(
+            error.contains(&format!("`{}`", removed.normalized())),
+            "{}",
+            error
+        );
+
+        sym.0.push(removed.clone());
+        let mut shifted = removed;
+        shifted.translation[0] += Fraction::from(1);
+        sym.0.push(shifted);
+        let error = sym.validate().unwrap_err().to_string();
+        assert!(error.contains("is duplicated"), "{}", error);
+
+        assert!(SymmetryEquivPosAsXYZ::closure(&["x+1/2,y,z".parse().unwrap()]).is_ok());
+        assert!(SymmetryEquivPosAsXYZ::closure(&["2x,y,z".parse().unwrap()]).is_err());
     }
 }
@@ -0,0 +1,289 @@
+// Expansion of the asymmetric unit of a phase into all atoms of the unit cell
+
+use crystallib::{Atom, Atoms, Cell, Phase};
+
+use super::{to_float, SymmetryEquivPosAsXYZ, SymmetryEquivTransform};
+
+/// Default distance in Å below which two images of an atom are merged
+pub const DEFAULT_MERGE_TOLERANCE_ANGSTROM: f64 = 1e-2;
+
+impl SymmetryEquivPosAsXYZ {
+    /// Phase in `P 1` with every atom of the unit cell. Each atom of `phase` is moved by all
+    /// operations and wrapped into `[0, 1)`, and images closer than `tolerance_angstrom` (across
+    /// the cell boundaries) to an earlier image of the same atom are dropped. The first image keeps
+    /// the label of the atom and the others get the suffixes `_2`, `_3`, ..., like `O1_2`.
+    pub fn expand_to_p1(&self, phase: &Phase, tolerance_angstrom: f64) -> anyhow::Result<Phase> {
+        // missing or duplicated operations give a wrong number of atoms
+        if let Err(error) = self.validate() {
+            log::warn!("{:#}", error);
+        }
+
+        let metric = metric_tensor(&phase.cell);
+        let reciprocal = reciprocal_lengths(&phase.cell);
+
+        let mut atoms = Vec::new();
+
+        for atom in phase.atoms.iter() {
+            let mut images: Vec<[f64; 3]> = Vec::new();
+
+            for operation in &self.0 {
+                let position = operation
+                    .transform_point([atom.x, atom.y, atom.z])?
+                    .map(wrap);
+
+                if images
+                    .iter()
+                    .any(|image| distance(&metric, image, &position) < tolerance_angstrom)
+                {
+                    continue;
+                }
+
+                images.push(position);
+
+                let label = match images.len() {
+                    1 => atom.label.clone(),
+                    count => format!("{}_{}", atom.label, count),
+                };
+
+                let [u11, u22, u33, u12, u13, u23] = rotate_adp(operation, atom, &reciprocal);
+
+                atoms.push(Atom {
+                    label,
+                    x: position[0],
+                    y: position[1],
+                    z: position[2],
+                    multiplicity: Some(1.0),
+                    u11,
+                    u22,
+                    u33,
+                    u12,
+                    u13,
+                    u23,
+                    ..atom.clone()
+                });
+            }
+        }
+
+        Ok(Phase {
+            cell: Cell {
+                space_group: "P 1".to_string(),
+                space_group_number: 1,
+                ..phase.cell.clone()
+            },
+            atoms: Atoms(atoms),
+        })
+    }
+}
+
+/// Fractional coordinate in `[0, 1)`
+fn wrap(value: f64) -> f64 {
+    let wrapped = value - value.floor();
+
+    // values just below an integer round up to 1
+    if wrapped >= 1.0 {
+        0.0
+    } else {
+        wrapped
+    }
+}
+
+fn metric_tensor(cell: &Cell) -> [[f64; 3]; 3] {
+    let [cos_alpha, cos_beta, cos_gamma] =
+        [cell.alpha, cell.beta, cell.gamma].map(|angle| angle.to_radians().cos());
+
+    [
+        [
+            cell.a * cell.a,
+            cell.a * cell.b * cos_gamma,
+            cell.a * cell.c * cos_beta,
+        ],
+        [
+            cell.a * cell.b * cos_gamma,
+            cell.b * cell.b,
+            cell.b * cell.c * cos_alpha,
+        ],
+        [
+            cell.a * cell.c * cos_beta,
+            cell.b * cell.c * cos_alpha,
+            cell.c * cell.c,
+        ],
+    ]
+}
+
+/// `a*`, `b*` and `c*`
+fn reciprocal_lengths(cell: &Cell) -> [f64; 3] {
+    let [cos_alpha, cos_beta, cos_gamma] =
+        [cell.alpha, cell.beta, cell.gamma].map(|angle| angle.to_radians().cos());
+
+    let volume = cell.a
+        * cell.b
+        * cell.c
+        * (1.0 - cos_alpha.powi(2) - cos_beta.powi(2) - cos_gamma.powi(2)
+            + 2.0 * cos_alpha * cos_beta * cos_gamma)
+            .sqrt();
+
+    [
+        cell.b * cell.c * cell.alpha.to_radians().sin() / volume,
+        cell.a * cell.c * cell.beta.to_radians().sin() / volume,
+        cell.a * cell.b * cell.gamma.to_radians().sin() / volume,
+    ]
+}
+
+/// Shortest distance between two fractional positions in a periodic cell
+fn distance(metric: &[[f64; 3]; 3], a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    let difference = [0, 1, 2].map(|index| {
+        let difference = a[index] - b[index];
+        difference - difference.round()
+    });
+
+    let mut squared = 0.0;
+
+    for (row, di) in metric.iter().zip(difference) {
+        for (value, dj) in row.iter().zip(difference) {
+            squared += value * di * dj;
+        }
+    }
+
+    squared.max(0.0).sqrt()
+}
+
+/// `U_ij` of `atom` after the rotation of `operation`, `U*' = W U* Wᵀ` with `U*_ij = a*_i a*_j U_ij`
+fn rotate_adp(operation: &SymmetryEquivTransform, atom: &Atom, reciprocal: &[f64; 3]) -> [f64; 6] {
+    let u = [
+        [atom.u11, atom.u12, atom.u13],
+        [atom.u12, atom.u22, atom.u23],
+        [atom.u13, atom.u23, atom.u33],
+    ];
+
+    let rotation = operation
+        .rotation
+        .map(|row| row.map(|value| to_float::<f64>(&value).unwrap_or_default()));
+
+    let mut rotated = [[0.0; 3]; 3];
+
+    for (i, rotated_row) in rotated.iter_mut().enumerate() {
+        for (j, value) in rotated_row.iter_mut().enumerate() {
+            for k in 0..3 {
+                for l in 0..3 {
+                    *value +=
+                        rotation[i][k] * reciprocal[k] * u[k][l] * reciprocal[l] * rotation[j][l];
+                }
+            }
+
+            *value /= reciprocal[i] * reciprocal[j];
+        }
+    }
+
+    [
+        rotated[0][0],
+        rotated[1][1],
+        rotated[2][2],
+        rotated[0][1],
+        rotated[0][2],
+        rotated[1][2],
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Parser;
+
+    use super::DEFAULT_MERGE_TOLERANCE_ANGSTROM;
+
+    #[test]
+    fn test_expand_to_p1() {
+        let bytes = std::fs::read(r"assets\BaTiO3.cif").unwrap();
+        let data = Parser::new(&bytes).parse();
+        let block = data.first_key_value().unwrap().1;
+
+        let phase = block.try_into_phase().unwrap();
+        let sym = block.symmetry_equiv_pos_as_xyz().unwrap();
+
+        let p1 = sym
+            .expand_to_p1(&phase, DEFAULT_MERGE_TOLERANCE_ANGSTROM)
+            .unwrap();
+
+        assert_eq!(p1.cell.space_group_number, 1);
+        assert_eq!(p1.atoms.len(), 5);
+        assert_eq!(
+            p1.atoms
+                .iter()
+                .filter(|atom| atom.type_ == "O")
+                .map(|atom| atom.label.as_str())
+                .collect::<Vec<_>>(),
+            ["O1", "O1_2", "O1_3"]
+        );
+
+        for atom in p1.atoms.iter() {
+            for coordinate in [atom.x, atom.y, atom.z] {
+                assert!((0.0..1.0).contains(&coordinate));
+            }
+        }
+
+        let bytes = std::fs::read(r"assets\diamond.cif").unwrap();
+        let data = Parser::new(&bytes).parse();
+        let block = data.first_key_value().unwrap().1;
+
+        let p1 = block
+            .try_into_p1_phase(DEFAULT_MERGE_TOLERANCE_ANGSTROM)
+            .unwrap();
+
+        assert_eq!(p1.atoms.len(), 8);
+    }
+
+    #[test]
+    fn test_rotate_adp() {
+        let data = Parser::new(
+            b"data_a
+_cell_length_a 4
+_cell_length_b 4
+_cell_length_c 6
+_cell_angle_alpha 90
+_cell_angle_beta 90
+_cell_angle_gamma 90
+_cell_volume 96
+_space_group_IT_number 75
+loop_
+_atom_site_label
+_atom_site_type_symbol
+_atom_site_fract_x
+_atom_site_fract_y
+_atom_site_fract_z
+_atom_site_occupancy
+_atom_site_adp_type
+_atom_site_aniso_U_11
+_atom_site_aniso_U_22
+_atom_site_aniso_U_33
+_atom_site_aniso_U_12
+_atom_site_aniso_U_13
+_atom_site_aniso_U_23
+O1 O 0.1 0.2 0.3 1 Uani 0.01 0.02 0.03 0.004 0.005 0.006
+",
+        )
+        .parse();
+        let block = data.first_key_value().unwrap().1;
+
+        let phase = block.try_into_phase().unwrap();
+        let sym = block.symmetry_equiv_pos_as_xyz().unwrap();
+
+        let p1 = sym
+            .expand_to_p1(&phase, DEFAULT_MERGE_TOLERANCE_ANGSTROM)
+            .unwrap();
+
+        assert_eq!(p1.atoms.len(), 4);
+
+        // the 4-fold rotation -y,x,z swaps U11 and U22 and turns (U13, U23) into (-U23, U13)
+        let atom = p1
+            .atoms
+            .iter()
+            .find(|atom| (atom.x - 0.8).abs() < 1e-9 && (atom.y - 0.1).abs() < 1e-9)
+            .unwrap();
+
+        let expected = [0.02, 0.01, 0.03, -0.004, -0.006, 0.005];
+        let actual = [atom.u11, atom.u22, atom.u33, atom.u12, atom.u13, atom.u23];
+
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!((actual - expected).abs() < 1e-12, "{:?}", actual);
+        }
+    }
+}
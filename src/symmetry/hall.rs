@@ -0,0 +1,321 @@
+// https://cci.lbl.gov/sginfo/hall_symbols.html
+// Hall symbols like `-P 2ac 2n` describe a space group by its lattice and generators.
+
+use anyhow::Context;
+use fraction::Fraction;
+
+use super::space_groups::{centring_translations, generate};
+use super::SymmetryEquivTransform;
+
+type Matrix = [[i8; 3]; 3];
+
+const IDENTITY: Matrix = [[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+
+/// Rotations about z, and the two-fold rotations about a-b (`'`) and a+b (`"`)
+const ROTATIONS_Z: [(u8, Matrix); 5] = [
+    (1, IDENTITY),
+    (2, [[-1, 0, 0], [0, -1, 0], [0, 0, 1]]),
+    (3, [[0, -1, 0], [1, -1, 0], [0, 0, 1]]),
+    (4, [[0, -1, 0], [1, 0, 0], [0, 0, 1]]),
+    (6, [[1, -1, 0], [1, 0, 0], [0, 0, 1]]),
+];
+
+const TWO_FOLD_PRIME_Z: Matrix = [[0, -1, 0], [-1, 0, 0], [0, 0, -1]];
+const TWO_FOLD_DOUBLE_PRIME_Z: Matrix = [[0, 1, 0], [1, 0, 0], [0, 0, -1]];
+
+/// Three-fold rotation about a+b+c
+const THREE_FOLD_DIAGONAL: Matrix = [[0, 0, 1], [1, 0, 0], [0, 1, 0]];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Axis {
+    X,
+    Y,
+    Z,
+    Prime,
+    DoublePrime,
+    Diagonal,
+}
+
+/// Operations of the space group of a Hall symbol, including the centring translations
+pub(crate) fn operations_from_hall(symbol: &str) -> anyhow::Result<Vec<SymmetryEquivTransform>> {
+    let (symbol, origin_shift) = match symbol.split_once('(') {
+        Some((symbol, shift)) => (symbol, Some(shift)),
+        None => (symbol, None),
+    };
+
+    let mut parts = symbol.split_whitespace();
+
+    let lattice = parts.next().context("Hall symbol is empty")?;
+
+    let (centrosymmetric, lattice) = match lattice.strip_prefix('-') {
+        Some(lattice) => (true, lattice),
+        None => (false, lattice),
+    };
+
+    let centring = centring_translations(lattice)?;
+
+    let mut generators = Vec::new();
+
+    if centrosymmetric {
+        generators.push(seitz(negate(IDENTITY), [Fraction::from(0); 3]));
+    }
+
+    let mut previous: Option<(u8, Axis)> = None;
+
+    for (index, part) in parts.enumerate() {
+        let (generator, order, axis) = matrix_symbol(part, index, previous)
+            .with_context(|| format!("Invalid matrix symbol `{}`", part))?;
+
+        generators.push(generator);
+        previous = Some((order, axis));
+    }
+
+    if let Some(shift) = origin_shift {
+        let shift = parse_origin_shift(shift)?;
+
+        for generator in generators.iter_mut() {
+            *generator = shift_origin(generator, &shift);
+        }
+    }
+
+    Ok(generate(&generators, &centring))
+}
+
+/// Generator of a matrix symbol like `-2ybc`, `31`, `4bw` or `2"c`, with its order and axis
+fn matrix_symbol(
+    part: &str,
+    index: usize,
+    previous: Option<(u8, Axis)>,
+) -> anyhow::Result<(SymmetryEquivTransform, u8, Axis)> {
+    let mut chars = part.chars().peekable();
+
+    let improper = chars.next_if_eq(&'-').is_some();
+
+    let order = chars
+        .next()
+        .and_then(|c| c.to_digit(10))
+        .filter(|order| [1, 2, 3, 4, 6].contains(order))
+        .context("Expected a rotation order of 1, 2, 3, 4 or 6")? as u8;
+
+    let screw = chars
+        .next_if(|c| {
+            c.to_digit(10)
+                .is_some_and(|screw| screw > 0 && screw < order as u32)
+        })
+        .and_then(|c| c.to_digit(10));
+
+    let axis = match chars.peek() {
+        Some('x') => Some(Axis::X),
+        Some('y') => Some(Axis::Y),
+        Some('z') => Some(Axis::Z),
+        Some('\'') => Some(Axis::Prime),
+        Some('"') => Some(Axis::DoublePrime),
+        Some('*') => Some(Axis::Diagonal),
+        _ => None,
+    };
+
+    if axis.is_some() {
+        chars.next();
+    }
+
+    let axis = match (axis, index, order, previous) {
+        (Some(axis), ..) => axis,
+        (None, 0, ..) => Axis::Z,
+        (None, _, 1, _) => Axis::Z,
+        (None, 1, 2, Some((2 | 4, _))) => Axis::X,
+        (None, 1, 2, Some((3 | 6, _))) => Axis::Prime,
+        (None, _, 3, _) => Axis::Diagonal,
+        _ => return Err(anyhow::anyhow!("Missing axis symbol")),
+    };
+
+    // `'` and `"` are relative to the preceding axis
+    let principal = match previous {
+        Some((_, axis @ (Axis::X | Axis::Y))) => axis,
+        _ => Axis::Z,
+    };
+
+    let rotation = match (axis, order) {
+        (_, 1) => IDENTITY,
+        (Axis::Prime, 2) => permute(TWO_FOLD_PRIME_Z, principal),
+        (Axis::DoublePrime, 2) => permute(TWO_FOLD_DOUBLE_PRIME_Z, principal),
+        (Axis::Diagonal, 3) => THREE_FOLD_DIAGONAL,
+        (Axis::X | Axis::Y | Axis::Z, order) => {
+            let (_, rotation) = ROTATIONS_Z
+                .iter()
+                .find(|(n, _)| *n == order)
+                .expect("the order is one of the tabulated rotations");
+
+            permute(*rotation, axis)
+        }
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Rotation of order {} about {:?} is not allowed",
+                order,
+                axis
+            ))
+        }
+    };
+
+    let rotation = match improper {
+        true => negate(rotation),
+        false => rotation,
+    };
+
+    let mut translation = [Fraction::from(0); 3];
+
+    if let Some(screw) = screw {
+        let component = match axis {
+            Axis::X => 0,
+            Axis::Y => 1,
+            Axis::Z => 2,
+            _ => return Err(anyhow::anyhow!("Screw rotations need the axis x, y or z")),
+        };
+
+        translation[component] = Fraction::new(screw as u64, order as u64);
+    }
+
+    for c in chars {
+        let (component, value) = match c {
+            'a' => (0, Fraction::new(1u64, 2u64)),
+            'b' => (1, Fraction::new(1u64, 2u64)),
+            'c' => (2, Fraction::new(1u64, 2u64)),
+            'u' => (0, Fraction::new(1u64, 4u64)),
+            'v' => (1, Fraction::new(1u64, 4u64)),
+            'w' => (2, Fraction::new(1u64, 4u64)),
+            'n' | 'd' => {
+                let value = match c {
+                    'n' => Fraction::new(1u64, 2u64),
+                    _ => Fraction::new(1u64, 4u64),
+                };
+
+                for translation in translation.iter_mut() {
+                    *translation += value;
+                }
+
+                continue;
+            }
+            c => return Err(anyhow::anyhow!("Unknown translation symbol `{}`", c)),
+        };
+
+        translation[component] += value;
+    }
+
+    Ok((seitz(rotation, translation), order, axis))
+}
+
+/// Rotation about z expressed for the axis x or y by cyclically permuting the coordinates
+fn permute(rotation: Matrix, axis: Axis) -> Matrix {
+    let shift = match axis {
+        Axis::X => 1,
+        Axis::Y => 2,
+        _ => return rotation,
+    };
+
+    let mut permuted = [[0; 3]; 3];
+
+    for (i, row) in rotation.iter().enumerate() {
+        for (j, value) in row.iter().enumerate() {
+            permuted[(i + shift) % 3][(j + shift) % 3] = *value;
+        }
+    }
+
+    permuted
+}
+
+fn negate(rotation: Matrix) -> Matrix {
+    rotation.map(|row| row.map(|value| -value))
+}
+
+fn seitz(rotation: Matrix, translation: [Fraction; 3]) -> SymmetryEquivTransform {
+    SymmetryEquivTransform {
+        rotation: rotation.map(|row| row.map(Fraction::from)),
+        translation,
+    }
+}
+
+/// Origin shift like `0 0 1)` in twelfths of the lattice vectors
+fn parse_origin_shift(shift: &str) -> anyhow::Result<[Fraction; 3]> {
+    let values = shift
+        .trim_end()
+        .strip_suffix(')')
+        .context("Missing `)` after the origin shift")?
+        .split_whitespace()
+        .map(|value| value.parse::<i64>())
+        .collect::<Result<Vec<_>, _>>()
+        .context("Origin shifts are given as three integers in twelfths")?;
+
+    let values: [i64; 3] = values
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Origin shifts need three components"))?;
+
+    Ok(values.map(|value| Fraction::from(value) / Fraction::from(12)))
+}
+
+/// The generator in the shifted origin, `{W|w + v - Wv}`
+fn shift_origin(
+    generator: &SymmetryEquivTransform,
+    shift: &[Fraction; 3],
+) -> SymmetryEquivTransform {
+    let mut translation = generator.translation;
+
+    for (translation, (row, component)) in translation
+        .iter_mut()
+        .zip(generator.rotation.iter().zip(shift))
+    {
+        *translation += *component;
+
+        for (coefficient, component) in row.iter().zip(shift) {
+            *translation -= *coefficient * *component;
+        }
+    }
+
+    SymmetryEquivTransform {
+        rotation: generator.rotation,
+        translation,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::symmetry::SPACE_GROUPS;
+
+    use super::operations_from_hall;
+
+    fn operations(symbol: &str) -> Vec<String> {
+        operations_from_hall(symbol)
+            .unwrap()
+            .iter()
+            .map(|operation| operation.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_operations_from_hall() {
+        assert_eq!(
+            operations("-P 2ybc"),
+            ["x,y,z", "-x,-y,-z", "-x,y+1/2,-z+1/2", "x,-y+1/2,z+1/2"]
+        );
+        assert_eq!(operations("P 31 2c (0 0 1)").len(), 6);
+        assert!(operations("P 31 2c (0 0 1)").contains(&"-y,x-y,z+1/3".to_string()));
+        assert!(operations("P 3 2\"").contains(&"y,x,-z".to_string()));
+        assert!(operations("P 3*").contains(&"z,x,y".to_string()));
+        assert_eq!(operations("-F 4vw 2vw 3").len(), 192);
+        assert_eq!(operations("-R 3 2\"c").len(), 36);
+
+        for symbol in ["", "Q 2", "P 5", "P 2q", "P 2 2 (0 0", "P 3 2 2x3"] {
+            assert!(operations_from_hall(symbol).is_err(), "{}", symbol);
+        }
+    }
+
+    #[test]
+    fn test_tabulated_hall_symbols() {
+        for setting in SPACE_GROUPS {
+            assert_eq!(
+                operations_from_hall(setting.hall).unwrap(),
+                setting.operations().0,
+                "{:?}",
+                setting
+            );
+        }
+    }
+}
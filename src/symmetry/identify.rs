@@ -0,0 +1,667 @@
+// Identification of the space group of a list of symmetry operations by comparison with the
+// built-in tables. Operations in a tabulated setting are matched directly, and the transformation
+// to the standard setting is searched among changes of basis with entries -1, 0 and 1 and
+// determinant 1, combined with origin shifts in multiples of 1/24. Transformations that change the
+// volume of the cell, like hexagonal to rhombohedral axes, are not found.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use anyhow::Context;
+use fraction::{Fraction, ToPrimitive};
+
+use crate::parser::DataBlock;
+use crate::phase::{first_parsed, SPACE_GROUP_NUMBER_NAMES, SPACE_GROUP_SYMBOL_NAMES};
+
+use super::{SpaceGroupSetting, SymmetryEquivPosAsXYZ, SymmetryEquivTransform, SPACE_GROUPS};
+
+type Matrix = [[i32; 3]; 3];
+
+/// Translations are compared in multiples of 1/24
+const STEPS: i32 = 24;
+
+/// Space group of a list of operations
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpaceGroupIdentification {
+    /// Setting with exactly these operations, `None` for settings that are not tabulated
+    pub setting: Option<&'static SpaceGroupSetting>,
+    /// Default setting of the space group, see [`SpaceGroupSetting::from_number`]
+    pub standard: &'static SpaceGroupSetting,
+    /// Coordinate transformation `T`, `x' = P x + p`, to the standard setting, under which every
+    /// operation `g` becomes `T g T⁻¹`. `None` for settings outside the search, like rhombohedral
+    /// axes.
+    pub transformation: Option<SymmetryEquivTransform>,
+}
+
+/// Difference between the declared space group of a data block and that of its operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpaceGroupMismatch {
+    /// `_space_group_IT_number` or `_symmetry_Int_Tables_number` names another space group
+    Number { declared: u8, identified: u8 },
+    /// The Hermann–Mauguin symbol names another space group, setting or origin choice
+    Symbol {
+        declared: String,
+        identified: String,
+    },
+    /// The Hermann–Mauguin symbol is not in the tables
+    UnknownSymbol { declared: String },
+    /// The space group number is not an integer
+    InvalidNumber { declared: String },
+}
+
+impl Display for SpaceGroupMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number {
+                declared,
+                identified,
+            } => write!(
+                f,
+                "declared space group number {} but the operations belong to {}",
+                declared, identified
+            ),
+            Self::Symbol {
+                declared,
+                identified,
+            } => write!(
+                f,
+                "declared space group `{}` but the operations belong to `{}`",
+                declared, identified
+            ),
+            Self::UnknownSymbol { declared } => {
+                write!(
+                    f,
+                    "declared space group `{}` is not a known symbol",
+                    declared
+                )
+            }
+            Self::InvalidNumber { declared } => {
+                write!(
+                    f,
+                    "declared space group number `{}` is not a number",
+                    declared
+                )
+            }
+        }
+    }
+}
+
+impl SymmetryEquivPosAsXYZ {
+    /// Space group, setting and transformation to the standard setting of the operations, `None`
+    /// if they do not form a tabulated space group in any setting that the search covers
+    pub fn identify_space_group(&self) -> Option<SpaceGroupIdentification> {
+        let operations = integer_operations(&self.0)?;
+
+        let candidates = SPACE_GROUPS
+            .iter()
+            .filter(|setting| setting.order() == operations.len());
+
+        let setting = candidates.clone().find(|setting| {
+            let target = target_operations(setting);
+
+            operations.iter().all(|(rotation, translation)| {
+                target
+                    .get(rotation)
+                    .is_some_and(|translations| translations.contains(translation))
+            })
+        });
+
+        let bases = bases();
+
+        let (standard, transformation) = match setting {
+            Some(setting) => {
+                let standard = SpaceGroupSetting::from_number(setting.number)?;
+
+                (standard, find_transformation(&operations, standard, &bases))
+            }
+            None => candidates
+                .filter(|candidate| {
+                    SpaceGroupSetting::from_number(candidate.number) == Some(*candidate)
+                })
+                .find_map(|standard| {
+                    find_transformation(&operations, standard, &bases)
+                        .map(|transformation| (standard, Some(transformation)))
+                })?,
+        };
+
+        Some(SpaceGroupIdentification {
+            setting,
+            standard,
+            transformation,
+        })
+    }
+}
+
+impl SpaceGroupIdentification {
+    /// Differences to the Hermann–Mauguin symbol and the number declared by a data block. Only the
+    /// declared values are compared, `None` is never a mismatch, but a symbol that is not in the
+    /// tables is.
+    pub fn mismatches(&self, symbol: Option<&str>, number: Option<u8>) -> Vec<SpaceGroupMismatch> {
+        let mut mismatches = Vec::new();
+
+        if let Some(number) = number.filter(|number| *number != self.standard.number) {
+            mismatches.push(SpaceGroupMismatch::Number {
+                declared: number,
+                identified: self.standard.number,
+            });
+        }
+
+        let Some(symbol) = symbol else {
+            return mismatches;
+        };
+
+        let Some(declared) = SpaceGroupSetting::from_hermann_mauguin(symbol) else {
+            mismatches.push(SpaceGroupMismatch::UnknownSymbol {
+                declared: symbol.to_string(),
+            });
+
+            return mismatches;
+        };
+
+        let matches = match self.setting {
+            Some(setting) => declared == setting,
+            None => declared.number == self.standard.number,
+        };
+
+        if !matches {
+            let identified = self.setting.unwrap_or(self.standard);
+
+            mismatches.push(SpaceGroupMismatch::Symbol {
+                declared: symbol.to_string(),
+                identified: identified.hermann_mauguin_with_setting(),
+            });
+        }
+
+        mismatches
+    }
+}
+
+impl DataBlock {
+    /// Differences between the space group symbol and number of the data block, looked up like for
+    /// the cell of a phase, and the space group of its symmetry operations
+    pub fn space_group_mismatches(&self) -> anyhow::Result<Vec<SpaceGroupMismatch>> {
+        let symbol = first_parsed::<String>(self, SPACE_GROUP_SYMBOL_NAMES);
+        let number = first_parsed::<u8>(self, SPACE_GROUP_NUMBER_NAMES);
+
+        let identification = self
+            .symmetry_equiv_pos_as_xyz()?
+            .identify_space_group()
+            .context("Failed to identify the space group of the symmetry operations")?;
+
+        let mut mismatches = Vec::new();
+
+        // a declared number that does not parse is reported instead of ignored
+        if number.is_none() {
+            let declared = SPACE_GROUP_NUMBER_NAMES
+                .into_iter()
+                .find_map(|name| self.get(name)?.first().filter(|value| !value.is_null()));
+
+            if let Some(declared) = declared {
+                mismatches.push(SpaceGroupMismatch::InvalidNumber {
+                    declared: declared.to_string(),
+                });
+            }
+        }
+
+        mismatches.extend(identification.mismatches(symbol.as_deref(), number));
+
+        Ok(mismatches)
+    }
+}
+
+/// Distinct operations with integer rotations and translations in multiples of 1/24 in `[0, 24)`
+fn integer_operations(operations: &[SymmetryEquivTransform]) -> Option<Vec<(Matrix, [i32; 3])>> {
+    let to_integer = |value: &Fraction| {
+        if value.floor() == *value {
+            value.to_i32()
+        } else {
+            None
+        }
+    };
+
+    let mut integer = Vec::new();
+    let mut seen = HashSet::new();
+
+    for operation in operations {
+        let mut rotation = [[0; 3]; 3];
+
+        for (row, values) in rotation.iter_mut().zip(&operation.rotation) {
+            for (value, fraction) in row.iter_mut().zip(values) {
+                *value = to_integer(fraction)?;
+            }
+        }
+
+        let mut translation = [0; 3];
+
+        for (value, fraction) in translation.iter_mut().zip(&operation.translation) {
+            *value = to_integer(&(*fraction * Fraction::from(STEPS as i64)))?.rem_euclid(STEPS);
+        }
+
+        if seen.insert((rotation, translation)) {
+            integer.push((rotation, translation));
+        }
+    }
+
+    Some(integer)
+}
+
+/// Translations of the operations of `setting` by their rotation
+fn target_operations(setting: &SpaceGroupSetting) -> HashMap<Matrix, Vec<[i32; 3]>> {
+    let mut target: HashMap<Matrix, Vec<[i32; 3]>> = HashMap::new();
+
+    for (rotation, translation) in integer_operations(&setting.operations().0).unwrap_or_default() {
+        target.entry(rotation).or_default().push(translation);
+    }
+
+    target
+}
+
+/// Changes of basis with entries -1, 0 and 1 and determinant 1 with their inverses, the
+/// identity first and then by the number of non-zero entries
+fn bases() -> Vec<(Matrix, Matrix)> {
+    let mut bases = Vec::new();
+
+    for index in 0..3i32.pow(9) {
+        let mut matrix = [[0; 3]; 3];
+
+        for (position, value) in matrix.iter_mut().flatten().enumerate() {
+            *value = index / 3i32.pow(position as u32) % 3 - 1;
+        }
+
+        if determinant(&matrix) == 1 {
+            bases.push((matrix, adjugate(&matrix)));
+        }
+    }
+
+    let identity = [[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+
+    bases.sort_by_key(|(matrix, _)| {
+        (
+            *matrix != identity,
+            matrix.iter().flatten().filter(|value| **value != 0).count(),
+        )
+    });
+
+    bases
+}
+
+/// `{P|p}` that maps `operations` onto those of `standard`
+fn find_transformation(
+    operations: &[(Matrix, [i32; 3])],
+    standard: &SpaceGroupSetting,
+    bases: &[(Matrix, Matrix)],
+) -> Option<SymmetryEquivTransform> {
+    let target = target_operations(standard);
+
+    let target_operations = target
+        .iter()
+        .flat_map(|(rotation, translations)| {
+            translations
+                .iter()
+                .map(|translation| (*rotation, *translation))
+        })
+        .collect::<Vec<_>>();
+
+    if signature(operations) != signature(&target_operations) {
+        return None;
+    }
+
+    for (basis, inverse) in bases {
+        // P W P⁻¹ and P w
+        let transformed = operations
+            .iter()
+            .map(|(rotation, translation)| {
+                (
+                    multiply(&multiply(basis, rotation), inverse),
+                    apply(basis, translation),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if !transformed
+            .iter()
+            .all(|(rotation, _)| target.contains_key(rotation))
+        {
+            continue;
+        }
+
+        // an origin shift p changes the translations to P w + p - P W P⁻¹ p
+        for index in 0..STEPS.pow(3) {
+            let shift = [index % STEPS, index / STEPS % STEPS, index / STEPS / STEPS];
+
+            let matches = transformed.iter().all(|(rotation, translation)| {
+                let moved = apply(rotation, &shift);
+
+                let translation =
+                    [0, 1, 2].map(|i| (translation[i] + shift[i] - moved[i]).rem_euclid(STEPS));
+
+                target[rotation].contains(&translation)
+            });
+
+            if matches {
+                return Some(SymmetryEquivTransform {
+                    rotation: basis.map(|row| row.map(|value| Fraction::from(value as i64))),
+                    translation: shift.map(|value| Fraction::new(value as u64, STEPS as u64)),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Number of operations of each rotation type with and without a screw or glide component, which
+/// does not change with the basis or the origin
+fn signature(operations: &[(Matrix, [i32; 3])]) -> [[usize; 2]; 10] {
+    let identity = [[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+
+    let centring = operations
+        .iter()
+        .filter(|(rotation, _)| *rotation == identity)
+        .map(|(_, translation)| *translation)
+        .collect::<HashSet<_>>();
+
+    let mut signature = [[0; 2]; 10];
+
+    for (rotation, translation) in operations {
+        let trace = rotation[0][0] + rotation[1][1] + rotation[2][2];
+
+        // index in 1, 2, 3, 4, 6, -1, m, -3, -4, -6 and the order of the operation
+        let (index, order) = match (determinant(rotation), trace) {
+            (1, 3) => (0, 1),
+            (1, -1) => (1, 2),
+            (1, 0) => (2, 3),
+            (1, 1) => (3, 4),
+            (1, 2) => (4, 6),
+            (-1, -3) => (5, 2),
+            (-1, 1) => (6, 2),
+            (-1, 0) => (7, 6),
+            (-1, -1) => (8, 4),
+            (-1, -2) => (9, 6),
+            _ => continue,
+        };
+
+        // (W, w)^n = (I, t) with t = S w and S = 1 + W + ... + W^(n-1). The operation combined
+        // with a centring translation c has a fixed point if t + S c = -S n for a lattice vector n.
+        let mut sum = [[0; 3]; 3];
+        let mut power = identity;
+
+        for _ in 0..order {
+            for (row, power_row) in sum.iter_mut().zip(&power) {
+                for (value, power_value) in row.iter_mut().zip(power_row) {
+                    *value += power_value;
+                }
+            }
+
+            power = multiply(&power, rotation);
+        }
+
+        let has_fixed_point = centring.iter().any(|centring| {
+            let target = [0, 1, 2].map(|i| {
+                (0..3)
+                    .map(|k| sum[i][k] * (translation[k] + centring[k]))
+                    .sum::<i32>()
+            });
+
+            (0..9i32.pow(3)).any(|index| {
+                let lattice = [index % 9, index / 9 % 9, index / 81].map(|n| (n - 4) * STEPS);
+
+                (0..3).all(|i| (0..3).map(|k| sum[i][k] * lattice[k]).sum::<i32>() + target[i] == 0)
+            })
+        });
+
+        signature[index][usize::from(!has_fixed_point)] += 1;
+    }
+
+    signature
+}
+
+fn multiply(a: &Matrix, b: &Matrix) -> Matrix {
+    let mut product = [[0; 3]; 3];
+
+    for (i, row) in product.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+
+    product
+}
+
+/// `m v` modulo 24
+fn apply(m: &Matrix, v: &[i32; 3]) -> [i32; 3] {
+    m.map(|row| (0..3).map(|k| row[k] * v[k]).sum::<i32>().rem_euclid(STEPS))
+}
+
+fn determinant(m: &Matrix) -> i32 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+/// The inverse of a matrix with determinant 1
+fn adjugate(m: &Matrix) -> Matrix {
+    let mut adjugate = [[0; 3]; 3];
+
+    for (i, row) in adjugate.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            let (r1, r2) = ((j + 1) % 3, (j + 2) % 3);
+            let (c1, c2) = ((i + 1) % 3, (i + 2) % 3);
+
+            *value = m[r1][c1] * m[r2][c2] - m[r1][c2] * m[r2][c1];
+        }
+    }
+
+    adjugate
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::symmetry::{SpaceGroupSetting, SymmetryEquivPosAsXYZ, SymmetryEquivTransform};
+    use crate::Parser;
+
+    use super::{SpaceGroupIdentification, SpaceGroupMismatch};
+
+    /// Checks that the transformation maps the operations onto those of the standard setting
+    fn assert_transforms(operations: &SymmetryEquivPosAsXYZ, found: &SpaceGroupIdentification) {
+        let transformation = found.transformation.as_ref().unwrap();
+        let inverse = transformation.inverse().unwrap();
+
+        let transformed = operations
+            .0
+            .iter()
+            .map(|operation| {
+                transformation
+                    .compose(operation)
+                    .compose(&inverse)
+                    .normalized()
+            })
+            .collect::<HashSet<_>>();
+
+        let expected = found
+            .standard
+            .operations()
+            .0
+            .iter()
+            .map(SymmetryEquivTransform::normalized)
+            .collect::<HashSet<_>>();
+
+        assert_eq!(transformed, expected);
+    }
+
+    #[test]
+    fn test_identify_space_group() {
+        for (symbol, number, setting) in [
+            ("P m -3 m", 221, ""),
+            ("F d -3 m :2", 227, "2"),
+            ("P 1 21/n 1", 14, "b2"),
+            ("P 63/m m c", 194, ""),
+        ] {
+            let operations = SpaceGroupSetting::from_hermann_mauguin(symbol)
+                .unwrap()
+                .operations();
+
+            let found = operations.identify_space_group().unwrap();
+
+            assert_eq!(found.standard.number, number);
+            assert_eq!(found.setting.unwrap().setting, setting);
+            assert_transforms(&operations, &found);
+        }
+
+        let operations = SpaceGroupSetting::from_hermann_mauguin("R -3 m :R")
+            .unwrap()
+            .operations();
+
+        let found = operations.identify_space_group().unwrap();
+
+        assert_eq!(found.setting.unwrap().setting, "R");
+        assert_eq!(found.standard.setting, "H");
+        assert_eq!(found.transformation, None);
+
+        // P b n m, the setting c b a of P n m a
+        let basis: SymmetryEquivTransform = "y,x,-z".parse().unwrap();
+
+        let operations = SymmetryEquivPosAsXYZ(
+            SpaceGroupSetting::from_number(62)
+                .unwrap()
+                .operations()
+                .0
+                .iter()
+                .map(|operation| basis.compose(operation).compose(&basis.inverse().unwrap()))
+                .collect(),
+        );
+
+        let found = operations.identify_space_group().unwrap();
+
+        assert_eq!(found.setting, None);
+        assert_eq!(found.standard.number, 62);
+        assert_transforms(&operations, &found);
+
+        let operations = SymmetryEquivPosAsXYZ(vec![
+            "x,y,z".parse().unwrap(),
+            "-x,-y,z".parse().unwrap(),
+            "x+1/2,y,z".parse().unwrap(),
+        ]);
+
+        assert_eq!(operations.identify_space_group(), None);
+    }
+
+    #[test]
+    fn test_space_group_mismatches() {
+        let bytes = std::fs::read(r"assets\BaTiO3.cif").unwrap();
+        let data = Parser::new(&bytes).parse();
+
+        assert_eq!(
+            data.first_key_value()
+                .unwrap()
+                .1
+                .space_group_mismatches()
+                .unwrap(),
+            []
+        );
+
+        let bytes = std::fs::read(r"assets\diamond.cif").unwrap();
+        let data = Parser::new(&bytes).parse();
+
+        assert_eq!(
+            data.first_key_value()
+                .unwrap()
+                .1
+                .space_group_mismatches()
+                .unwrap(),
+            []
+        );
+
+        let text = String::from_utf8(bytes)
+            .unwrap()
+            .replace("'F d -3 m S'", "'F d -3 m Z'")
+            .replace(
+                "_symmetry_Int_Tables_number 227",
+                "_symmetry_Int_Tables_number 225",
+            );
+
+        let data = Parser::new(text.as_bytes()).parse();
+
+        assert_eq!(
+            data.first_key_value()
+                .unwrap()
+                .1
+                .space_group_mismatches()
+                .unwrap(),
+            [
+                SpaceGroupMismatch::Number {
+                    declared: 225,
+                    identified: 227
+                },
+                SpaceGroupMismatch::Symbol {
+                    declared: "F d -3 m Z".to_string(),
+                    identified: "F d -3 m :1".to_string()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_space_group_mismatches_without_number() {
+        let bytes = std::fs::read(r"assets\diamond.cif").unwrap();
+
+        // only the Hermann–Mauguin symbol is declared, so the number cannot mismatch
+        let text = String::from_utf8(bytes)
+            .unwrap()
+            .replace("_symmetry_Int_Tables_number 227", "");
+
+        let data = Parser::new(text.as_bytes()).parse();
+        let data = data.first_key_value().unwrap().1;
+
+        assert!(!data.contains_key("_symmetry_Int_Tables_number"));
+        assert_eq!(data.space_group_mismatches().unwrap(), []);
+
+        let found = data
+            .symmetry_equiv_pos_as_xyz()
+            .unwrap()
+            .identify_space_group()
+            .unwrap();
+
+        assert_eq!(found.mismatches(Some("F d -3 m S"), None), []);
+        assert_eq!(
+            found.mismatches(None, Some(225)),
+            [SpaceGroupMismatch::Number {
+                declared: 225,
+                identified: 227
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unknown_declarations() {
+        let bytes = std::fs::read(r"assets\BaTiO3.cif").unwrap();
+
+        let text = String::from_utf8(bytes)
+            .unwrap()
+            .replace("'P m -3 m'", "'P m -3 x'")
+            .replace(
+                "_symmetry_Int_Tables_number        221",
+                "_symmetry_Int_Tables_number        2x1",
+            );
+
+        let data = Parser::new(text.as_bytes()).parse();
+
+        assert_eq!(
+            data.first_key_value()
+                .unwrap()
+                .1
+                .space_group_mismatches()
+                .unwrap(),
+            [
+                SpaceGroupMismatch::InvalidNumber {
+                    declared: "2x1".to_string()
+                },
+                SpaceGroupMismatch::UnknownSymbol {
+                    declared: "P m -3 x".to_string()
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,213 @@
+// Site multiplicity and site symmetry of positions under the operations of a space group
+
+use fraction::Fraction;
+
+use super::{SymmetryEquivPosAsXYZ, SymmetryEquivTransform};
+
+/// Default distance in fractional coordinates below which two images of a position coincide
+pub const DEFAULT_SITE_TOLERANCE_FRACTIONAL: f64 = 1e-3;
+
+/// Point groups by the number of operations of each type, in the order
+/// `1, 2, 3, 4, 6, -1, m, -3, -4, -6`
+const POINT_GROUPS: [(&str, [u8; 10]); 32] = [
+    ("1", [1, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ("-1", [1, 0, 0, 0, 0, 1, 0, 0, 0, 0]),
+    ("2", [1, 1, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ("m", [1, 0, 0, 0, 0, 0, 1, 0, 0, 0]),
+    ("2/m", [1, 1, 0, 0, 0, 1, 1, 0, 0, 0]),
+    ("222", [1, 3, 0, 0, 0, 0, 0, 0, 0, 0]),
+    ("mm2", [1, 1, 0, 0, 0, 0, 2, 0, 0, 0]),
+    ("mmm", [1, 3, 0, 0, 0, 1, 3, 0, 0, 0]),
+    ("4", [1, 1, 0, 2, 0, 0, 0, 0, 0, 0]),
+    ("-4", [1, 1, 0, 0, 0, 0, 0, 0, 2, 0]),
+    ("4/m", [1, 1, 0, 2, 0, 1, 1, 0, 2, 0]),
+    ("422", [1, 5, 0, 2, 0, 0, 0, 0, 0, 0]),
+    ("4mm", [1, 1, 0, 2, 0, 0, 4, 0, 0, 0]),
+    ("-42m", [1, 3, 0, 0, 0, 0, 2, 0, 2, 0]),
+    ("4/mmm", [1, 5, 0, 2, 0, 1, 5, 0, 2, 0]),
+    ("3", [1, 0, 2, 0, 0, 0, 0, 0, 0, 0]),
+    ("-3", [1, 0, 2, 0, 0, 1, 0, 2, 0, 0]),
+    ("32", [1, 3, 2, 0, 0, 0, 0, 0, 0, 0]),
+    ("3m", [1, 0, 2, 0, 0, 0, 3, 0, 0, 0]),
+    ("-3m", [1, 3, 2, 0, 0, 1, 3, 2, 0, 0]),
+    ("6", [1, 1, 2, 0, 2, 0, 0, 0, 0, 0]),
+    ("-6", [1, 0, 2, 0, 0, 0, 1, 0, 0, 2]),
+    ("6/m", [1, 1, 2, 0, 2, 1, 1, 2, 0, 2]),
+    ("622", [1, 7, 2, 0, 2, 0, 0, 0, 0, 0]),
+    ("6mm", [1, 1, 2, 0, 2, 0, 6, 0, 0, 0]),
+    ("-6m2", [1, 3, 2, 0, 0, 0, 4, 0, 0, 2]),
+    ("6/mmm", [1, 7, 2, 0, 2, 1, 7, 2, 0, 2]),
+    ("23", [1, 3, 8, 0, 0, 0, 0, 0, 0, 0]),
+    ("m-3", [1, 3, 8, 0, 0, 1, 3, 8, 0, 0]),
+    ("432", [1, 9, 8, 6, 0, 0, 0, 0, 0, 0]),
+    ("-43m", [1, 3, 8, 0, 0, 0, 6, 0, 6, 0]),
+    ("m-3m", [1, 9, 8, 6, 0, 1, 9, 8, 6, 0]),
+];
+
+/// Orbit and stabilizer of a position
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct SiteSymmetry {
+    /// Number of distinct images of the position in the unit cell
+    pub multiplicity: usize,
+    /// Operations that map the position onto itself, with translations that keep it in place
+    pub operations: Vec<SymmetryEquivTransform>,
+    /// Point group of the operations like `-43m`, without the orientation of its elements
+    pub point_group: Option<&'static str>,
+}
+
+impl SymmetryEquivPosAsXYZ {
+    /// Multiplicity and site-symmetry group of `point`, where positions closer than
+    /// `tolerance_fractional` in every coordinate (across the cell boundaries) coincide
+    pub fn site_symmetry(
+        &self,
+        point: [f64; 3],
+        tolerance_fractional: f64,
+    ) -> anyhow::Result<SiteSymmetry> {
+        let mut images: Vec<[f64; 3]> = Vec::new();
+        let mut operations: Vec<SymmetryEquivTransform> = Vec::new();
+
+        for operation in &self.0 {
+            let image = operation.transform_point(point)?;
+
+            let offset = [0, 1, 2].map(|index| image[index] - point[index]);
+
+            if offset
+                .iter()
+                .all(|offset| (offset - offset.round()).abs() < tolerance_fractional)
+                && !operations
+                    .iter()
+                    .any(|stabilizer| stabilizer.rotation == operation.rotation)
+            {
+                // the lattice translation that brings the image back onto the point
+                let lattice = offset.map(|offset| Fraction::from(-offset.round() as i64));
+
+                operations.push(SymmetryEquivTransform {
+                    rotation: operation.rotation,
+                    translation: [0, 1, 2]
+                        .map(|index| operation.translation[index] + lattice[index]),
+                });
+            }
+
+            if !images.iter().any(|other| {
+                (0..3).all(|index| {
+                    let difference = other[index] - image[index];
+                    (difference - difference.round()).abs() < tolerance_fractional
+                })
+            }) {
+                images.push(image);
+            }
+        }
+
+        let point_group =
+            point_group_symbol(operations.iter().map(|operation| &operation.rotation));
+
+        Ok(SiteSymmetry {
+            multiplicity: images.len(),
+            operations,
+            point_group,
+        })
+    }
+}
+
+/// Symbol of the point group formed by distinct `rotations`, like `4/mmm`
+pub(crate) fn point_group_symbol<'a>(
+    rotations: impl Iterator<Item = &'a [[Fraction; 3]; 3]>,
+) -> Option<&'static str> {
+    let mut counts = [0u8; 10];
+
+    for rotation in rotations {
+        counts[rotation_type(rotation)? as usize] += 1;
+    }
+
+    POINT_GROUPS
+        .iter()
+        .find(|(_, expected)| *expected == counts)
+        .map(|(symbol, _)| *symbol)
+}
+
+/// Index of the type of a rotation in `1, 2, 3, 4, 6, -1, m, -3, -4, -6`, from its determinant and
+/// trace
+fn rotation_type(rotation: &[[Fraction; 3]; 3]) -> Option<u8> {
+    let trace = rotation[0][0] + rotation[1][1] + rotation[2][2];
+
+    let determinant = rotation[0][0]
+        * (rotation[1][1] * rotation[2][2] - rotation[1][2] * rotation[2][1])
+        - rotation[0][1] * (rotation[1][0] * rotation[2][2] - rotation[1][2] * rotation[2][0])
+        + rotation[0][2] * (rotation[1][0] * rotation[2][1] - rotation[1][1] * rotation[2][0]);
+
+    let trace = if determinant == Fraction::from(1) {
+        trace
+    } else if determinant == Fraction::from(-1) {
+        -trace
+    } else {
+        return None;
+    };
+
+    let offset = if determinant == Fraction::from(1) {
+        0
+    } else {
+        5
+    };
+
+    let index = [3, -1, 0, 1, 2]
+        .iter()
+        .position(|value| Fraction::from(*value as i64) == trace)?;
+
+    Some(offset + index as u8)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::symmetry::SpaceGroupSetting;
+
+    use super::DEFAULT_SITE_TOLERANCE_FRACTIONAL;
+
+    #[test]
+    fn test_site_symmetry() {
+        let operations = SpaceGroupSetting::from_hermann_mauguin("F d -3 m :1")
+            .unwrap()
+            .operations();
+
+        let cases = [
+            ([0.0, 0.0, 0.0], 8, "-43m"),
+            ([0.5, 0.5, 0.5], 8, "-43m"),
+            ([0.625, 0.625, 0.625], 16, "-3m"),
+            ([0.125, 0.125, 0.125], 16, "-3m"),
+            ([0.3, 0.3, 0.3], 32, "3m"),
+            ([0.2, 0.0, 0.0], 48, "mm2"),
+            ([0.1, 0.1, 0.3], 96, "m"),
+            ([0.11, 0.23, 0.37], 192, "1"),
+        ];
+
+        for (point, multiplicity, point_group) in cases {
+            let site = operations
+                .site_symmetry(point, DEFAULT_SITE_TOLERANCE_FRACTIONAL)
+                .unwrap();
+
+            assert_eq!(site.multiplicity, multiplicity, "{:?}", point);
+            assert_eq!(site.point_group, Some(point_group), "{:?}", point);
+            assert_eq!(site.operations.len() * multiplicity, 192, "{:?}", point);
+
+            for operation in &site.operations {
+                let image = operation.transform_point(point).unwrap();
+
+                for (image, point) in image.iter().zip(point) {
+                    assert!((image - point).abs() < 1e-9);
+                }
+            }
+        }
+
+        let operations = SpaceGroupSetting::from_number(191).unwrap().operations();
+
+        let site = operations
+            .site_symmetry(
+                [1.0 / 3.0, 2.0 / 3.0, 0.5],
+                DEFAULT_SITE_TOLERANCE_FRACTIONAL,
+            )
+            .unwrap();
+
+        assert_eq!(site.multiplicity, 2);
+        assert_eq!(site.point_group, Some("-6m2"));
+    }
+}
@@ -0,0 +1,669 @@
+// https://cci.lbl.gov/sginfo/itvb_2001_table_a1427_hall_symbols.html
+// Settings of the 230 space groups with their Hermann–Mauguin and Hall symbols and the generators
+// of their operations. Besides the standard setting of every space group this includes both origin
+// choices, hexagonal and rhombohedral axes, and the unique axis c and cell choices of the
+// monoclinic space groups.
+
+use crate::parse::GetAndParse;
+use crate::parser::DataBlock;
+
+use fraction::Fraction;
+
+use super::{SymmetryEquivPosAsXYZ, SymmetryEquivTransform};
+
+/// Setting of a space group from the built-in tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SpaceGroupSetting {
+    /// Number in the International Tables for Crystallography, Vol. A
+    pub number: u8,
+    /// Full Hermann–Mauguin symbol like `P 1 21/c 1`
+    pub hermann_mauguin: &'static str,
+    /// `1` or `2` for the origin choice, `H` or `R` for hexagonal or rhombohedral axes, `b`, `c`
+    /// or `b1` to `c1` for the unique axis and cell choice of monoclinic space groups, or empty
+    pub setting: &'static str,
+    pub hall: &'static str,
+    /// Generators of the operations besides the centring translations, separated by `;`
+    pub generators: &'static str,
+}
+
+impl SpaceGroupSetting {
+    /// Default setting of the space group: unique axis b, origin choice 1 and hexagonal axes
+    pub fn from_number(number: u8) -> Option<&'static Self> {
+        SPACE_GROUPS.iter().find(|setting| setting.number == number)
+    }
+
+    pub fn settings(number: u8) -> impl Iterator<Item = &'static Self> {
+        SPACE_GROUPS
+            .iter()
+            .filter(move |setting| setting.number == number)
+    }
+
+    /// Setting of a full or short symbol like `P 1 21/c 1`, `P21/c`, `F d -3 m :2` or `R -3 m H`.
+    /// Without a setting the first one is used, like for [`SpaceGroupSetting::from_number`].
+    pub fn from_hermann_mauguin(symbol: &str) -> Option<&'static Self> {
+        let (symbol, setting) = split_setting(symbol);
+
+        let symbol = normalize(symbol);
+
+        SPACE_GROUPS.iter().find(|candidate| {
+            let matches_symbol = normalize(candidate.hermann_mauguin) == symbol
+                || normalize(&short_symbol(candidate.hermann_mauguin)) == symbol;
+
+            let matches_setting = match &setting {
+                Some(setting) => candidate.setting.eq_ignore_ascii_case(setting),
+                None => true,
+            };
+
+            matches_symbol && matches_setting
+        })
+    }
+
+    /// Setting of a tabulated Hall symbol like `-P 2ybc`, ignoring case and spaces
+    pub fn from_hall(symbol: &str) -> Option<&'static Self> {
+        let symbol = normalize_hall(symbol);
+
+        SPACE_GROUPS
+            .iter()
+            .find(|candidate| normalize_hall(candidate.hall) == symbol)
+    }
+
+    /// Setting named by the Hall symbol, the Hermann–Mauguin symbol or the number of a data
+    /// block, in this order
+    pub fn from_data_block(map: &DataBlock) -> Option<&'static Self> {
+        let first = |keys: [&str; 2]| {
+            keys.into_iter()
+                .find_map(|key| map.get_and_parse_first::<String>(key).ok())
+        };
+
+        if let Some(setting) = first(["_space_group_name_Hall", "_symmetry_space_group_name_Hall"])
+            .and_then(|symbol| Self::from_hall(&symbol))
+        {
+            return Some(setting);
+        }
+
+        if let Some(setting) = first([
+            "_space_group_name_H-M_alt",
+            "_symmetry_space_group_name_H-M",
+        ])
+        .and_then(|symbol| Self::from_hermann_mauguin(&symbol))
+        {
+            return Some(setting);
+        }
+
+        ["_space_group_IT_number", "_symmetry_Int_Tables_number"]
+            .into_iter()
+            .find_map(|key| map.get_and_parse_first::<u8>(key).ok())
+            .and_then(Self::from_number)
+    }
+
+    /// Symbol like `F d -3 m :2` that includes the setting if the space group has several
+    pub fn hermann_mauguin_with_setting(&self) -> String {
+        match self.setting {
+            "1" | "2" | "H" | "R" => format!("{} :{}", self.hermann_mauguin, self.setting),
+            _ => self.hermann_mauguin.to_string(),
+        }
+    }
+
+    /// Number of operations modulo the lattice translations, including the centring translations
+    pub fn order(&self) -> usize {
+        let point_group_order = match self.number {
+            1 => 1,
+            2..=9 => 2,
+            10..=46 => 4,
+            47..=74 => 8,
+            75..=82 => 4,
+            83..=122 => 8,
+            123..=142 => 16,
+            143..=146 => 3,
+            147..=161 => 6,
+            162..=167 => 12,
+            168..=174 => 6,
+            175..=190 => 12,
+            191..=194 => 24,
+            195..=199 => 12,
+            200..=220 => 24,
+            _ => 48,
+        };
+
+        let centring = match (&self.hermann_mauguin[..1], self.setting) {
+            ("P", _) | ("R", "R") => 1,
+            ("A" | "B" | "C" | "I", _) => 2,
+            ("R", _) => 3,
+            _ => 4,
+        };
+
+        point_group_order * centring
+    }
+
+    /// All symmetry operations, generated from the generators and the lattice centring of the
+    /// Hall symbol
+    pub fn operations(&self) -> SymmetryEquivPosAsXYZ {
+        let generators = self
+            .generators
+            .split(';')
+            .filter(|generator| !generator.trim().is_empty())
+            .map(|generator| generator.trim().parse())
+            .collect::<anyhow::Result<Vec<_>>>()
+            .expect("the tabulated generators are valid");
+
+        let lattice = self
+            .hall
+            .trim_start_matches('-')
+            .get(..1)
+            .unwrap_or_default();
+
+        let centring = centring_translations(lattice).expect("the tabulated lattices are valid");
+
+        SymmetryEquivPosAsXYZ(generate(&generators, &centring))
+    }
+}
+
+/// Centring translations of the lattice symbol of a Hall symbol, without the zero vector
+pub(crate) fn centring_translations(lattice: &str) -> anyhow::Result<Vec<[Fraction; 3]>> {
+    let vectors: &[[(u64, u64); 3]] = match lattice.to_ascii_uppercase().as_str() {
+        "P" => &[],
+        "A" => &[[(0, 1), (1, 2), (1, 2)]],
+        "B" => &[[(1, 2), (0, 1), (1, 2)]],
+        "C" => &[[(1, 2), (1, 2), (0, 1)]],
+        "I" => &[[(1, 2), (1, 2), (1, 2)]],
+        "R" => &[[(2, 3), (1, 3), (1, 3)], [(1, 3), (2, 3), (2, 3)]],
+        "S" => &[[(1, 3), (1, 3), (2, 3)], [(2, 3), (2, 3), (1, 3)]],
+        "T" => &[[(1, 3), (2, 3), (1, 3)], [(2, 3), (1, 3), (2, 3)]],
+        "F" => &[
+            [(0, 1), (1, 2), (1, 2)],
+            [(1, 2), (0, 1), (1, 2)],
+            [(1, 2), (1, 2), (0, 1)],
+        ],
+        _ => return Err(anyhow::anyhow!("Unknown lattice symbol `{}`", lattice)),
+    };
+
+    Ok(vectors
+        .iter()
+        .map(|vector| vector.map(|(numerator, denominator)| Fraction::new(numerator, denominator)))
+        .collect())
+}
+
+/// All operations generated by `generators`, first the ones without centring translation and then
+/// each of them shifted by every centring translation
+pub(crate) fn generate(
+    generators: &[SymmetryEquivTransform],
+    centring: &[[Fraction; 3]],
+) -> Vec<SymmetryEquivTransform> {
+    // the representative of the operation modulo the centring translations
+    let canonical = |operation: SymmetryEquivTransform| {
+        std::iter::once(translate(&operation, &[Fraction::from(0); 3]))
+            .chain(centring.iter().map(|vector| translate(&operation, vector)))
+            .min_by(|a, b| a.translation.cmp(&b.translation))
+            .expect("the operation itself is a candidate")
+    };
+
+    let mut operations = vec![SymmetryEquivTransform::identity()];
+    let mut index = 0;
+
+    while index < operations.len() {
+        for generator in generators {
+            let product = canonical(operations[index].compose(generator));
+
+            if !operations.contains(&product) {
+                operations.push(product);
+            }
+        }
+
+        index += 1;
+    }
+
+    let mut all = operations.clone();
+
+    for vector in centring {
+        all.extend(
+            operations
+                .iter()
+                .map(|operation| translate(operation, vector)),
+        );
+    }
+
+    all
+}
+
+/// The operation followed by the translation `vector`, with the translation reduced to `[0, 1)`
+fn translate(operation: &SymmetryEquivTransform, vector: &[Fraction; 3]) -> SymmetryEquivTransform {
+    let mut translation = operation.translation;
+
+    for (value, component) in translation.iter_mut().zip(vector) {
+        *value += *component;
+    }
+
+    SymmetryEquivTransform {
+        rotation: operation.rotation,
+        translation,
+    }
+    .normalized()
+}
+
+/// Symbol and setting of `F d -3 m :2`, `F d -3 m S` (origin choice 1) or `R 3 H`
+fn split_setting(symbol: &str) -> (&str, Option<String>) {
+    if let Some((symbol, setting)) = symbol.split_once(':') {
+        return (symbol, Some(setting.trim().to_string()));
+    }
+
+    let symbol = symbol.trim();
+
+    match symbol.rsplit_once(char::is_whitespace) {
+        Some((rest, last)) if rest.trim().contains(char::is_whitespace) || rest.len() > 1 => {
+            let setting = match last.to_ascii_uppercase().as_str() {
+                "S" => "1",
+                "Z" => "2",
+                "H" => "H",
+                "R" => "R",
+                _ => return (symbol, None),
+            };
+
+            (rest, Some(setting.to_string()))
+        }
+        _ => (symbol, None),
+    }
+}
+
+/// Symbol without spaces and case, with the glide planes `e` instead of the older `a` or `b`
+fn normalize(symbol: &str) -> String {
+    let symbol = symbol
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_')
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    match symbol.as_str() {
+        "abm2" => "aem2".to_string(),
+        "aba2" => "aea2".to_string(),
+        "cmca" => "cmce".to_string(),
+        "cmma" => "cmme".to_string(),
+        "ccca" => "ccce".to_string(),
+        _ => symbol,
+    }
+}
+
+/// Short symbol of a monoclinic full symbol, `P 1 21/c 1` to `P 21/c`
+fn short_symbol(symbol: &str) -> String {
+    let parts = symbol.split_whitespace().collect::<Vec<_>>();
+
+    match parts.as_slice() {
+        [lattice, "1", element, "1"] | [lattice, "1", "1", element] => {
+            format!("{} {}", lattice, element)
+        }
+        _ => symbol.to_string(),
+    }
+}
+
+fn normalize_hall(symbol: &str) -> String {
+    symbol
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_ascii_lowercase()
+}
+
+macro_rules! settings {
+    ($(($number:expr, $hermann_mauguin:expr, $setting:expr, $hall:expr, $generators:expr)),*
+     $(,)?) => {
+        &[$(SpaceGroupSetting {
+            number: $number,
+            hermann_mauguin: $hermann_mauguin,
+            setting: $setting,
+            hall: $hall,
+            generators: $generators,
+        }),*]
+    };
+}
+
+/// Settings ordered by space group number, the default setting of each space group first
+#[rustfmt::skip]
+pub static SPACE_GROUPS: &[SpaceGroupSetting] = settings![
+    (1, "P 1", "", "P 1", ""),
+    (2, "P -1", "", "-P 1", "-x,-y,-z"),
+    (3, "P 1 2 1", "b", "P 2y", "-x,y,-z"),
+    (3, "P 1 1 2", "c", "P 2", "-x,-y,z"),
+    (4, "P 1 21 1", "b", "P 2yb", "-x,y+1/2,-z"),
+    (4, "P 1 1 21", "c", "P 2c", "-x,-y,z+1/2"),
+    (5, "C 1 2 1", "b1", "C 2y", "-x,y,-z"),
+    (5, "A 1 2 1", "b2", "A 2y", "-x,y,-z"),
+    (5, "I 1 2 1", "b3", "I 2y", "-x,y,-z"),
+    (5, "A 1 1 2", "c1", "A 2", "-x,-y,z"),
+    (6, "P 1 m 1", "b", "P -2y", "x,-y,z"),
+    (6, "P 1 1 m", "c", "P -2", "x,y,-z"),
+    (7, "P 1 c 1", "b1", "P -2yc", "x,-y,z+1/2"),
+    (7, "P 1 n 1", "b2", "P -2yac", "x+1/2,-y,z+1/2"),
+    (7, "P 1 a 1", "b3", "P -2ya", "x+1/2,-y,z"),
+    (7, "P 1 1 a", "c1", "P -2a", "x+1/2,y,-z"),
+    (8, "C 1 m 1", "b1", "C -2y", "x,-y,z"),
+    (8, "A 1 m 1", "b2", "A -2y", "x,-y,z"),
+    (8, "I 1 m 1", "b3", "I -2y", "x,-y,z"),
+    (8, "A 1 1 m", "c1", "A -2", "x,y,-z"),
+    (9, "C 1 c 1", "b1", "C -2yc", "x,-y,z+1/2"),
+    (9, "A 1 n 1", "b2", "A -2yab", "x+1/2,-y+1/2,z"),
+    (9, "I 1 a 1", "b3", "I -2ya", "x+1/2,-y,z"),
+    (9, "A 1 1 a", "c1", "A -2a", "x+1/2,y,-z"),
+    (10, "P 1 2/m 1", "b", "-P 2y", "-x,-y,-z; -x,y,-z"),
+    (10, "P 1 1 2/m", "c", "-P 2", "-x,-y,-z; -x,-y,z"),
+    (11, "P 1 21/m 1", "b", "-P 2yb", "-x,-y,-z; -x,y+1/2,-z"),
+    (11, "P 1 1 21/m", "c", "-P 2c", "-x,-y,-z; -x,-y,z+1/2"),
+    (12, "C 1 2/m 1", "b1", "-C 2y", "-x,-y,-z; -x,y,-z"),
+    (12, "A 1 2/m 1", "b2", "-A 2y", "-x,-y,-z; -x,y,-z"),
+    (12, "I 1 2/m 1", "b3", "-I 2y", "-x,-y,-z; -x,y,-z"),
+    (12, "A 1 1 2/m", "c1", "-A 2", "-x,-y,-z; -x,-y,z"),
+    (13, "P 1 2/c 1", "b1", "-P 2yc", "-x,-y,-z; -x,y,-z+1/2"),
+    (13, "P 1 2/n 1", "b2", "-P 2yac", "-x,-y,-z; -x+1/2,y,-z+1/2"),
+    (13, "P 1 2/a 1", "b3", "-P 2ya", "-x,-y,-z; -x+1/2,y,-z"),
+    (13, "P 1 1 2/a", "c1", "-P 2a", "-x,-y,-z; -x+1/2,-y,z"),
+    (14, "P 1 21/c 1", "b1", "-P 2ybc", "-x,-y,-z; -x,y+1/2,-z+1/2"),
+    (14, "P 1 21/n 1", "b2", "-P 2yn", "-x,-y,-z; -x+1/2,y+1/2,-z+1/2"),
+    (14, "P 1 21/a 1", "b3", "-P 2yab", "-x,-y,-z; -x+1/2,y+1/2,-z"),
+    (14, "P 1 1 21/a", "c1", "-P 2ac", "-x,-y,-z; -x+1/2,-y,z+1/2"),
+    (15, "C 1 2/c 1", "b1", "-C 2yc", "-x,-y,-z; -x,y,-z+1/2"),
+    (15, "A 1 2/n 1", "b2", "-A 2yab", "-x,-y,-z; -x+1/2,y+1/2,-z"),
+    (15, "I 1 2/a 1", "b3", "-I 2ya", "-x,-y,-z; -x+1/2,y,-z"),
+    (15, "A 1 1 2/a", "c1", "-A 2a", "-x,-y,-z; -x+1/2,-y,z"),
+    (16, "P 2 2 2", "", "P 2 2", "-x,-y,z; x,-y,-z"),
+    (17, "P 2 2 21", "", "P 2c 2", "-x,-y,z+1/2; x,-y,-z"),
+    (18, "P 21 21 2", "", "P 2 2ab", "-x,-y,z; x+1/2,-y+1/2,-z"),
+    (19, "P 21 21 21", "", "P 2ac 2ab", "-x+1/2,-y,z+1/2; x+1/2,-y+1/2,-z"),
+    (20, "C 2 2 21", "", "C 2c 2", "-x,-y,z+1/2; x,-y,-z"),
+    (21, "C 2 2 2", "", "C 2 2", "-x,-y,z; x,-y,-z"),
+    (22, "F 2 2 2", "", "F 2 2", "-x,-y,z; x,-y,-z"),
+    (23, "I 2 2 2", "", "I 2 2", "-x,-y,z; x,-y,-z"),
+    (24, "I 21 21 21", "", "I 2b 2c", "-x,-y+1/2,z; x,-y,-z+1/2"),
+    (25, "P m m 2", "", "P 2 -2", "-x,-y,z; -x,y,z"),
+    (26, "P m c 21", "", "P 2c -2", "-x,-y,z+1/2; -x,y,z"),
+    (27, "P c c 2", "", "P 2 -2c", "-x,-y,z; -x,y,z+1/2"),
+    (28, "P m a 2", "", "P 2 -2a", "-x,-y,z; -x+1/2,y,z"),
+    (29, "P c a 21", "", "P 2c -2ac", "-x,-y,z+1/2; -x+1/2,y,z+1/2"),
+    (30, "P n c 2", "", "P 2 -2bc", "-x,-y,z; -x,y+1/2,z+1/2"),
+    (31, "P m n 21", "", "P 2ac -2", "-x+1/2,-y,z+1/2; -x,y,z"),
+    (32, "P b a 2", "", "P 2 -2ab", "-x,-y,z; -x+1/2,y+1/2,z"),
+    (33, "P n a 21", "", "P 2c -2n", "-x,-y,z+1/2; -x+1/2,y+1/2,z+1/2"),
+    (34, "P n n 2", "", "P 2 -2n", "-x,-y,z; -x+1/2,y+1/2,z+1/2"),
+    (35, "C m m 2", "", "C 2 -2", "-x,-y,z; -x,y,z"),
+    (36, "C m c 21", "", "C 2c -2", "-x,-y,z+1/2; -x,y,z"),
+    (37, "C c c 2", "", "C 2 -2c", "-x,-y,z; -x,y,z+1/2"),
+    (38, "A m m 2", "", "A 2 -2", "-x,-y,z; -x,y,z"),
+    (39, "A e m 2", "", "A 2 -2c", "-x,-y,z; -x,y,z+1/2"),
+    (40, "A m a 2", "", "A 2 -2a", "-x,-y,z; -x+1/2,y,z"),
+    (41, "A e a 2", "", "A 2 -2ac", "-x,-y,z; -x+1/2,y,z+1/2"),
+    (42, "F m m 2", "", "F 2 -2", "-x,-y,z; -x,y,z"),
+    (43, "F d d 2", "", "F 2 -2d", "-x,-y,z; -x+1/4,y+1/4,z+1/4"),
+    (44, "I m m 2", "", "I 2 -2", "-x,-y,z; -x,y,z"),
+    (45, "I b a 2", "", "I 2 -2c", "-x,-y,z; -x,y,z+1/2"),
+    (46, "I m a 2", "", "I 2 -2a", "-x,-y,z; -x+1/2,y,z"),
+    (47, "P m m m", "", "-P 2 2", "-x,-y,-z; -x,-y,z; x,-y,-z"),
+    (48, "P n n n", "1", "P 2 2 -1n", "-x,-y,z; x,-y,-z; -x+1/2,-y+1/2,-z+1/2"),
+    (48, "P n n n", "2", "-P 2ab 2bc", "-x,-y,-z; -x+1/2,-y+1/2,z; x,-y+1/2,-z+1/2"),
+    (49, "P c c m", "", "-P 2 2c", "-x,-y,-z; -x,-y,z; x,-y,-z+1/2"),
+    (50, "P b a n", "1", "P 2 2 -1ab", "-x,-y,z; x,-y,-z; -x+1/2,-y+1/2,-z"),
+    (50, "P b a n", "2", "-P 2ab 2b", "-x,-y,-z; -x+1/2,-y+1/2,z; x,-y+1/2,-z"),
+    (51, "P m m a", "", "-P 2a 2a", "-x,-y,-z; -x+1/2,-y,z; x+1/2,-y,-z"),
+    (52, "P n n a", "", "-P 2a 2bc", "-x,-y,-z; -x+1/2,-y,z; x,-y+1/2,-z+1/2"),
+    (53, "P m n a", "", "-P 2ac 2", "-x,-y,-z; -x+1/2,-y,z+1/2; x,-y,-z"),
+    (54, "P c c a", "", "-P 2a 2ac", "-x,-y,-z; -x+1/2,-y,z; x+1/2,-y,-z+1/2"),
+    (55, "P b a m", "", "-P 2 2ab", "-x,-y,-z; -x,-y,z; x+1/2,-y+1/2,-z"),
+    (56, "P c c n", "", "-P 2ab 2ac", "-x,-y,-z; -x+1/2,-y+1/2,z; x+1/2,-y,-z+1/2"),
+    (57, "P b c m", "", "-P 2c 2b", "-x,-y,-z; -x,-y,z+1/2; x,-y+1/2,-z"),
+    (58, "P n n m", "", "-P 2 2n", "-x,-y,-z; -x,-y,z; x+1/2,-y+1/2,-z+1/2"),
+    (59, "P m m n", "1", "P 2 2ab -1ab", "-x,-y,z; x+1/2,-y+1/2,-z; -x+1/2,-y+1/2,-z"),
+    (59, "P m m n", "2", "-P 2ab 2a", "-x,-y,-z; -x+1/2,-y+1/2,z; x+1/2,-y,-z"),
+    (60, "P b c n", "", "-P 2n 2ab", "-x,-y,-z; -x+1/2,-y+1/2,z+1/2; x+1/2,-y+1/2,-z"),
+    (61, "P b c a", "", "-P 2ac 2ab", "-x,-y,-z; -x+1/2,-y,z+1/2; x+1/2,-y+1/2,-z"),
+    (62, "P n m a", "", "-P 2ac 2n", "-x,-y,-z; -x+1/2,-y,z+1/2; x+1/2,-y+1/2,-z+1/2"),
+    (63, "C m c m", "", "-C 2c 2", "-x,-y,-z; -x,-y,z+1/2; x,-y,-z"),
+    (64, "C m c e", "", "-C 2ac 2", "-x,-y,-z; -x+1/2,-y,z+1/2; x,-y,-z"),
+    (65, "C m m m", "", "-C 2 2", "-x,-y,-z; -x,-y,z; x,-y,-z"),
+    (66, "C c c m", "", "-C 2 2c", "-x,-y,-z; -x,-y,z; x,-y,-z+1/2"),
+    (67, "C m m e", "", "-C 2a 2", "-x,-y,-z; -x+1/2,-y,z; x,-y,-z"),
+    (68, "C c c e", "1", "C 2 2 -1ac", "-x,-y,z; x,-y,-z; -x+1/2,-y,-z+1/2"),
+    (68, "C c c e", "2", "-C 2a 2ac", "-x,-y,-z; -x+1/2,-y,z; x+1/2,-y,-z+1/2"),
+    (69, "F m m m", "", "-F 2 2", "-x,-y,-z; -x,-y,z; x,-y,-z"),
+    (70, "F d d d", "1", "F 2 2 -1d", "-x,-y,z; x,-y,-z; -x+1/4,-y+1/4,-z+1/4"),
+    (70, "F d d d", "2", "-F 2uv 2vw", "-x,-y,-z; -x+1/4,-y+1/4,z; x,-y+1/4,-z+1/4"),
+    (71, "I m m m", "", "-I 2 2", "-x,-y,-z; -x,-y,z; x,-y,-z"),
+    (72, "I b a m", "", "-I 2 2c", "-x,-y,-z; -x,-y,z; x,-y,-z+1/2"),
+    (73, "I b c a", "", "-I 2b 2c", "-x,-y,-z; -x,-y+1/2,z; x,-y,-z+1/2"),
+    (74, "I m m a", "", "-I 2b 2", "-x,-y,-z; -x,-y+1/2,z; x,-y,-z"),
+    (75, "P 4", "", "P 4", "-y,x,z"),
+    (76, "P 41", "", "P 4w", "-y,x,z+1/4"),
+    (77, "P 42", "", "P 4c", "-y,x,z+1/2"),
+    (78, "P 43", "", "P 4cw", "-y,x,z+3/4"),
+    (79, "I 4", "", "I 4", "-y,x,z"),
+    (80, "I 41", "", "I 4bw", "-y,x+1/2,z+1/4"),
+    (81, "P -4", "", "P -4", "y,-x,-z"),
+    (82, "I -4", "", "I -4", "y,-x,-z"),
+    (83, "P 4/m", "", "-P 4", "-x,-y,-z; -y,x,z"),
+    (84, "P 42/m", "", "-P 4c", "-x,-y,-z; -y,x,z+1/2"),
+    (85, "P 4/n", "1", "P 4ab -1ab", "-y+1/2,x+1/2,z; -x+1/2,-y+1/2,-z"),
+    (85, "P 4/n", "2", "-P 4a", "-x,-y,-z; -y+1/2,x,z"),
+    (86, "P 42/n", "1", "P 4n -1n", "-y+1/2,x+1/2,z+1/2; -x+1/2,-y+1/2,-z+1/2"),
+    (86, "P 42/n", "2", "-P 4bc", "-x,-y,-z; -y,x+1/2,z+1/2"),
+    (87, "I 4/m", "", "-I 4", "-x,-y,-z; -y,x,z"),
+    (88, "I 41/a", "1", "I 4bw -1bw", "-y,x+1/2,z+1/4; -x,-y+1/2,-z+1/4"),
+    (88, "I 41/a", "2", "-I 4ad", "-x,-y,-z; -y+3/4,x+1/4,z+1/4"),
+    (89, "P 4 2 2", "", "P 4 2", "-y,x,z; x,-y,-z"),
+    (90, "P 4 21 2", "", "P 4ab 2ab", "-y+1/2,x+1/2,z; x+1/2,-y+1/2,-z"),
+    (91, "P 41 2 2", "", "P 4w 2c", "-y,x,z+1/4; x,-y,-z+1/2"),
+    (92, "P 41 21 2", "", "P 4abw 2nw", "-y+1/2,x+1/2,z+1/4; x+1/2,-y+1/2,-z+3/4"),
+    (93, "P 42 2 2", "", "P 4c 2", "-y,x,z+1/2; x,-y,-z"),
+    (94, "P 42 21 2", "", "P 4n 2n", "-y+1/2,x+1/2,z+1/2; x+1/2,-y+1/2,-z+1/2"),
+    (95, "P 43 2 2", "", "P 4cw 2c", "-y,x,z+3/4; x,-y,-z+1/2"),
+    (96, "P 43 21 2", "", "P 4nw 2abw", "-y+1/2,x+1/2,z+3/4; x+1/2,-y+1/2,-z+1/4"),
+    (97, "I 4 2 2", "", "I 4 2", "-y,x,z; x,-y,-z"),
+    (98, "I 41 2 2", "", "I 4bw 2bw", "-y,x+1/2,z+1/4; x,-y+1/2,-z+1/4"),
+    (99, "P 4 m m", "", "P 4 -2", "-y,x,z; -x,y,z"),
+    (100, "P 4 b m", "", "P 4 -2ab", "-y,x,z; -x+1/2,y+1/2,z"),
+    (101, "P 42 c m", "", "P 4c -2c", "-y,x,z+1/2; -x,y,z+1/2"),
+    (102, "P 42 n m", "", "P 4n -2n", "-y+1/2,x+1/2,z+1/2; -x+1/2,y+1/2,z+1/2"),
+    (103, "P 4 c c", "", "P 4 -2c", "-y,x,z; -x,y,z+1/2"),
+    (104, "P 4 n c", "", "P 4 -2n", "-y,x,z; -x+1/2,y+1/2,z+1/2"),
+    (105, "P 42 m c", "", "P 4c -2", "-y,x,z+1/2; -x,y,z"),
+    (106, "P 42 b c", "", "P 4c -2ab", "-y,x,z+1/2; -x+1/2,y+1/2,z"),
+    (107, "I 4 m m", "", "I 4 -2", "-y,x,z; -x,y,z"),
+    (108, "I 4 c m", "", "I 4 -2c", "-y,x,z; -x,y,z+1/2"),
+    (109, "I 41 m d", "", "I 4bw -2", "-y,x+1/2,z+1/4; -x,y,z"),
+    (110, "I 41 c d", "", "I 4bw -2c", "-y,x+1/2,z+1/4; -x,y,z+1/2"),
+    (111, "P -4 2 m", "", "P -4 2", "y,-x,-z; x,-y,-z"),
+    (112, "P -4 2 c", "", "P -4 2c", "y,-x,-z; x,-y,-z+1/2"),
+    (113, "P -4 21 m", "", "P -4 2ab", "y,-x,-z; x+1/2,-y+1/2,-z"),
+    (114, "P -4 21 c", "", "P -4 2n", "y,-x,-z; x+1/2,-y+1/2,-z+1/2"),
+    (115, "P -4 m 2", "", "P -4 -2", "y,-x,-z; -x,y,z"),
+    (116, "P -4 c 2", "", "P -4 -2c", "y,-x,-z; -x,y,z+1/2"),
+    (117, "P -4 b 2", "", "P -4 -2ab", "y,-x,-z; -x+1/2,y+1/2,z"),
+    (118, "P -4 n 2", "", "P -4 -2n", "y,-x,-z; -x+1/2,y+1/2,z+1/2"),
+    (119, "I -4 m 2", "", "I -4 -2", "y,-x,-z; -x,y,z"),
+    (120, "I -4 c 2", "", "I -4 -2c", "y,-x,-z; -x,y,z+1/2"),
+    (121, "I -4 2 m", "", "I -4 2", "y,-x,-z; x,-y,-z"),
+    (122, "I -4 2 d", "", "I -4 2bw", "y,-x,-z; x,-y+1/2,-z+1/4"),
+    (123, "P 4/m m m", "", "-P 4 2", "-x,-y,-z; -y,x,z; x,-y,-z"),
+    (124, "P 4/m c c", "", "-P 4 2c", "-x,-y,-z; -y,x,z; x,-y,-z+1/2"),
+    (125, "P 4/n b m", "1", "P 4 2 -1ab", "-y,x,z; x,-y,-z; -x+1/2,-y+1/2,-z"),
+    (125, "P 4/n b m", "2", "-P 4a 2b", "-x,-y,-z; -y+1/2,x,z; x,-y+1/2,-z"),
+    (126, "P 4/n n c", "1", "P 4 2 -1n", "-y,x,z; x,-y,-z; -x+1/2,-y+1/2,-z+1/2"),
+    (126, "P 4/n n c", "2", "-P 4a 2bc", "-x,-y,-z; -y+1/2,x,z; x,-y+1/2,-z+1/2"),
+    (127, "P 4/m b m", "", "-P 4 2ab", "-x,-y,-z; -y,x,z; x+1/2,-y+1/2,-z"),
+    (128, "P 4/m n c", "", "-P 4 2n", "-x,-y,-z; -y,x,z; x+1/2,-y+1/2,-z+1/2"),
+    (129, "P 4/n m m", "1", "P 4ab 2ab -1ab", "-y+1/2,x+1/2,z; x+1/2,-y+1/2,-z; -x+1/2,-y+1/2,-z"),
+    (129, "P 4/n m m", "2", "-P 4a 2a", "-x,-y,-z; -y+1/2,x,z; x+1/2,-y,-z"),
+    (130, "P 4/n c c", "1", "P 4ab 2n -1ab", "-y+1/2,x+1/2,z; x+1/2,-y+1/2,-z+1/2; -x+1/2,-y+1/2,-z"),
+    (130, "P 4/n c c", "2", "-P 4a 2ac", "-x,-y,-z; -y+1/2,x,z; x+1/2,-y,-z+1/2"),
+    (131, "P 42/m m c", "", "-P 4c 2", "-x,-y,-z; -y,x,z+1/2; x,-y,-z"),
+    (132, "P 42/m c m", "", "-P 4c 2c", "-x,-y,-z; -y,x,z+1/2; x,-y,-z+1/2"),
+    (133, "P 42/n b c", "1", "P 4n 2c -1n", "-y+1/2,x+1/2,z+1/2; x,-y,-z+1/2; -x+1/2,-y+1/2,-z+1/2"),
+    (133, "P 42/n b c", "2", "-P 4ac 2b", "-x,-y,-z; -y+1/2,x,z+1/2; x,-y+1/2,-z"),
+    (134, "P 42/n n m", "1", "P 4n 2 -1n", "-y+1/2,x+1/2,z+1/2; x,-y,-z; -x+1/2,-y+1/2,-z+1/2"),
+    (134, "P 42/n n m", "2", "-P 4ac 2bc", "-x,-y,-z; -y+1/2,x,z+1/2; x,-y+1/2,-z+1/2"),
+    (135, "P 42/m b c", "", "-P 4c 2ab", "-x,-y,-z; -y,x,z+1/2; x+1/2,-y+1/2,-z"),
+    (136, "P 42/m n m", "", "-P 4n 2n", "-x,-y,-z; -y+1/2,x+1/2,z+1/2; x+1/2,-y+1/2,-z+1/2"),
+    (137, "P 42/n m c", "1", "P 4n 2n -1n", "-y+1/2,x+1/2,z+1/2; x+1/2,-y+1/2,-z+1/2; -x+1/2,-y+1/2,-z+1/2"),
+    (137, "P 42/n m c", "2", "-P 4ac 2a", "-x,-y,-z; -y+1/2,x,z+1/2; x+1/2,-y,-z"),
+    (138, "P 42/n c m", "1", "P 4n 2ab -1n", "-y+1/2,x+1/2,z+1/2; x+1/2,-y+1/2,-z; -x+1/2,-y+1/2,-z+1/2"),
+    (138, "P 42/n c m", "2", "-P 4ac 2ac", "-x,-y,-z; -y+1/2,x,z+1/2; x+1/2,-y,-z+1/2"),
+    (139, "I 4/m m m", "", "-I 4 2", "-x,-y,-z; -y,x,z; x,-y,-z"),
+    (140, "I 4/m c m", "", "-I 4 2c", "-x,-y,-z; -y,x,z; x,-y,-z+1/2"),
+    (141, "I 41/a m d", "1", "I 4bw 2bw -1bw", "-y,x+1/2,z+1/4; x,-y+1/2,-z+1/4; -x,-y+1/2,-z+1/4"),
+    (141, "I 41/a m d", "2", "-I 4bd 2", "-x,-y,-z; -y+1/4,x+3/4,z+1/4; x,-y,-z"),
+    (142, "I 41/a c d", "1", "I 4bw 2aw -1bw", "-y,x+1/2,z+1/4; x+1/2,-y,-z+1/4; -x,-y+1/2,-z+1/4"),
+    (142, "I 41/a c d", "2", "-I 4bd 2c", "-x,-y,-z; -y+1/4,x+3/4,z+1/4; x,-y,-z+1/2"),
+    (143, "P 3", "", "P 3", "-y,x-y,z"),
+    (144, "P 31", "", "P 31", "-y,x-y,z+1/3"),
+    (145, "P 32", "", "P 32", "-y,x-y,z+2/3"),
+    (146, "R 3", "H", "R 3", "-y,x-y,z"),
+    (146, "R 3", "R", "P 3*", "z,x,y"),
+    (147, "P -3", "", "-P 3", "-x,-y,-z; -y,x-y,z"),
+    (148, "R -3", "H", "-R 3", "-x,-y,-z; -y,x-y,z"),
+    (148, "R -3", "R", "-P 3*", "-x,-y,-z; z,x,y"),
+    (149, "P 3 1 2", "", "P 3 2", "-y,x-y,z; -y,-x,-z"),
+    (150, "P 3 2 1", "", "P 3 2\"", "-y,x-y,z; y,x,-z"),
+    (151, "P 31 1 2", "", "P 31 2c (0 0 1)", "-y,x-y,z+1/3; -y,-x,-z+2/3"),
+    (152, "P 31 2 1", "", "P 31 2\"", "-y,x-y,z+1/3; y,x,-z"),
+    (153, "P 32 1 2", "", "P 32 2c (0 0 -1)", "-y,x-y,z+2/3; -y,-x,-z+1/3"),
+    (154, "P 32 2 1", "", "P 32 2\"", "-y,x-y,z+2/3; y,x,-z"),
+    (155, "R 3 2", "H", "R 3 2\"", "-y,x-y,z; y,x,-z"),
+    (155, "R 3 2", "R", "P 3* 2", "z,x,y; -y,-x,-z"),
+    (156, "P 3 m 1", "", "P 3 -2\"", "-y,x-y,z; -y,-x,z"),
+    (157, "P 3 1 m", "", "P 3 -2", "-y,x-y,z; y,x,z"),
+    (158, "P 3 c 1", "", "P 3 -2\"c", "-y,x-y,z; -y,-x,z+1/2"),
+    (159, "P 3 1 c", "", "P 3 -2c", "-y,x-y,z; y,x,z+1/2"),
+    (160, "R 3 m", "H", "R 3 -2\"", "-y,x-y,z; -y,-x,z"),
+    (160, "R 3 m", "R", "P 3* -2", "z,x,y; y,x,z"),
+    (161, "R 3 c", "H", "R 3 -2\"c", "-y,x-y,z; -y,-x,z+1/2"),
+    (161, "R 3 c", "R", "P 3* -2n", "z,x,y; y+1/2,x+1/2,z+1/2"),
+    (162, "P -3 1 m", "", "-P 3 2", "-x,-y,-z; -y,x-y,z; -y,-x,-z"),
+    (163, "P -3 1 c", "", "-P 3 2c", "-x,-y,-z; -y,x-y,z; -y,-x,-z+1/2"),
+    (164, "P -3 m 1", "", "-P 3 2\"", "-x,-y,-z; -y,x-y,z; y,x,-z"),
+    (165, "P -3 c 1", "", "-P 3 2\"c", "-x,-y,-z; -y,x-y,z; y,x,-z+1/2"),
+    (166, "R -3 m", "H", "-R 3 2\"", "-x,-y,-z; -y,x-y,z; y,x,-z"),
+    (166, "R -3 m", "R", "-P 3* 2", "-x,-y,-z; z,x,y; -y,-x,-z"),
+    (167, "R -3 c", "H", "-R 3 2\"c", "-x,-y,-z; -y,x-y,z; y,x,-z+1/2"),
+    (167, "R -3 c", "R", "-P 3* 2n", "-x,-y,-z; z,x,y; -y+1/2,-x+1/2,-z+1/2"),
+    (168, "P 6", "", "P 6", "x-y,x,z"),
+    (169, "P 61", "", "P 61", "x-y,x,z+1/6"),
+    (170, "P 65", "", "P 65", "x-y,x,z+5/6"),
+    (171, "P 62", "", "P 62", "x-y,x,z+1/3"),
+    (172, "P 64", "", "P 64", "x-y,x,z+2/3"),
+    (173, "P 63", "", "P 6c", "x-y,x,z+1/2"),
+    (174, "P -6", "", "P -6", "-x+y,-x,-z"),
+    (175, "P 6/m", "", "-P 6", "-x,-y,-z; x-y,x,z"),
+    (176, "P 63/m", "", "-P 6c", "-x,-y,-z; x-y,x,z+1/2"),
+    (177, "P 6 2 2", "", "P 6 2", "x-y,x,z; -y,-x,-z"),
+    (178, "P 61 2 2", "", "P 61 2 (0 0 -1)", "x-y,x,z+1/6; -y,-x,-z-1/6"),
+    (179, "P 65 2 2", "", "P 65 2 (0 0 1)", "x-y,x,z+5/6; -y,-x,-z+1/6"),
+    (180, "P 62 2 2", "", "P 62 2c (0 0 1)", "x-y,x,z+1/3; -y,-x,-z+2/3"),
+    (181, "P 64 2 2", "", "P 64 2c (0 0 -1)", "x-y,x,z+2/3; -y,-x,-z+1/3"),
+    (182, "P 63 2 2", "", "P 6c 2c", "x-y,x,z+1/2; -y,-x,-z+1/2"),
+    (183, "P 6 m m", "", "P 6 -2", "x-y,x,z; y,x,z"),
+    (184, "P 6 c c", "", "P 6 -2c", "x-y,x,z; y,x,z+1/2"),
+    (185, "P 63 c m", "", "P 6c -2", "x-y,x,z+1/2; y,x,z"),
+    (186, "P 63 m c", "", "P 6c -2c", "x-y,x,z+1/2; y,x,z+1/2"),
+    (187, "P -6 m 2", "", "P -6 2", "-x+y,-x,-z; -y,-x,-z"),
+    (188, "P -6 c 2", "", "P -6c 2", "-x+y,-x,-z+1/2; -y,-x,-z"),
+    (189, "P -6 2 m", "", "P -6 -2", "-x+y,-x,-z; y,x,z"),
+    (190, "P -6 2 c", "", "P -6c -2c", "-x+y,-x,-z+1/2; y,x,z+1/2"),
+    (191, "P 6/m m m", "", "-P 6 2", "-x,-y,-z; x-y,x,z; -y,-x,-z"),
+    (192, "P 6/m c c", "", "-P 6 2c", "-x,-y,-z; x-y,x,z; -y,-x,-z+1/2"),
+    (193, "P 63/m c m", "", "-P 6c 2", "-x,-y,-z; x-y,x,z+1/2; -y,-x,-z"),
+    (194, "P 63/m m c", "", "-P 6c 2c", "-x,-y,-z; x-y,x,z+1/2; -y,-x,-z+1/2"),
+    (195, "P 2 3", "", "P 2 2 3", "-x,-y,z; x,-y,-z; z,x,y"),
+    (196, "F 2 3", "", "F 2 2 3", "-x,-y,z; x,-y,-z; z,x,y"),
+    (197, "I 2 3", "", "I 2 2 3", "-x,-y,z; x,-y,-z; z,x,y"),
+    (198, "P 21 3", "", "P 2ac 2ab 3", "-x+1/2,-y,z+1/2; x+1/2,-y+1/2,-z; z,x,y"),
+    (199, "I 21 3", "", "I 2b 2c 3", "-x,-y+1/2,z; x,-y,-z+1/2; z,x,y"),
+    (200, "P m -3", "", "-P 2 2 3", "-x,-y,-z; -x,-y,z; x,-y,-z; z,x,y"),
+    (201, "P n -3", "1", "P 2 2 3 -1n", "-x,-y,z; x,-y,-z; z,x,y; -x+1/2,-y+1/2,-z+1/2"),
+    (201, "P n -3", "2", "-P 2ab 2bc 3", "-x,-y,-z; -x+1/2,-y+1/2,z; x,-y+1/2,-z+1/2; z,x,y"),
+    (202, "F m -3", "", "-F 2 2 3", "-x,-y,-z; -x,-y,z; x,-y,-z; z,x,y"),
+    (203, "F d -3", "1", "F 2 2 3 -1d", "-x,-y,z; x,-y,-z; z,x,y; -x+1/4,-y+1/4,-z+1/4"),
+    (203, "F d -3", "2", "-F 2uv 2vw 3", "-x,-y,-z; -x+1/4,-y+1/4,z; x,-y+1/4,-z+1/4; z,x,y"),
+    (204, "I m -3", "", "-I 2 2 3", "-x,-y,-z; -x,-y,z; x,-y,-z; z,x,y"),
+    (205, "P a -3", "", "-P 2ac 2ab 3", "-x,-y,-z; -x+1/2,-y,z+1/2; x+1/2,-y+1/2,-z; z,x,y"),
+    (206, "I a -3", "", "-I 2b 2c 3", "-x,-y,-z; -x,-y+1/2,z; x,-y,-z+1/2; z,x,y"),
+    (207, "P 4 3 2", "", "P 4 2 3", "-y,x,z; x,-y,-z; z,x,y"),
+    (208, "P 42 3 2", "", "P 4n 2 3", "-y+1/2,x+1/2,z+1/2; x,-y,-z; z,x,y"),
+    (209, "F 4 3 2", "", "F 4 2 3", "-y,x,z; x,-y,-z; z,x,y"),
+    (210, "F 41 3 2", "", "F 4d 2 3", "-y+1/4,x+1/4,z+1/4; x,-y,-z; z,x,y"),
+    (211, "I 4 3 2", "", "I 4 2 3", "-y,x,z; x,-y,-z; z,x,y"),
+    (212, "P 43 3 2", "", "P 4acd 2ab 3", "-y+3/4,x+1/4,z+3/4; x+1/2,-y+1/2,-z; z,x,y"),
+    (213, "P 41 3 2", "", "P 4bd 2ab 3", "-y+1/4,x+3/4,z+1/4; x+1/2,-y+1/2,-z; z,x,y"),
+    (214, "I 41 3 2", "", "I 4bd 2c 3", "-y+1/4,x+3/4,z+1/4; x,-y,-z+1/2; z,x,y"),
+    (215, "P -4 3 m", "", "P -4 2 3", "y,-x,-z; x,-y,-z; z,x,y"),
+    (216, "F -4 3 m", "", "F -4 2 3", "y,-x,-z; x,-y,-z; z,x,y"),
+    (217, "I -4 3 m", "", "I -4 2 3", "y,-x,-z; x,-y,-z; z,x,y"),
+    (218, "P -4 3 n", "", "P -4n 2 3", "y+1/2,-x+1/2,-z+1/2; x,-y,-z; z,x,y"),
+    (219, "F -4 3 c", "", "F -4a 2 3", "y+1/2,-x,-z; x,-y,-z; z,x,y"),
+    (220, "I -4 3 d", "", "I -4bd 2c 3", "y+1/4,-x+3/4,-z+1/4; x,-y,-z+1/2; z,x,y"),
+    (221, "P m -3 m", "", "-P 4 2 3", "-x,-y,-z; -y,x,z; x,-y,-z; z,x,y"),
+    (222, "P n -3 n", "1", "P 4 2 3 -1n", "-y,x,z; x,-y,-z; z,x,y; -x+1/2,-y+1/2,-z+1/2"),
+    (222, "P n -3 n", "2", "-P 4a 2bc 3", "-x,-y,-z; -y+1/2,x,z; x,-y+1/2,-z+1/2; z,x,y"),
+    (223, "P m -3 n", "", "-P 4n 2 3", "-x,-y,-z; -y+1/2,x+1/2,z+1/2; x,-y,-z; z,x,y"),
+    (224, "P n -3 m", "1", "P 4n 2 3 -1n", "-y+1/2,x+1/2,z+1/2; x,-y,-z; z,x,y; -x+1/2,-y+1/2,-z+1/2"),
+    (224, "P n -3 m", "2", "-P 4bc 2bc 3", "-x,-y,-z; -y,x+1/2,z+1/2; x,-y+1/2,-z+1/2; z,x,y"),
+    (225, "F m -3 m", "", "-F 4 2 3", "-x,-y,-z; -y,x,z; x,-y,-z; z,x,y"),
+    (226, "F m -3 c", "", "-F 4a 2 3", "-x,-y,-z; -y+1/2,x,z; x,-y,-z; z,x,y"),
+    (227, "F d -3 m", "1", "F 4d 2 3 -1d", "-y+1/4,x+1/4,z+1/4; x,-y,-z; z,x,y; -x+1/4,-y+1/4,-z+1/4"),
+    (227, "F d -3 m", "2", "-F 4vw 2vw 3", "-x,-y,-z; -y,x+1/4,z+1/4; x,-y+1/4,-z+1/4; z,x,y"),
+    (228, "F d -3 c", "1", "F 4d 2 3 -1cd", "-y+1/4,x+1/4,z+1/4; x,-y,-z; z,x,y; -x+1/4,-y+1/4,-z+3/4"),
+    (228, "F d -3 c", "2", "-F 4cvw 2vw 3", "-x,-y,-z; -y,x+1/4,z+3/4; x,-y+1/4,-z+1/4; z,x,y"),
+    (229, "I m -3 m", "", "-I 4 2 3", "-x,-y,-z; -y,x,z; x,-y,-z; z,x,y"),
+    (230, "I a -3 d", "", "-I 4bd 2c 3", "-x,-y,-z; -y+1/4,x+3/4,z+1/4; x,-y,-z+1/2; z,x,y"),
+];
+
+#[cfg(test)]
+mod test {
+    use super::{SpaceGroupSetting, SPACE_GROUPS};
+
+    #[test]
+    fn test_tables() {
+        for number in 1..=230 {
+            assert!(
+                SpaceGroupSetting::from_number(number).is_some(),
+                "{}",
+                number
+            );
+        }
+
+        for setting in SPACE_GROUPS {
+            let operations = setting.operations().0;
+
+            assert_eq!(operations.len(), setting.order(), "{:?}", setting);
+
+            for (index, operation) in operations.iter().enumerate() {
+                assert!(!operations[..index].contains(operation), "{:?}", setting);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup() {
+        let find =
+            |symbol| SpaceGroupSetting::from_hermann_mauguin(symbol).map(|s| (s.number, s.setting));
+
+        assert_eq!(find("P 1 21/c 1"), Some((14, "b1")));
+        assert_eq!(find("P21/c"), Some((14, "b1")));
+        assert_eq!(find("P 21/n"), Some((14, "b2")));
+        assert_eq!(find("p n m a"), Some((62, "")));
+        assert_eq!(find("Fd-3m"), Some((227, "1")));
+        assert_eq!(find("F d -3 m :2"), Some((227, "2")));
+        assert_eq!(find("F d -3 m Z"), Some((227, "2")));
+        assert_eq!(find("R -3 m"), Some((166, "H")));
+        assert_eq!(find("R -3 m :R"), Some((166, "R")));
+        assert_eq!(find("C m c a"), Some((64, "")));
+        assert_eq!(find("P 5"), None);
+
+        assert_eq!(SpaceGroupSetting::from_hall("-p 2YBC").unwrap().number, 14);
+        assert_eq!(
+            SpaceGroupSetting::from_number(227)
+                .unwrap()
+                .hermann_mauguin_with_setting(),
+            "F d -3 m :1"
+        );
+
+        let operations = SpaceGroupSetting::from_number(221).unwrap().operations();
+
+        assert_eq!(operations.0.len(), 48);
+        assert_eq!(operations.0[0].to_string(), "x,y,z");
+    }
+}
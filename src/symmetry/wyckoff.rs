@@ -0,0 +1,1304 @@
+// Wyckoff positions of the 230 space groups in the standard settings and, for the groups with two
+// origin choices, both origins, from the International Tables for Crystallography, Vol. A. The
+// letters follow a convention of the tables that cannot be derived from the operations. Other
+// settings, like P b n m or rhombohedral axes, are transformed to the standard setting first.
+
+use std::fmt::Display;
+
+use anyhow::{bail, Context};
+use fraction::Fraction;
+
+use crate::parse::GetAndParse;
+use crate::parser::DataBlock;
+
+use super::{
+    SpaceGroupSetting, SymmetryEquivPosAsXYZ, SymmetryEquivTransform,
+    DEFAULT_SITE_TOLERANCE_FRACTIONAL,
+};
+
+/// Representative coordinates of the Wyckoff positions of a setting, starting with the letter `a`
+#[rustfmt::skip]
+const WYCKOFF_POSITIONS: &[(u8, &str, &[&str])] = &[
+    (1, "", &["x,y,z"]),
+    (2, "", &[
+        "0,0,0", "0,0,1/2", "0,1/2,0", "1/2,0,0", "1/2,1/2,0", "1/2,0,1/2", "0,1/2,1/2",
+        "1/2,1/2,1/2", "x,y,z",
+    ]),
+    (3, "b", &["0,y,0", "0,y,1/2", "1/2,y,0", "1/2,y,1/2", "x,y,z"]),
+    (4, "b", &["x,y,z"]),
+    (5, "b1", &["0,y,0", "0,y,1/2", "x,y,z"]),
+    (6, "b", &["x,0,z", "x,1/2,z", "x,y,z"]),
+    (7, "b1", &["x,y,z"]),
+    (8, "b1", &["x,0,z", "x,y,z"]),
+    (9, "b1", &["x,y,z"]),
+    (10, "b", &[
+        "0,0,0", "0,1/2,0", "0,0,1/2", "1/2,0,0", "1/2,1/2,0", "0,1/2,1/2", "1/2,0,1/2",
+        "1/2,1/2,1/2", "0,y,0", "1/2,y,0", "0,y,1/2", "1/2,y,1/2", "x,0,z", "x,1/2,z", "x,y,z",
+    ]),
+    (11, "b", &["0,0,0", "1/2,0,0", "0,0,1/2", "1/2,0,1/2", "x,1/4,z", "x,y,z"]),
+    (12, "b1", &[
+        "0,0,0", "0,1/2,0", "0,0,1/2", "0,1/2,1/2", "1/4,1/4,0", "1/4,1/4,1/2", "0,y,0", "0,y,1/2",
+        "x,0,z", "x,y,z",
+    ]),
+    (13, "b1", &["0,0,0", "1/2,1/2,0", "0,1/2,0", "1/2,0,0", "0,y,1/4", "1/2,y,1/4", "x,y,z"]),
+    (14, "b1", &["0,0,0", "1/2,0,0", "0,0,1/2", "1/2,0,1/2", "x,y,z"]),
+    (15, "b1", &["0,0,0", "0,1/2,0", "1/4,1/4,0", "1/4,1/4,1/2", "0,y,1/4", "x,y,z"]),
+    (16, "", &[
+        "0,0,0", "1/2,0,0", "0,1/2,0", "0,0,1/2", "1/2,1/2,0", "1/2,0,1/2", "0,1/2,1/2",
+        "1/2,1/2,1/2", "x,0,0", "x,0,1/2", "x,1/2,0", "x,1/2,1/2", "0,y,0", "0,y,1/2", "1/2,y,0",
+        "1/2,y,1/2", "0,0,z", "1/2,0,z", "0,1/2,z", "1/2,1/2,z", "x,y,z",
+    ]),
+    (17, "", &["x,0,0", "x,1/2,0", "0,y,1/4", "1/2,y,1/4", "x,y,z"]),
+    (18, "", &["0,0,z", "0,1/2,z", "x,y,z"]),
+    (19, "", &["x,y,z"]),
+    (20, "", &["x,0,0", "0,y,1/4", "x,y,z"]),
+    (21, "", &[
+        "0,0,0", "0,1/2,0", "1/2,0,1/2", "0,0,1/2", "x,0,0", "x,0,1/2", "0,y,0", "0,y,1/2", "0,0,z",
+        "0,1/2,z", "1/4,1/4,z", "x,y,z",
+    ]),
+    (22, "", &[
+        "0,0,0", "0,0,1/2", "1/4,1/4,1/4", "1/4,1/4,3/4", "x,0,0", "0,y,0", "0,0,z", "1/4,1/4,z",
+        "1/4,y,1/4", "x,1/4,1/4", "x,y,z",
+    ]),
+    (23, "", &[
+        "0,0,0", "1/2,0,0", "0,0,1/2", "0,1/2,0", "x,0,0", "x,0,1/2", "0,y,0", "1/2,y,0", "0,0,z",
+        "0,1/2,z", "x,y,z",
+    ]),
+    (24, "", &["x,0,1/4", "1/4,y,0", "0,1/4,z", "x,y,z"]),
+    (25, "", &[
+        "0,0,z", "0,1/2,z", "1/2,0,z", "1/2,1/2,z", "x,0,z", "x,1/2,z", "0,y,z", "1/2,y,z", "x,y,z",
+    ]),
+    (26, "", &["0,y,z", "1/2,y,z", "x,y,z"]),
+    (27, "", &["0,0,z", "0,1/2,z", "1/2,0,z", "1/2,1/2,z", "x,y,z"]),
+    (28, "", &["0,0,z", "0,1/2,z", "1/4,y,z", "x,y,z"]),
+    (29, "", &["x,y,z"]),
+    (30, "", &["0,0,z", "1/2,0,z", "x,y,z"]),
+    (31, "", &["0,y,z", "x,y,z"]),
+    (32, "", &["0,0,z", "0,1/2,z", "x,y,z"]),
+    (33, "", &["x,y,z"]),
+    (34, "", &["0,0,z", "0,1/2,z", "x,y,z"]),
+    (35, "", &["0,0,z", "0,1/2,z", "1/4,1/4,z", "x,0,z", "0,y,z", "x,y,z"]),
+    (36, "", &["0,y,z", "x,y,z"]),
+    (37, "", &["0,0,z", "0,1/2,z", "1/4,1/4,z", "x,y,z"]),
+    (38, "", &["0,0,z", "1/2,0,z", "x,0,z", "0,y,z", "1/2,y,z", "x,y,z"]),
+    (39, "", &["0,0,z", "1/2,0,z", "x,1/4,z", "x,y,z"]),
+    (40, "", &["0,0,z", "1/4,y,z", "x,y,z"]),
+    (41, "", &["0,0,z", "x,y,z"]),
+    (42, "", &["0,0,z", "1/4,1/4,z", "0,y,z", "x,0,z", "x,y,z"]),
+    (43, "", &["0,0,z", "x,y,z"]),
+    (44, "", &["0,0,z", "0,1/2,z", "x,0,z", "0,y,z", "x,y,z"]),
+    (45, "", &["0,0,z", "1/2,0,z", "x,y,z"]),
+    (46, "", &["0,0,z", "1/4,y,z", "x,y,z"]),
+    (47, "", &[
+        "0,0,0", "1/2,0,0", "0,0,1/2", "1/2,0,1/2", "0,1/2,0", "1/2,1/2,0", "0,1/2,1/2",
+        "1/2,1/2,1/2", "x,0,0", "x,0,1/2", "x,1/2,0", "x,1/2,1/2", "0,y,0", "0,y,1/2", "1/2,y,0",
+        "1/2,y,1/2", "0,0,z", "0,1/2,z", "1/2,0,z", "1/2,1/2,z", "0,y,z", "1/2,y,z", "x,0,z",
+        "x,1/2,z", "x,y,0", "x,y,1/2", "x,y,z",
+    ]),
+    (48, "1", &[
+        "0,0,0", "1/2,0,0", "0,0,1/2", "0,1/2,0", "1/4,1/4,1/4", "3/4,3/4,3/4", "x,0,0", "x,0,1/2",
+        "0,y,0", "1/2,y,0", "0,0,z", "0,1/2,z", "x,y,z",
+    ]),
+    (48, "2", &[
+        "1/4,1/4,1/4", "3/4,1/4,1/4", "1/4,1/4,3/4", "1/4,3/4,1/4", "1/2,1/2,1/2", "0,0,0",
+        "x,1/4,1/4", "x,1/4,3/4", "1/4,y,1/4", "1/4,y,3/4", "1/4,1/4,z", "1/4,3/4,z", "x,y,z",
+    ]),
+    (49, "", &[
+        "0,0,0", "1/2,1/2,0", "0,1/2,0", "1/2,0,0", "0,0,1/4", "1/2,0,1/4", "0,1/2,1/4",
+        "1/2,1/2,1/4", "x,0,1/4", "x,1/2,1/4", "0,y,1/4", "1/2,y,1/4", "0,0,z", "1/2,1/2,z",
+        "0,1/2,z", "1/2,0,z", "x,y,0", "x,y,z",
+    ]),
+    (50, "1", &[
+        "0,0,0", "1/2,0,0", "1/2,0,1/2", "0,0,1/2", "1/4,1/4,0", "1/4,1/4,1/2", "0,0,z", "0,1/2,z",
+        "x,0,0", "x,0,1/2", "0,y,0", "0,y,1/2", "x,y,z",
+    ]),
+    (50, "2", &[
+        "1/4,1/4,0", "1/4,3/4,0", "1/4,3/4,1/2", "1/4,1/4,1/2", "0,0,0", "1/2,1/2,1/2", "1/4,1/4,z",
+        "1/4,3/4,z", "x,1/4,0", "x,1/4,1/2", "1/4,y,0", "1/4,y,1/2", "x,y,z",
+    ]),
+    (51, "", &[
+        "0,0,0", "0,1/2,0", "0,0,1/2", "0,1/2,1/2", "1/4,0,z", "1/4,1/2,z", "0,y,0", "0,y,1/2",
+        "x,0,z", "x,1/2,z", "1/4,y,z", "x,y,z",
+    ]),
+    (52, "", &["0,0,0", "0,0,1/2", "1/4,0,z", "x,1/4,1/4", "x,y,z"]),
+    (53, "", &[
+        "0,0,0", "1/2,0,0", "1/2,1/2,0", "0,1/2,0", "x,0,0", "x,1/2,0", "1/4,y,1/4", "0,y,z",
+        "x,y,z",
+    ]),
+    (54, "", &["0,0,0", "0,1/2,0", "0,y,1/4", "1/4,0,z", "1/4,1/2,z", "x,y,z"]),
+    (55, "", &[
+        "0,0,0", "0,0,1/2", "0,1/2,0", "0,1/2,1/2", "0,0,z", "0,1/2,z", "x,y,0", "x,y,1/2", "x,y,z",
+    ]),
+    (56, "", &["0,0,0", "0,0,1/2", "1/4,1/4,z", "1/4,3/4,z", "x,y,z"]),
+    (57, "", &["0,0,0", "1/2,0,0", "x,1/4,0", "x,y,1/4", "x,y,z"]),
+    (58, "", &["0,0,0", "0,0,1/2", "0,1/2,0", "0,1/2,1/2", "0,0,z", "0,1/2,z", "x,y,0", "x,y,z"]),
+    (59, "1", &["0,0,z", "0,1/2,z", "1/4,1/4,0", "1/4,1/4,1/2", "0,y,z", "x,0,z", "x,y,z"]),
+    (59, "2", &["1/4,1/4,z", "1/4,3/4,z", "0,0,0", "1/2,1/2,1/2", "1/4,y,z", "x,1/4,z", "x,y,z"]),
+    (60, "", &["0,0,0", "0,1/2,0", "0,y,1/4", "x,y,z"]),
+    (61, "", &["0,0,0", "0,0,1/2", "x,y,z"]),
+    (62, "", &["0,0,0", "0,0,1/2", "x,1/4,z", "x,y,z"]),
+    (63, "", &["0,0,0", "0,1/2,0", "0,y,1/4", "1/4,1/4,0", "x,0,0", "0,y,z", "x,y,1/4", "x,y,z"]),
+    (64, "", &["0,0,0", "1/2,0,0", "1/4,1/4,0", "x,0,0", "1/4,y,1/4", "0,y,z", "x,y,z"]),
+    (65, "", &[
+        "0,0,0", "1/2,0,0", "1/2,0,1/2", "0,0,1/2", "1/4,1/4,0", "1/4,1/4,1/2", "x,0,0", "x,0,1/2",
+        "0,y,0", "0,y,1/2", "0,0,z", "0,1/2,z", "1/4,1/4,z", "0,y,z", "x,0,z", "x,y,0", "x,y,1/2",
+        "x,y,z",
+    ]),
+    (66, "", &[
+        "0,0,1/4", "0,1/2,1/4", "0,0,0", "0,1/2,0", "1/4,1/4,0", "1/4,3/4,0", "x,0,1/4", "0,y,1/4",
+        "0,0,z", "0,1/2,z", "1/4,1/4,z", "x,y,0", "x,y,z",
+    ]),
+    (67, "", &[
+        "1/4,0,0", "1/4,0,1/2", "0,0,0", "0,0,1/2", "1/4,1/4,0", "1/4,1/4,1/2", "0,1/4,z", "x,0,0",
+        "x,0,1/2", "1/4,y,0", "1/4,y,1/2", "1/4,0,z", "0,y,z", "x,1/4,z", "x,y,z",
+    ]),
+    (68, "1", &[
+        "0,0,0", "1/2,1/2,1/2", "1/4,0,1/4", "0,1/4,1/4", "x,0,0", "0,y,0", "0,0,z", "1/4,1/4,z",
+        "x,y,z",
+    ]),
+    (68, "2", &[
+        "0,1/4,1/4", "0,1/4,3/4", "1/4,3/4,0", "0,0,0", "x,1/4,1/4", "0,y,1/4", "0,1/4,z",
+        "1/4,0,z", "x,y,z",
+    ]),
+    (69, "", &[
+        "0,0,0", "0,0,1/2", "0,1/4,1/4", "1/4,0,1/4", "1/4,1/4,0", "1/4,1/4,1/4", "x,0,0", "0,y,0",
+        "0,0,z", "1/4,1/4,z", "1/4,y,1/4", "x,1/4,1/4", "0,y,z", "x,0,z", "x,y,0", "x,y,z",
+    ]),
+    (70, "1", &[
+        "0,0,0", "0,0,1/2", "1/8,1/8,1/8", "5/8,5/8,5/8", "x,0,0", "0,y,0", "0,0,z", "x,y,z",
+    ]),
+    (70, "2", &[
+        "1/8,1/8,1/8", "3/8,3/8,3/8", "0,0,0", "1/2,1/2,1/2", "x,1/8,1/8", "1/8,y,1/8", "1/8,1/8,z",
+        "x,y,z",
+    ]),
+    (71, "", &[
+        "0,0,0", "0,1/2,1/2", "1/2,1/2,0", "1/2,0,1/2", "x,0,0", "x,1/2,0", "0,y,0", "0,y,1/2",
+        "0,0,z", "1/2,0,z", "1/4,1/4,1/4", "0,y,z", "x,0,z", "x,y,0", "x,y,z",
+    ]),
+    (72, "", &[
+        "0,0,1/4", "1/2,0,1/4", "0,0,0", "1/2,0,0", "1/4,1/4,1/4", "x,0,1/4", "0,y,1/4", "0,1/2,z",
+        "0,0,z", "x,y,0", "x,y,z",
+    ]),
+    (73, "", &["0,0,0", "1/4,1/4,1/4", "x,0,1/4", "1/4,y,0", "0,1/4,z", "x,y,z"]),
+    (74, "", &[
+        "0,0,0", "0,0,1/2", "1/4,1/4,1/4", "1/4,1/4,3/4", "0,1/4,z", "x,0,0", "1/4,y,1/4", "0,y,z",
+        "x,1/4,z", "x,y,z",
+    ]),
+    (75, "", &["0,0,z", "1/2,1/2,z", "0,1/2,z", "x,y,z"]),
+    (76, "", &["x,y,z"]),
+    (77, "", &["0,0,z", "1/2,1/2,z", "0,1/2,z", "x,y,z"]),
+    (78, "", &["x,y,z"]),
+    (79, "", &["0,0,z", "0,1/2,z", "x,y,z"]),
+    (80, "", &["0,0,z", "x,y,z"]),
+    (81, "", &[
+        "0,0,0", "0,0,1/2", "1/2,1/2,0", "1/2,1/2,1/2", "0,0,z", "1/2,1/2,z", "0,1/2,z", "x,y,z",
+    ]),
+    (82, "", &["0,0,0", "0,0,1/2", "0,1/2,1/4", "0,1/2,3/4", "0,0,z", "0,1/2,z", "x,y,z"]),
+    (83, "", &[
+        "0,0,0", "0,0,1/2", "1/2,1/2,0", "1/2,1/2,1/2", "0,1/2,0", "0,1/2,1/2", "0,0,z",
+        "1/2,1/2,z", "0,1/2,z", "x,y,0", "x,y,1/2", "x,y,z",
+    ]),
+    (84, "", &[
+        "0,0,0", "1/2,1/2,0", "0,1/2,0", "0,1/2,1/2", "0,0,1/4", "1/2,1/2,1/4", "0,0,z",
+        "1/2,1/2,z", "0,1/2,z", "x,y,0", "x,y,z",
+    ]),
+    (85, "1", &["0,0,0", "1/2,1/2,1/2", "0,1/2,z", "1/4,1/4,0", "1/4,1/4,1/2", "0,0,z", "x,y,z"]),
+    (85, "2", &["1/4,3/4,0", "1/4,3/4,1/2", "1/4,1/4,z", "0,0,0", "0,0,1/2", "1/4,3/4,z", "x,y,z"]),
+    (86, "1", &["0,0,0", "0,0,1/2", "1/4,1/4,1/4", "3/4,3/4,3/4", "0,1/2,z", "0,0,z", "x,y,z"]),
+    (86, "2", &[
+        "1/4,1/4,1/4", "1/4,1/4,3/4", "0,0,0", "0,0,1/2", "3/4,1/4,z", "1/4,1/4,z", "x,y,z",
+    ]),
+    (87, "", &[
+        "0,0,0", "0,0,1/2", "0,1/2,0", "0,1/2,1/4", "0,0,z", "1/4,1/4,1/4", "0,1/2,z", "x,y,0",
+        "x,y,z",
+    ]),
+    (88, "1", &["0,0,0", "0,0,1/2", "0,1/4,1/8", "1/4,0,3/8", "0,0,z", "x,y,z"]),
+    (88, "2", &["0,1/4,1/8", "0,1/4,5/8", "0,0,0", "0,0,1/2", "0,1/4,z", "x,y,z"]),
+    (89, "", &[
+        "0,0,0", "0,0,1/2", "1/2,1/2,0", "1/2,1/2,1/2", "1/2,0,0", "1/2,0,1/2", "0,0,z",
+        "1/2,1/2,z", "0,1/2,z", "x,x,0", "x,x,1/2", "x,0,0", "x,1/2,1/2", "x,0,1/2", "x,1/2,0",
+        "x,y,z",
+    ]),
+    (90, "", &["0,0,0", "0,0,1/2", "0,1/2,z", "0,0,z", "x,x,0", "x,x,1/2", "x,y,z"]),
+    (91, "", &["0,y,0", "1/2,y,0", "x,x,3/8", "x,y,z"]),
+    (92, "", &["x,x,0", "x,y,z"]),
+    (93, "", &[
+        "0,0,0", "1/2,1/2,0", "0,1/2,0", "0,1/2,1/2", "0,0,1/4", "1/2,1/2,1/4", "0,0,z",
+        "1/2,1/2,z", "0,1/2,z", "x,0,0", "x,1/2,1/2", "x,0,1/2", "x,1/2,0", "x,x,1/4", "x,x,3/4",
+        "x,y,z",
+    ]),
+    (94, "", &["0,0,0", "0,0,1/2", "0,0,z", "0,1/2,z", "x,x,0", "x,x,1/2", "x,y,z"]),
+    (95, "", &["0,y,0", "1/2,y,0", "x,x,5/8", "x,y,z"]),
+    (96, "", &["x,x,0", "x,y,z"]),
+    (97, "", &[
+        "0,0,0", "0,0,1/2", "0,1/2,0", "0,1/2,1/4", "0,0,z", "0,1/2,z", "x,x,0", "x,0,0", "x,0,1/2",
+        "x,x+1/2,1/4", "x,y,z",
+    ]),
+    (98, "", &["0,0,0", "0,0,1/2", "0,0,z", "x,x,0", "-x,x,0", "x,1/4,1/8", "x,y,z"]),
+    (99, "", &["0,0,z", "1/2,1/2,z", "1/2,0,z", "x,x,z", "x,0,z", "x,1/2,z", "x,y,z"]),
+    (100, "", &["0,0,z", "1/2,0,z", "x,x+1/2,z", "x,y,z"]),
+    (101, "", &["0,0,z", "1/2,1/2,z", "0,1/2,z", "x,x,z", "x,y,z"]),
+    (102, "", &["0,0,z", "0,1/2,z", "x,x,z", "x,y,z"]),
+    (103, "", &["0,0,z", "1/2,1/2,z", "0,1/2,z", "x,y,z"]),
+    (104, "", &["0,0,z", "0,1/2,z", "x,y,z"]),
+    (105, "", &["0,0,z", "1/2,1/2,z", "0,1/2,z", "x,0,z", "x,1/2,z", "x,y,z"]),
+    (106, "", &["0,0,z", "0,1/2,z", "x,y,z"]),
+    (107, "", &["0,0,z", "0,1/2,z", "x,x,z", "x,0,z", "x,y,z"]),
+    (108, "", &["0,0,z", "1/2,0,z", "x,x+1/2,z", "x,y,z"]),
+    (109, "", &["0,0,z", "0,y,z", "x,y,z"]),
+    (110, "", &["0,0,z", "x,y,z"]),
+    (111, "", &[
+        "0,0,0", "1/2,1/2,1/2", "0,0,1/2", "1/2,1/2,0", "1/2,0,0", "1/2,0,1/2", "0,0,z",
+        "1/2,1/2,z", "x,0,0", "x,1/2,1/2", "x,0,1/2", "x,1/2,0", "0,1/2,z", "x,x,z", "x,y,z",
+    ]),
+    (112, "", &[
+        "0,0,1/4", "1/2,0,1/4", "1/2,1/2,1/4", "0,1/2,1/4", "0,0,0", "1/2,1/2,0", "x,0,1/4",
+        "1/2,y,1/4", "x,1/2,1/4", "0,y,1/4", "0,0,z", "1/2,1/2,z", "0,1/2,z", "x,y,z",
+    ]),
+    (113, "", &["0,0,0", "0,0,1/2", "0,1/2,z", "0,0,z", "x,x+1/2,z", "x,y,z"]),
+    (114, "", &["0,0,0", "0,0,1/2", "0,0,z", "0,1/2,z", "x,y,z"]),
+    (115, "", &[
+        "0,0,0", "1/2,1/2,0", "1/2,1/2,1/2", "0,0,1/2", "0,0,z", "1/2,1/2,z", "0,1/2,z", "x,x,0",
+        "x,x,1/2", "x,0,z", "x,1/2,z", "x,y,z",
+    ]),
+    (116, "", &[
+        "0,0,1/4", "1/2,1/2,1/4", "0,0,0", "1/2,1/2,0", "x,x,1/4", "x,x,3/4", "0,0,z", "1/2,1/2,z",
+        "0,1/2,z", "x,y,z",
+    ]),
+    (117, "", &[
+        "0,0,0", "0,0,1/2", "0,1/2,0", "0,1/2,1/2", "0,0,z", "0,1/2,z", "x,x+1/2,0", "x,x+1/2,1/2",
+        "x,y,z",
+    ]),
+    (118, "", &[
+        "0,0,0", "0,0,1/2", "0,1/2,1/4", "0,1/2,3/4", "0,0,z", "0,1/2,z", "x,-x+1/2,1/4",
+        "x,x+1/2,1/4", "x,y,z",
+    ]),
+    (119, "", &[
+        "0,0,0", "0,0,1/2", "0,1/2,1/4", "0,1/2,3/4", "0,0,z", "0,1/2,z", "x,x,0", "x,x+1/2,1/4",
+        "x,0,z", "x,y,z",
+    ]),
+    (120, "", &[
+        "0,0,1/4", "0,0,0", "0,1/2,1/4", "0,1/2,0", "x,x,1/4", "0,0,z", "0,1/2,z", "x,x+1/2,0",
+        "x,y,z",
+    ]),
+    (121, "", &[
+        "0,0,0", "0,0,1/2", "0,1/2,0", "0,1/2,1/4", "0,0,z", "x,0,0", "x,0,1/2", "0,1/2,z", "x,x,z",
+        "x,y,z",
+    ]),
+    (122, "", &["0,0,0", "0,0,1/2", "0,0,z", "x,1/4,1/8", "x,y,z"]),
+    (123, "", &[
+        "0,0,0", "0,0,1/2", "1/2,1/2,0", "1/2,1/2,1/2", "0,1/2,1/2", "0,1/2,0", "0,0,z",
+        "1/2,1/2,z", "0,1/2,z", "x,x,0", "x,x,1/2", "x,0,0", "x,0,1/2", "x,1/2,0", "x,1/2,1/2",
+        "x,y,0", "x,y,1/2", "x,x,z", "x,0,z", "x,1/2,z", "x,y,z",
+    ]),
+    (124, "", &[
+        "0,0,1/4", "0,0,0", "1/2,1/2,1/4", "1/2,1/2,0", "0,1/2,0", "0,1/2,1/4", "0,0,z",
+        "1/2,1/2,z", "0,1/2,z", "x,x,1/4", "x,0,1/4", "x,1/2,1/4", "x,y,0", "x,y,z",
+    ]),
+    (125, "1", &[
+        "0,0,0", "1/2,1/2,1/2", "0,1/2,0", "0,1/2,1/2", "1/4,1/4,0", "1/4,1/4,1/2", "0,0,z",
+        "0,1/2,z", "x,0,0", "x,1/2,1/2", "x,x,0", "x,x,1/2", "x,x+1/2,z", "x,y,z",
+    ]),
+    (125, "2", &[
+        "1/4,1/4,0", "1/4,1/4,1/2", "3/4,1/4,0", "3/4,1/4,1/2", "0,0,0", "0,0,1/2", "1/4,1/4,z",
+        "3/4,1/4,z", "x,1/4,0", "x,1/4,1/2", "x,x,0", "x,x,1/2", "x,-x,z", "x,y,z",
+    ]),
+    (126, "1", &[
+        "0,0,0", "0,0,1/2", "0,1/2,0", "0,1/2,1/4", "0,0,z", "1/4,1/4,1/4", "0,1/2,z", "x,0,0",
+        "x,0,1/2", "x,x,0", "x,y,z",
+    ]),
+    (126, "2", &[
+        "1/4,1/4,1/4", "1/4,1/4,3/4", "1/4,3/4,3/4", "1/4,3/4,0", "1/4,1/4,z", "0,0,0", "1/4,3/4,z",
+        "x,1/4,1/4", "x,3/4,1/4", "x,x,1/4", "x,y,z",
+    ]),
+    (127, "", &[
+        "0,0,0", "0,0,1/2", "0,1/2,1/2", "0,1/2,0", "0,0,z", "0,1/2,z", "x,x+1/2,0", "x,x+1/2,1/2",
+        "x,y,0", "x,y,1/2", "x,x+1/2,z", "x,y,z",
+    ]),
+    (128, "", &[
+        "0,0,0", "0,0,1/2", "0,1/2,0", "0,1/2,1/4", "0,0,z", "0,1/2,z", "x,x+1/2,1/4", "x,y,0",
+        "x,y,z",
+    ]),
+    (129, "1", &[
+        "0,0,0", "1/2,1/2,1/2", "0,1/2,z", "1/4,1/4,0", "1/4,1/4,1/2", "0,0,z", "x,x,0", "x,x,1/2",
+        "x,0,z", "x,x+1/2,z", "x,y,z",
+    ]),
+    (129, "2", &[
+        "3/4,1/4,0", "3/4,1/4,1/2", "1/4,1/4,z", "0,0,0", "0,0,1/2", "3/4,1/4,z", "x,-x,0",
+        "x,-x,1/2", "1/4,y,z", "x,x,z", "x,y,z",
+    ]),
+    (130, "1", &["0,0,1/4", "0,0,0", "0,1/2,z", "1/4,1/4,0", "0,0,z", "x,x,1/4", "x,y,z"]),
+    (130, "2", &[
+        "3/4,1/4,1/4", "3/4,1/4,0", "1/4,1/4,z", "0,0,0", "3/4,1/4,z", "x,-x,1/4", "x,y,z",
+    ]),
+    (131, "", &[
+        "0,0,0", "1/2,1/2,0", "0,1/2,0", "0,1/2,1/2", "0,0,1/4", "1/2,1/2,1/4", "0,0,z",
+        "1/2,1/2,z", "0,1/2,z", "x,0,0", "x,1/2,1/2", "x,0,1/2", "x,1/2,0", "x,x,1/4", "0,y,z",
+        "1/2,y,z", "x,y,0", "x,y,z",
+    ]),
+    (132, "", &[
+        "0,0,0", "0,0,1/4", "1/2,1/2,0", "1/2,1/2,1/4", "0,1/2,0", "0,1/2,1/4", "0,0,z",
+        "1/2,1/2,z", "x,x,0", "x,x,1/2", "0,1/2,z", "x,0,1/4", "x,1/2,1/4", "x,y,0", "x,x,z",
+        "x,y,z",
+    ]),
+    (133, "1", &[
+        "0,1/2,0", "0,0,0", "0,0,1/4", "0,1/2,1/4", "1/4,1/4,1/4", "0,1/2,z", "0,0,z", "x,0,1/4",
+        "x,0,3/4", "x,x+1/2,0", "x,y,z",
+    ]),
+    (133, "2", &[
+        "1/4,1/4,1/4", "1/4,3/4,1/4", "1/4,3/4,0", "1/4,1/4,0", "0,0,0", "1/4,1/4,z", "1/4,3/4,z",
+        "x,1/4,0", "x,1/4,1/2", "x,x,1/4", "x,y,z",
+    ]),
+    (134, "1", &[
+        "0,0,0", "0,0,1/2", "0,1/2,0", "0,1/2,1/4", "1/4,1/4,1/4", "3/4,3/4,3/4", "0,0,z",
+        "0,1/2,z", "x,0,0", "x,0,1/2", "x,x+1/2,3/4", "x,x+1/2,1/4", "x,x,z", "x,y,z",
+    ]),
+    (134, "2", &[
+        "3/4,1/4,3/4", "3/4,1/4,1/4", "1/4,1/4,1/4", "1/4,1/4,0", "0,0,1/2", "0,0,0", "3/4,1/4,z",
+        "1/4,1/4,z", "x,1/4,3/4", "x,1/4,1/4", "x,x,1/2", "x,x,0", "x,-x,z", "x,y,z",
+    ]),
+    (135, "", &[
+        "0,0,0", "0,0,1/4", "0,1/2,0", "0,1/2,1/4", "0,0,z", "0,1/2,z", "x,x+1/2,1/4", "x,y,0",
+        "x,y,z",
+    ]),
+    (136, "", &[
+        "0,0,0", "0,0,1/2", "0,1/2,0", "0,1/2,1/4", "0,0,z", "x,x,0", "x,-x,0", "0,1/2,z", "x,y,0",
+        "x,x,z", "x,y,z",
+    ]),
+    (137, "1", &["0,0,0", "0,0,1/2", "0,0,z", "0,1/2,z", "1/4,1/4,1/4", "x,x,0", "x,0,z", "x,y,z"]),
+    (137, "2", &[
+        "3/4,1/4,3/4", "3/4,1/4,1/4", "3/4,1/4,z", "1/4,1/4,z", "0,0,0", "x,-x,1/4", "1/4,y,z",
+        "x,y,z",
+    ]),
+    (138, "1", &[
+        "0,0,1/4", "0,0,0", "1/4,1/4,1/4", "3/4,3/4,3/4", "0,1/2,z", "0,0,z", "x,x,1/4", "x,x,3/4",
+        "x,x+1/2,z", "x,y,z",
+    ]),
+    (138, "2", &[
+        "3/4,1/4,0", "3/4,1/4,3/4", "0,0,1/2", "0,0,0", "1/4,1/4,z", "3/4,1/4,z", "x,-x,1/2",
+        "x,-x,0", "x,x,z", "x,y,z",
+    ]),
+    (139, "", &[
+        "0,0,0", "0,0,1/2", "0,1/2,0", "0,1/2,1/4", "0,0,z", "1/4,1/4,1/4", "0,1/2,z", "x,x,0",
+        "x,0,0", "x,1/2,0", "x,x+1/2,1/4", "x,y,0", "x,x,z", "0,y,z", "x,y,z",
+    ]),
+    (140, "", &[
+        "0,0,1/4", "0,1/2,1/4", "0,0,0", "0,1/2,0", "1/4,1/4,1/4", "0,0,z", "0,1/2,z", "x,x+1/2,0",
+        "x,0,1/4", "x,x,1/4", "x,y,0", "x,x+1/2,z", "x,y,z",
+    ]),
+    (141, "1", &[
+        "0,0,0", "0,0,1/2", "0,1/4,1/8", "1/4,0,3/8", "0,0,z", "x,1/4,1/8", "x,x,0", "x,0,z",
+        "x,y,z",
+    ]),
+    (141, "2", &[
+        "0,3/4,1/8", "0,1/4,3/8", "0,0,0", "0,0,1/2", "0,1/4,z", "x,0,0", "x,x+1/4,7/8", "0,y,z",
+        "x,y,z",
+    ]),
+    (142, "1", &["0,0,0", "0,0,1/4", "0,1/4,1/8", "0,0,z", "x,1/4,3/8", "x,x,1/4", "x,y,z"]),
+    (142, "2", &["0,1/4,3/8", "0,1/4,1/8", "0,0,0", "0,1/4,z", "x,0,1/4", "x,x+1/4,1/8", "x,y,z"]),
+    (143, "", &["0,0,z", "1/3,2/3,z", "2/3,1/3,z", "x,y,z"]),
+    (144, "", &["x,y,z"]),
+    (145, "", &["x,y,z"]),
+    (146, "H", &["0,0,z", "x,y,z"]),
+    (147, "", &["0,0,0", "0,0,1/2", "0,0,z", "1/3,2/3,z", "1/2,0,0", "1/2,0,1/2", "x,y,z"]),
+    (148, "H", &["0,0,0", "0,0,1/2", "0,0,z", "1/2,0,1/2", "1/2,0,0", "x,y,z"]),
+    (149, "", &[
+        "0,0,0", "0,0,1/2", "1/3,2/3,0", "1/3,2/3,1/2", "2/3,1/3,0", "2/3,1/3,1/2", "0,0,z",
+        "1/3,2/3,z", "2/3,1/3,z", "x,-x,0", "x,-x,1/2", "x,y,z",
+    ]),
+    (150, "", &["0,0,0", "0,0,1/2", "0,0,z", "1/3,2/3,z", "x,0,0", "x,0,1/2", "x,y,z"]),
+    (151, "", &["x,-x,1/3", "x,-x,5/6", "x,y,z"]),
+    (152, "", &["x,0,1/3", "x,0,5/6", "x,y,z"]),
+    (153, "", &["x,-x,2/3", "x,-x,1/6", "x,y,z"]),
+    (154, "", &["x,0,2/3", "x,0,1/6", "x,y,z"]),
+    (155, "H", &["0,0,0", "0,0,1/2", "0,0,z", "x,0,0", "x,0,1/2", "x,y,z"]),
+    (156, "", &["0,0,z", "1/3,2/3,z", "2/3,1/3,z", "x,-x,z", "x,y,z"]),
+    (157, "", &["0,0,z", "1/3,2/3,z", "x,0,z", "x,y,z"]),
+    (158, "", &["0,0,z", "1/3,2/3,z", "2/3,1/3,z", "x,y,z"]),
+    (159, "", &["0,0,z", "1/3,2/3,z", "x,y,z"]),
+    (160, "H", &["0,0,z", "x,-x,z", "x,y,z"]),
+    (161, "H", &["0,0,z", "x,y,z"]),
+    (162, "", &[
+        "0,0,0", "0,0,1/2", "1/3,2/3,0", "1/3,2/3,1/2", "0,0,z", "1/2,0,0", "1/2,0,1/2",
+        "1/3,2/3,z", "x,-x,0", "x,-x,1/2", "x,0,z", "x,y,z",
+    ]),
+    (163, "", &[
+        "0,0,1/4", "0,0,0", "1/3,2/3,1/4", "2/3,1/3,1/4", "0,0,z", "1/3,2/3,z", "1/2,0,0",
+        "x,-x,1/4", "x,y,z",
+    ]),
+    (164, "", &[
+        "0,0,0", "0,0,1/2", "0,0,z", "1/3,2/3,z", "1/2,0,0", "1/2,0,1/2", "x,0,0", "x,0,1/2",
+        "x,-x,z", "x,y,z",
+    ]),
+    (165, "", &["0,0,1/4", "0,0,0", "0,0,z", "1/3,2/3,z", "1/2,0,0", "x,0,1/4", "x,y,z"]),
+    (166, "H", &[
+        "0,0,0", "0,0,1/2", "0,0,z", "1/2,0,1/2", "1/2,0,0", "x,0,0", "x,0,1/2", "x,-x,z", "x,y,z",
+    ]),
+    (167, "H", &["0,0,1/4", "0,0,0", "0,0,z", "1/2,0,0", "x,0,1/4", "x,y,z"]),
+    (168, "", &["0,0,z", "1/3,2/3,z", "1/2,0,z", "x,y,z"]),
+    (169, "", &["x,y,z"]),
+    (170, "", &["x,y,z"]),
+    (171, "", &["0,0,z", "1/2,1/2,z", "x,y,z"]),
+    (172, "", &["0,0,z", "1/2,1/2,z", "x,y,z"]),
+    (173, "", &["0,0,z", "1/3,2/3,z", "x,y,z"]),
+    (174, "", &[
+        "0,0,0", "0,0,1/2", "1/3,2/3,0", "1/3,2/3,1/2", "2/3,1/3,0", "2/3,1/3,1/2", "0,0,z",
+        "1/3,2/3,z", "2/3,1/3,z", "x,y,0", "x,y,1/2", "x,y,z",
+    ]),
+    (175, "", &[
+        "0,0,0", "0,0,1/2", "1/3,2/3,0", "1/3,2/3,1/2", "0,0,z", "1/2,0,0", "1/2,0,1/2",
+        "1/3,2/3,z", "1/2,0,z", "x,y,0", "x,y,1/2", "x,y,z",
+    ]),
+    (176, "", &[
+        "0,0,1/4", "0,0,0", "1/3,2/3,1/4", "2/3,1/3,1/4", "0,0,z", "1/3,2/3,z", "1/2,0,0",
+        "x,y,1/4", "x,y,z",
+    ]),
+    (177, "", &[
+        "0,0,0", "0,0,1/2", "1/3,2/3,0", "1/3,2/3,1/2", "0,0,z", "1/2,0,0", "1/2,0,1/2",
+        "1/3,2/3,z", "1/2,0,z", "x,0,0", "x,0,1/2", "x,2x,0", "x,2x,1/2", "x,y,z",
+    ]),
+    (178, "", &["x,0,0", "x,2x,1/4", "x,y,z"]),
+    (179, "", &["x,0,0", "x,2x,3/4", "x,y,z"]),
+    (180, "", &[
+        "0,0,0", "0,0,1/2", "1/2,0,0", "1/2,0,1/2", "0,0,z", "1/2,0,z", "x,0,0", "x,0,1/2",
+        "x,2x,0", "x,2x,1/2", "x,y,z",
+    ]),
+    (181, "", &[
+        "0,0,0", "0,0,1/2", "1/2,0,0", "1/2,0,1/2", "0,0,z", "1/2,0,z", "x,0,0", "x,0,1/2",
+        "x,2x,0", "x,2x,1/2", "x,y,z",
+    ]),
+    (182, "", &[
+        "0,0,0", "0,0,1/4", "1/3,2/3,1/4", "1/3,2/3,3/4", "0,0,z", "1/3,2/3,z", "x,0,0", "x,2x,1/4",
+        "x,y,z",
+    ]),
+    (183, "", &["0,0,z", "1/3,2/3,z", "1/2,0,z", "x,0,z", "x,-x,z", "x,y,z"]),
+    (184, "", &["0,0,z", "1/3,2/3,z", "1/2,0,z", "x,y,z"]),
+    (185, "", &["0,0,z", "1/3,2/3,z", "x,0,z", "x,y,z"]),
+    (186, "", &["0,0,z", "1/3,2/3,z", "x,-x,z", "x,y,z"]),
+    (187, "", &[
+        "0,0,0", "0,0,1/2", "1/3,2/3,0", "1/3,2/3,1/2", "2/3,1/3,0", "2/3,1/3,1/2", "0,0,z",
+        "1/3,2/3,z", "2/3,1/3,z", "x,-x,0", "x,-x,1/2", "x,y,0", "x,y,1/2", "x,-x,z", "x,y,z",
+    ]),
+    (188, "", &[
+        "0,0,0", "0,0,1/4", "1/3,2/3,0", "1/3,2/3,1/4", "2/3,1/3,0", "2/3,1/3,1/4", "0,0,z",
+        "1/3,2/3,z", "2/3,1/3,z", "x,-x,0", "x,y,1/4", "x,y,z",
+    ]),
+    (189, "", &[
+        "0,0,0", "0,0,1/2", "1/3,2/3,0", "1/3,2/3,1/2", "0,0,z", "x,0,0", "x,0,1/2", "1/3,2/3,z",
+        "x,0,z", "x,y,0", "x,y,1/2", "x,y,z",
+    ]),
+    (190, "", &[
+        "0,0,0", "0,0,1/4", "1/3,2/3,1/4", "2/3,1/3,1/4", "0,0,z", "1/3,2/3,z", "x,0,0", "x,y,1/4",
+        "x,y,z",
+    ]),
+    (191, "", &[
+        "0,0,0", "0,0,1/2", "1/3,2/3,0", "1/3,2/3,1/2", "0,0,z", "1/2,0,0", "1/2,0,1/2",
+        "1/3,2/3,z", "1/2,0,z", "x,0,0", "x,0,1/2", "x,2x,0", "x,2x,1/2", "x,0,z", "x,2x,z",
+        "x,y,0", "x,y,1/2", "x,y,z",
+    ]),
+    (192, "", &[
+        "0,0,1/4", "0,0,0", "1/3,2/3,1/4", "1/3,2/3,0", "0,0,z", "1/2,0,1/4", "1/2,0,0",
+        "1/3,2/3,z", "1/2,0,z", "x,0,1/4", "x,2x,1/4", "x,y,0", "x,y,z",
+    ]),
+    (193, "", &[
+        "0,0,1/4", "0,0,0", "1/3,2/3,1/4", "1/3,2/3,0", "0,0,z", "1/2,0,0", "x,0,1/4", "1/3,2/3,z",
+        "x,2x,0", "x,y,1/4", "x,0,z", "x,y,z",
+    ]),
+    (194, "", &[
+        "0,0,0", "0,0,1/4", "1/3,2/3,1/4", "1/3,2/3,3/4", "0,0,z", "1/3,2/3,z", "1/2,0,0",
+        "x,2x,1/4", "x,0,0", "x,y,1/4", "x,2x,z", "x,y,z",
+    ]),
+    (195, "", &[
+        "0,0,0", "1/2,1/2,1/2", "0,1/2,1/2", "1/2,0,0", "x,x,x", "x,0,0", "x,0,1/2", "x,1/2,0",
+        "x,1/2,1/2", "x,y,z",
+    ]),
+    (196, "", &[
+        "0,0,0", "1/2,1/2,1/2", "1/4,1/4,1/4", "3/4,3/4,3/4", "x,x,x", "x,0,0", "x,1/4,1/4",
+        "x,y,z",
+    ]),
+    (197, "", &["0,0,0", "0,1/2,1/2", "x,x,x", "x,0,0", "x,1/2,0", "x,y,z"]),
+    (198, "", &["x,x,x", "x,y,z"]),
+    (199, "", &["x,x,x", "x,0,1/4", "x,y,z"]),
+    (200, "", &[
+        "0,0,0", "1/2,1/2,1/2", "0,1/2,1/2", "1/2,0,0", "x,0,0", "x,0,1/2", "x,1/2,0", "x,1/2,1/2",
+        "x,x,x", "0,y,z", "1/2,y,z", "x,y,z",
+    ]),
+    (201, "1", &[
+        "0,0,0", "1/4,1/4,1/4", "3/4,3/4,3/4", "0,0,1/2", "x,x,x", "x,0,0", "x,0,1/2", "x,y,z",
+    ]),
+    (201, "2", &[
+        "1/4,1/4,1/4", "0,0,0", "1/2,1/2,1/2", "1/4,3/4,3/4", "x,x,x", "x,1/4,1/4", "x,3/4,1/4",
+        "x,y,z",
+    ]),
+    (202, "", &[
+        "0,0,0", "1/2,1/2,1/2", "1/4,1/4,1/4", "0,1/4,1/4", "x,0,0", "x,x,x", "x,1/4,1/4", "0,y,z",
+        "x,y,z",
+    ]),
+    (203, "1", &["0,0,0", "1/2,1/2,1/2", "1/8,1/8,1/8", "5/8,5/8,5/8", "x,x,x", "x,0,0", "x,y,z"]),
+    (203, "2", &[
+        "1/8,1/8,1/8", "5/8,5/8,5/8", "0,0,0", "1/2,1/2,1/2", "x,x,x", "x,1/8,1/8", "x,y,z",
+    ]),
+    (204, "", &[
+        "0,0,0", "0,1/2,1/2", "1/4,1/4,1/4", "x,0,0", "x,0,1/2", "x,x,x", "0,y,z", "x,y,z",
+    ]),
+    (205, "", &["0,0,0", "1/2,1/2,1/2", "x,x,x", "x,y,z"]),
+    (206, "", &["0,0,0", "1/4,1/4,1/4", "x,x,x", "x,0,1/4", "x,y,z"]),
+    (207, "", &[
+        "0,0,0", "1/2,1/2,1/2", "0,1/2,1/2", "1/2,0,0", "x,0,0", "x,1/2,1/2", "x,x,x", "x,1/2,0",
+        "0,y,y", "1/2,y,y", "x,y,z",
+    ]),
+    (208, "", &[
+        "0,0,0", "1/4,1/4,1/4", "3/4,3/4,3/4", "0,1/2,1/2", "1/4,0,1/2", "1/4,1/2,0", "x,x,x",
+        "x,0,0", "x,0,1/2", "x,1/2,0", "1/4,y,-y+1/2", "1/4,y,y+1/2", "x,y,z",
+    ]),
+    (209, "", &[
+        "0,0,0", "1/2,1/2,1/2", "1/4,1/4,1/4", "0,1/4,1/4", "x,0,0", "x,x,x", "x,1/4,1/4", "0,y,y",
+        "1/2,y,y", "x,y,z",
+    ]),
+    (210, "", &[
+        "0,0,0", "1/2,1/2,1/2", "1/8,1/8,1/8", "5/8,5/8,5/8", "x,x,x", "x,0,0", "1/8,y,-y+1/4",
+        "x,y,z",
+    ]),
+    (211, "", &[
+        "0,0,0", "0,1/2,1/2", "1/4,1/4,1/4", "1/4,1/2,0", "x,0,0", "x,x,x", "x,1/2,0", "0,y,y",
+        "1/4,y,-y+1/2", "x,y,z",
+    ]),
+    (212, "", &["1/8,1/8,1/8", "5/8,5/8,5/8", "x,x,x", "1/8,y,-y+1/4", "x,y,z"]),
+    (213, "", &["3/8,3/8,3/8", "7/8,7/8,7/8", "x,x,x", "1/8,y,y+1/4", "x,y,z"]),
+    (214, "", &[
+        "1/8,1/8,1/8", "7/8,7/8,7/8", "1/8,0,1/4", "5/8,0,1/4", "x,x,x", "x,0,1/4", "1/8,y,y+1/4",
+        "1/8,y,-y+1/4", "x,y,z",
+    ]),
+    (215, "", &[
+        "0,0,0", "1/2,1/2,1/2", "0,1/2,1/2", "1/2,0,0", "x,x,x", "x,0,0", "x,1/2,1/2", "x,1/2,0",
+        "x,x,z", "x,y,z",
+    ]),
+    (216, "", &[
+        "0,0,0", "1/2,1/2,1/2", "1/4,1/4,1/4", "3/4,3/4,3/4", "x,x,x", "x,0,0", "x,1/4,1/4",
+        "x,x,z", "x,y,z",
+    ]),
+    (217, "", &["0,0,0", "0,1/2,1/2", "x,x,x", "1/4,1/2,0", "x,0,0", "x,1/2,0", "x,x,z", "x,y,z"]),
+    (218, "", &[
+        "0,0,0", "0,1/2,1/2", "1/4,1/2,0", "1/4,0,1/2", "x,x,x", "x,0,0", "x,1/2,0", "x,0,1/2",
+        "x,y,z",
+    ]),
+    (219, "", &[
+        "0,0,0", "1/4,1/4,1/4", "1/4,0,0", "0,1/4,1/4", "x,x,x", "x,0,0", "x,1/4,1/4", "x,y,z",
+    ]),
+    (220, "", &["3/8,0,1/4", "7/8,0,1/4", "x,x,x", "x,0,1/4", "x,y,z"]),
+    (221, "", &[
+        "0,0,0", "1/2,1/2,1/2", "0,1/2,1/2", "1/2,0,0", "x,0,0", "x,1/2,1/2", "x,x,x", "x,1/2,0",
+        "0,y,y", "1/2,y,y", "0,y,z", "1/2,y,z", "x,x,z", "x,y,z",
+    ]),
+    (222, "1", &[
+        "0,0,0", "0,0,1/2", "1/4,1/4,1/4", "0,1/2,1/4", "x,0,0", "x,x,x", "x,0,1/2", "0,y,y",
+        "x,y,z",
+    ]),
+    (222, "2", &[
+        "1/4,1/4,1/4", "3/4,1/4,1/4", "0,0,0", "0,3/4,1/4", "x,1/4,1/4", "x,x,x", "x,3/4,1/4",
+        "1/4,y,y", "x,y,z",
+    ]),
+    (223, "", &[
+        "0,0,0", "0,1/2,1/2", "1/4,0,1/2", "1/4,1/2,0", "1/4,1/4,1/4", "x,0,0", "x,0,1/2",
+        "x,1/2,0", "x,x,x", "1/4,y,y+1/2", "0,y,z", "x,y,z",
+    ]),
+    (224, "1", &[
+        "0,0,0", "1/4,1/4,1/4", "3/4,3/4,3/4", "0,0,1/2", "x,x,x", "x,0,0", "0,1/2,1/4", "x,0,1/2",
+        "3/4,y,y+1/2", "1/4,y,y+1/2", "x,x,z", "x,y,z",
+    ]),
+    (224, "2", &[
+        "1/4,1/4,1/4", "0,0,0", "1/2,1/2,1/2", "1/4,3/4,3/4", "x,x,x", "x,1/4,1/4", "0,1/4,3/4",
+        "x,1/4,3/4", "1/2,y,y+1/2", "0,y,y+1/2", "x,x,z", "x,y,z",
+    ]),
+    (225, "", &[
+        "0,0,0", "1/2,1/2,1/2", "1/4,1/4,1/4", "0,1/4,1/4", "x,0,0", "x,x,x", "x,1/4,1/4", "0,y,y",
+        "1/2,y,y", "0,y,z", "x,x,z", "x,y,z",
+    ]),
+    (226, "", &[
+        "1/4,1/4,1/4", "0,0,0", "1/4,0,0", "0,1/4,1/4", "x,0,0", "x,1/4,1/4", "x,x,x", "1/4,y,y",
+        "0,y,z", "x,y,z",
+    ]),
+    (227, "1", &[
+        "0,0,0", "1/2,1/2,1/2", "1/8,1/8,1/8", "5/8,5/8,5/8", "x,x,x", "x,0,0", "x,x,z",
+        "3/8,y,y+1/4", "x,y,z",
+    ]),
+    (227, "2", &[
+        "1/8,1/8,1/8", "3/8,3/8,3/8", "0,0,0", "1/2,1/2,1/2", "x,x,x", "x,1/8,1/8", "x,x,z",
+        "0,y,-y", "x,y,z",
+    ]),
+    (228, "1", &[
+        "0,0,0", "3/8,3/8,3/8", "1/8,1/8,1/8", "0,0,1/4", "x,x,x", "x,0,0", "3/8,y,y+1/4", "x,y,z",
+    ]),
+    (228, "2", &[
+        "1/8,1/8,1/8", "0,0,0", "0,0,1/4", "1/8,1/8,3/8", "x,x,x", "x,1/8,1/8", "0,y,y+1/4",
+        "x,y,z",
+    ]),
+    (229, "", &[
+        "0,0,0", "0,1/2,1/2", "1/4,1/4,1/4", "1/4,0,1/2", "x,0,0", "x,x,x", "x,0,1/2", "0,y,y",
+        "1/4,y,-y+1/2", "0,y,z", "x,x,z", "x,y,z",
+    ]),
+    (230, "", &[
+        "0,0,0", "1/8,1/8,1/8", "1/8,0,1/4", "3/8,0,1/4", "x,x,x", "x,0,1/4", "1/8,y,-y+1/4",
+        "x,y,z",
+    ]),
+];
+
+/// Parameters of a generic point on the representative of a Wyckoff position
+const GENERIC_PARAMETERS: [f64; 3] = [0.0731, 0.1634, 0.2917];
+
+/// Wyckoff position like `8a`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct WyckoffPosition {
+    pub multiplicity: usize,
+    pub letter: char,
+}
+
+impl Display for WyckoffPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.multiplicity, self.letter)
+    }
+}
+
+/// Letter of the Wyckoff position at `index`, `a` to `z` and then `α` for the 27th position of
+/// P m m m
+fn letter(index: usize) -> char {
+    match index {
+        0..=25 => (b'a' + index as u8) as char,
+        _ => 'α',
+    }
+}
+
+impl SpaceGroupSetting {
+    /// Representative coordinates like `x,x,z` of the Wyckoff positions `a`, `b`, ..., for the
+    /// standard setting and both origin choices, `None` for other settings
+    pub fn wyckoff_representatives(&self) -> Option<&'static [&'static str]> {
+        WYCKOFF_POSITIONS
+            .iter()
+            .find(|(number, setting, _)| *number == self.number && *setting == self.setting)
+            .map(|(_, _, representatives)| *representatives)
+    }
+
+    /// Wyckoff position of `point`, where positions closer than `tolerance_fractional` in every
+    /// coordinate coincide
+    pub fn wyckoff_position(
+        &self,
+        point: [f64; 3],
+        tolerance_fractional: f64,
+    ) -> anyhow::Result<WyckoffPosition> {
+        let operations = self.operations();
+
+        WyckoffTable::new(&operations)?.position(point, tolerance_fractional)
+    }
+}
+
+impl SymmetryEquivPosAsXYZ {
+    /// Wyckoff positions of `points` in the space group of the operations, see
+    /// [`SpaceGroupSetting::wyckoff_position`]. Settings without tabulated positions are
+    /// transformed to the standard setting with [`SymmetryEquivPosAsXYZ::identify_space_group`].
+    pub fn wyckoff_positions(
+        &self,
+        points: &[[f64; 3]],
+        tolerance_fractional: f64,
+    ) -> anyhow::Result<Vec<WyckoffPosition>> {
+        let table = WyckoffTable::new(self)?;
+
+        points
+            .iter()
+            .map(|point| table.position(*point, tolerance_fractional))
+            .collect()
+    }
+}
+
+/// Tabulated Wyckoff positions for a list of operations, with the transformation of their
+/// coordinates to the setting of the table
+struct WyckoffTable<'a> {
+    operations: &'a SymmetryEquivPosAsXYZ,
+    setting: &'static SpaceGroupSetting,
+    transformation: SymmetryEquivTransform,
+    /// Operations of `setting`
+    frame: SymmetryEquivPosAsXYZ,
+    /// Representatives and the multiplicities of their generic points in `frame`
+    representatives: Vec<(SymmetryEquivTransform, usize)>,
+}
+
+impl<'a> WyckoffTable<'a> {
+    fn new(operations: &'a SymmetryEquivPosAsXYZ) -> anyhow::Result<Self> {
+        let identification = operations
+            .identify_space_group()
+            .context("The operations form no tabulated space group")?;
+
+        let standard = identification.standard;
+
+        let (setting, transformation) =
+            match (identification.setting, identification.transformation) {
+                (Some(setting), _) if setting.wyckoff_representatives().is_some() => {
+                    (setting, SymmetryEquivTransform::identity())
+                }
+                (_, Some(transformation)) => (standard, transformation),
+                (Some(setting), None) if setting.setting == "R" => {
+                    (standard, rhombohedral_to_hexagonal())
+                }
+                (_, None) => bail!(
+                    "Failed to transform the operations to the standard setting of `{}`",
+                    standard.hermann_mauguin
+                ),
+            };
+
+        let Some(representatives) = setting.wyckoff_representatives() else {
+            bail!(
+                "No Wyckoff positions are tabulated for the space group `{}` ({})",
+                setting.hermann_mauguin_with_setting(),
+                setting.number
+            );
+        };
+
+        let frame = setting.operations();
+
+        let representatives = representatives
+            .iter()
+            .map(|representative| {
+                let representative: SymmetryEquivTransform = representative.parse()?;
+
+                let generic = representative.transform_point(GENERIC_PARAMETERS)?;
+
+                let multiplicity = frame
+                    .site_symmetry(generic, DEFAULT_SITE_TOLERANCE_FRACTIONAL)?
+                    .multiplicity;
+
+                Ok((representative, multiplicity))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            operations,
+            setting,
+            transformation,
+            frame,
+            representatives,
+        })
+    }
+
+    /// Wyckoff position of `point` in the coordinates of the operations. The multiplicity is that
+    /// of the operations, so rhombohedral axes give a third of the hexagonal multiplicity.
+    fn position(
+        &self,
+        point: [f64; 3],
+        tolerance_fractional: f64,
+    ) -> anyhow::Result<WyckoffPosition> {
+        let multiplicity = self
+            .operations
+            .site_symmetry(point, tolerance_fractional)?
+            .multiplicity;
+
+        let transformed = self.transformation.transform_point(point)?;
+
+        let frame_multiplicity = self
+            .frame
+            .site_symmetry(transformed, tolerance_fractional)?
+            .multiplicity;
+
+        let images = self
+            .frame
+            .0
+            .iter()
+            .map(|operation| {
+                let image = operation.transform_point(transformed)?;
+                Ok(image.map(|value| value - value.floor()))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        for (index, (representative, generic_multiplicity)) in
+            self.representatives.iter().enumerate()
+        {
+            // a point with the site symmetry of the representative lies on one of its images
+            if *generic_multiplicity == frame_multiplicity
+                && images
+                    .iter()
+                    .any(|image| lies_on(representative, image, tolerance_fractional))
+            {
+                return Ok(WyckoffPosition {
+                    multiplicity,
+                    letter: letter(index),
+                });
+            }
+        }
+
+        bail!(
+            "The point {:?} lies on none of the Wyckoff positions of the space group `{}`",
+            point,
+            self.setting.hermann_mauguin
+        )
+    }
+}
+
+/// Coordinate transformation from rhombohedral to the hexagonal axes of the obverse setting,
+/// `x_H = (2x - y - z) / 3`, `y_H = (x + y - 2z) / 3` and `z_H = (x + y + z) / 3`
+fn rhombohedral_to_hexagonal() -> SymmetryEquivTransform {
+    let third = |numerator: i32| Fraction::from(numerator) / Fraction::from(3);
+
+    SymmetryEquivTransform {
+        rotation: [
+            [third(2), third(-1), third(-1)],
+            [third(1), third(1), third(-2)],
+            [third(1), third(1), third(1)],
+        ],
+        translation: [Fraction::from(0); 3],
+    }
+}
+
+/// Whether `point` equals the representative `W t + w` for some parameters `t`, up to a lattice
+/// translation
+fn lies_on(
+    representative: &SymmetryEquivTransform,
+    point: &[f64; 3],
+    tolerance_fractional: f64,
+) -> bool {
+    let to_f64 = |value: &fraction::Fraction| super::to_float::<f64>(value).unwrap_or_default();
+
+    let matrix = representative
+        .rotation
+        .map(|row| row.map(|value| to_f64(&value)));
+    let offset = representative.translation.map(|value| to_f64(&value));
+
+    // the parameters x, y and z the representative depends on
+    let columns = (0..3)
+        .filter(|column| matrix.iter().any(|row| row[*column] != 0.0))
+        .collect::<Vec<_>>();
+
+    for shift in 0..27 {
+        let lattice = [shift % 3, shift / 3 % 3, shift / 9].map(|n| n as f64 - 1.0);
+
+        let target = [0, 1, 2].map(|index| point[index] + lattice[index] - offset[index]);
+
+        let parameters = least_squares(&matrix, &columns, &target);
+
+        if (0..3).all(|row| {
+            let value: f64 = columns
+                .iter()
+                .zip(&parameters)
+                .map(|(column, parameter)| matrix[row][*column] * parameter)
+                .sum();
+
+            (value - target[row]).abs() < tolerance_fractional
+        }) {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Parameters for `columns` of `matrix` that best reproduce `target`, from the normal equations
+fn least_squares(matrix: &[[f64; 3]; 3], columns: &[usize], target: &[f64; 3]) -> Vec<f64> {
+    let size = columns.len();
+
+    let mut system = vec![vec![0.0; size + 1]; size];
+
+    for (i, column_i) in columns.iter().enumerate() {
+        for (j, column_j) in columns.iter().enumerate() {
+            system[i][j] = (0..3)
+                .map(|row| matrix[row][*column_i] * matrix[row][*column_j])
+                .sum();
+        }
+
+        system[i][size] = (0..3).map(|row| matrix[row][*column_i] * target[row]).sum();
+    }
+
+    for pivot in 0..size {
+        let best = (pivot..size)
+            .max_by(|a, b| system[*a][pivot].abs().total_cmp(&system[*b][pivot].abs()))
+            .unwrap_or(pivot);
+
+        system.swap(pivot, best);
+
+        if system[pivot][pivot].abs() < 1e-12 {
+            continue;
+        }
+
+        let pivot_row = system[pivot].clone();
+
+        for (index, row) in system.iter_mut().enumerate() {
+            if index != pivot {
+                let factor = row[pivot] / pivot_row[pivot];
+
+                for (value, pivot_value) in row.iter_mut().zip(&pivot_row).skip(pivot) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+    }
+
+    (0..size)
+        .map(|row| {
+            if system[row][row].abs() < 1e-12 {
+                0.0
+            } else {
+                system[row][size] / system[row][row]
+            }
+        })
+        .collect()
+}
+
+impl DataBlock {
+    /// Wyckoff positions of the `_atom_site_*` atoms for the operations of the data block, see
+    /// [`DataBlock::symmetry_equiv_pos_as_xyz`]. Differences to the `_atom_site_Wyckoff_symbol` of
+    /// the file are logged as warnings.
+    pub fn wyckoff_positions(&self) -> anyhow::Result<Vec<WyckoffPosition>> {
+        let operations = self.symmetry_equiv_pos_as_xyz()?;
+
+        let table = WyckoffTable::new(&operations)
+            .context("Failed to find the space group of the data block")?;
+
+        let label = self.get_and_parse_all::<String>("_atom_site_label")?;
+
+        let [x, y, z] = [
+            "_atom_site_fract_x",
+            "_atom_site_fract_y",
+            "_atom_site_fract_z",
+        ]
+        .map(|key| self.get_and_parse_all::<crate::Measured>(key));
+
+        let (x, y, z) = (x?, y?, z?);
+
+        let declared = match self.contains_key("_atom_site_Wyckoff_symbol") {
+            true => Some(self.get_and_parse_all_optional::<String>("_atom_site_Wyckoff_symbol")?),
+            false => None,
+        };
+
+        let mut positions = Vec::new();
+
+        for (index, label) in label.iter().enumerate() {
+            let position = table
+                .position(
+                    [x[index].value, y[index].value, z[index].value],
+                    DEFAULT_SITE_TOLERANCE_FRACTIONAL,
+                )
+                .with_context(|| format!("Failed to find the Wyckoff position of `{}`", label))?;
+
+            let declared = declared
+                .as_ref()
+                .and_then(|declared| declared.get(index).cloned().flatten());
+
+            if let Some(declared) = declared {
+                // the symbol is either the letter or the multiplicity and the letter
+                let matches =
+                    declared == position.letter.to_string() || declared == position.to_string();
+
+                if !matches {
+                    log::warn!(
+                        "Wyckoff symbol `{}` of `{}` does not match the computed `{}`",
+                        declared,
+                        label,
+                        position
+                    );
+                }
+            }
+
+            positions.push(position);
+        }
+
+        Ok(positions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::symmetry::{
+        SpaceGroupSetting, SymmetryEquivPosAsXYZ, SymmetryEquivTransform, SPACE_GROUPS,
+    };
+    use crate::Parser;
+
+    use super::{
+        letter, rhombohedral_to_hexagonal, WyckoffPosition, WyckoffTable,
+        DEFAULT_SITE_TOLERANCE_FRACTIONAL, GENERIC_PARAMETERS, WYCKOFF_POSITIONS,
+    };
+
+    #[test]
+    fn test_tables() {
+        for (number, setting, representatives) in WYCKOFF_POSITIONS {
+            let space_group = SPACE_GROUPS
+                .iter()
+                .find(|candidate| candidate.number == *number && candidate.setting == *setting)
+                .unwrap();
+
+            assert!(representatives.len() <= 27, "{}", number);
+
+            let operations = space_group.operations();
+            let table = WyckoffTable::new(&operations).unwrap();
+
+            assert_eq!(table.setting, space_group);
+
+            let mut previous = 0;
+
+            for (index, representative) in representatives.iter().enumerate() {
+                let point = representative
+                    .parse::<SymmetryEquivTransform>()
+                    .unwrap()
+                    .transform_point(GENERIC_PARAMETERS)
+                    .unwrap();
+
+                let multiplicity = operations
+                    .site_symmetry(point, DEFAULT_SITE_TOLERANCE_FRACTIONAL)
+                    .unwrap()
+                    .multiplicity;
+
+                // the letters start at the highest site symmetry
+                assert!(multiplicity >= previous, "{} {}", number, representative);
+                previous = multiplicity;
+
+                // every representative is found as its own Wyckoff position
+                assert_eq!(
+                    table
+                        .position(point, DEFAULT_SITE_TOLERANCE_FRACTIONAL)
+                        .unwrap(),
+                    WyckoffPosition {
+                        multiplicity,
+                        letter: letter(index),
+                    },
+                    "{} {}",
+                    number,
+                    representative
+                );
+            }
+
+            // the last position is the general position
+            assert_eq!(previous, operations.0.len(), "{}", number);
+        }
+
+        // every space group is tabulated
+        for number in 1..=230 {
+            let standard = SpaceGroupSetting::from_number(number).unwrap();
+
+            assert!(standard.wyckoff_representatives().is_some(), "{}", number);
+        }
+    }
+
+    #[test]
+    fn test_settings() {
+        // the general position of every setting is the last letter of the standard setting
+        for space_group in SPACE_GROUPS {
+            let operations = space_group.operations();
+            let table = WyckoffTable::new(&operations).unwrap();
+
+            let position = table
+                .position(GENERIC_PARAMETERS, DEFAULT_SITE_TOLERANCE_FRACTIONAL)
+                .unwrap();
+
+            assert_eq!(
+                position,
+                WyckoffPosition {
+                    multiplicity: operations.0.len(),
+                    letter: letter(table.representatives.len() - 1),
+                },
+                "{}",
+                space_group.hermann_mauguin_with_setting()
+            );
+        }
+    }
+
+    #[test]
+    fn test_rhombohedral_to_hexagonal() {
+        let rhombohedral = SPACE_GROUPS
+            .iter()
+            .find(|space_group| space_group.number == 166 && space_group.setting == "R")
+            .unwrap();
+
+        let transformation = rhombohedral_to_hexagonal();
+        let inverse: SymmetryEquivTransform = "x+z,-x+y+z,-y+z".parse().unwrap();
+
+        assert_eq!(
+            transformation.compose(&inverse),
+            SymmetryEquivTransform::identity()
+        );
+
+        let hexagonal = SpaceGroupSetting::from_number(166)
+            .unwrap()
+            .operations()
+            .0
+            .iter()
+            .map(|operation| operation.normalized())
+            .collect::<HashSet<_>>();
+
+        for operation in rhombohedral.operations().0 {
+            let transformed = transformation
+                .compose(&operation)
+                .compose(&inverse)
+                .normalized();
+
+            assert!(hexagonal.contains(&transformed), "{}", operation);
+        }
+
+        let operations = rhombohedral.operations();
+
+        let positions = operations
+            .wyckoff_positions(
+                &[
+                    [0.0, 0.0, 0.0],
+                    [0.5, 0.5, 0.5],
+                    [0.2, 0.2, 0.2],
+                    [0.5, 0.0, 0.0],
+                    [0.0, 0.5, 0.5],
+                    [0.1, 0.2, 0.3],
+                ],
+                DEFAULT_SITE_TOLERANCE_FRACTIONAL,
+            )
+            .unwrap()
+            .iter()
+            .map(|position| position.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(positions, ["1a", "1b", "2c", "3d", "3e", "12i"]);
+    }
+
+    #[test]
+    fn test_wyckoff_position() {
+        let space_group = SpaceGroupSetting::from_number(194).unwrap();
+
+        let position = space_group
+            .wyckoff_position(
+                [2.0 / 3.0, 1.0 / 3.0, 0.75],
+                DEFAULT_SITE_TOLERANCE_FRACTIONAL,
+            )
+            .unwrap();
+
+        assert_eq!(position.to_string(), "2c");
+
+        let space_group = SpaceGroupSetting::from_number(62).unwrap();
+
+        let position = space_group
+            .wyckoff_position([0.3, 0.75, 0.1], DEFAULT_SITE_TOLERANCE_FRACTIONAL)
+            .unwrap();
+
+        assert_eq!(position.to_string(), "4c");
+
+        let space_group = SpaceGroupSetting::from_number(47).unwrap();
+
+        let position = space_group
+            .wyckoff_position([0.1, 0.2, 0.3], DEFAULT_SITE_TOLERANCE_FRACTIONAL)
+            .unwrap();
+
+        assert_eq!(position.to_string(), "8α");
+
+        // the screw axes of P 1 21 1 and P 1 1 21 without their product form no space group
+        let operations = SymmetryEquivPosAsXYZ(
+            ["x,y,z", "-x,y+1/2,-z", "-x,-y,z+1/2"]
+                .iter()
+                .map(|operation| operation.parse().unwrap())
+                .collect(),
+        );
+
+        assert!(operations
+            .wyckoff_positions(&[[0.1, 0.2, 0.3]], DEFAULT_SITE_TOLERANCE_FRACTIONAL)
+            .is_err());
+    }
+
+    #[test]
+    fn test_wyckoff_positions() {
+        for (path, expected) in [
+            (r"assets\BaTiO3.cif", vec!["1a", "1b", "3c"]),
+            (r"assets\diamond.cif", vec!["8a"]),
+        ] {
+            let bytes = std::fs::read(path).unwrap();
+            let data = Parser::new(&bytes).parse();
+
+            let positions = data
+                .first_key_value()
+                .unwrap()
+                .1
+                .wyckoff_positions()
+                .unwrap();
+
+            let positions = positions
+                .iter()
+                .map(|position| position.to_string())
+                .collect::<Vec<_>>();
+
+            assert_eq!(positions, expected);
+        }
+
+        // P b n m, the setting c a b of P n m a, with the operations of the file
+        let data = Parser::new(
+            b"data_GdFeO3
+_space_group_name_H-M_alt 'P b n m'
+loop_
+_space_group_symop_operation_xyz
+x,y,z
+x+1/2,-y+1/2,-z
+-x,-y,z+1/2
+-x+1/2,y+1/2,-z+1/2
+-x,-y,-z
+-x+1/2,y+1/2,z
+x,y,-z+1/2
+x+1/2,-y+1/2,z+1/2
+loop_
+_atom_site_label
+_atom_site_fract_x
+_atom_site_fract_y
+_atom_site_fract_z
+Gd 0.985 0.060 0.25
+Fe 0.5 0 0
+O1 0.1 0.47 0.25
+O2 0.69 0.30 0.05
+",
+        )
+        .parse();
+
+        let positions = data
+            .first_key_value()
+            .unwrap()
+            .1
+            .wyckoff_positions()
+            .unwrap()
+            .iter()
+            .map(|position| position.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(positions, ["4c", "4b", "4c", "8d"]);
+    }
+
+    #[test]
+    fn test_phase_with_wyckoff_positions() {
+        let bytes = std::fs::read(r"assets\BaTiO3.cif").unwrap();
+        let data = Parser::new(&bytes).parse();
+
+        let (phase, positions) = data
+            .first_key_value()
+            .unwrap()
+            .1
+            .try_into_phase_with_wyckoff_positions()
+            .unwrap();
+
+        assert_eq!(phase.atoms.0.len(), positions.len());
+        assert_eq!(positions[2].to_string(), "3c");
+
+        let data = Parser::new(
+            b"data_test
+_space_group_IT_number 96
+loop_
+_atom_site_label
+_atom_site_type_symbol
+_atom_site_fract_x
+_atom_site_fract_y
+_atom_site_fract_z
+C1 C 0.1 0.2 0.3
+C2 C 0.3 0.3 0
+",
+        )
+        .parse();
+
+        let positions = data
+            .first_key_value()
+            .unwrap()
+            .1
+            .wyckoff_positions()
+            .unwrap()
+            .iter()
+            .map(|position| position.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(positions, ["8b", "4a"]);
+    }
+}
@@ -0,0 +1,209 @@
+// https://www.iucr.org/resources/cif/spec/version1.1/semicolon
+// https://doi.org/10.1107/S1600576715021871 (CIF 2.0, section 5.2)
+// Content of semicolon delimited text fields with the line-folding and text-prefix protocols.
+
+/// Prefix used when writing text whose lines would otherwise end the text field
+const PREFIX: &str = ">";
+
+/// Value of a text field from its content between the delimiters. Line breaks are kept as `\n`,
+/// an empty first line is dropped, and the line-folding and text-prefix protocols are undone.
+pub(crate) fn read_text_field(content: &str) -> String {
+    let content = content.replace("\r\n", "\n").replace('\r', "\n");
+
+    let (first_line, rest) = content.split_once('\n').unwrap_or((&content, ""));
+
+    if let Some((prefix, folded)) = protocol(first_line) {
+        let lines = rest
+            .split('\n')
+            .map(|line| line.strip_prefix(prefix))
+            .collect::<Option<Vec<_>>>();
+
+        // text that does not follow the protocol is taken as it is
+        if let Some(lines) = lines {
+            return match folded {
+                true => unfold(&lines),
+                false => lines.join("\n"),
+            };
+        }
+    }
+
+    match first_line.is_empty() {
+        true => rest.to_string(),
+        false => content,
+    }
+}
+
+/// Prefix and whether the lines are folded, from a first line like `\`, `prefix\` or `prefix\\`
+fn protocol(first_line: &str) -> Option<(&str, bool)> {
+    let marker = first_line
+        .trim_end_matches([' ', '\t'])
+        .strip_suffix('\\')?;
+
+    let (prefix, folded) = match marker.strip_suffix('\\') {
+        Some(prefix) => (prefix, true),
+        None => (marker, marker.is_empty()),
+    };
+
+    match prefix.contains(|c: char| c == '\\' || c.is_whitespace()) {
+        true => None,
+        false => Some((prefix, folded)),
+    }
+}
+
+/// Joins every line ending in `\` (optionally followed by whitespace) with the next one
+fn unfold(lines: &[&str]) -> String {
+    let mut text = String::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        match line.trim_end_matches([' ', '\t']).strip_suffix('\\') {
+            Some(line) => text.push_str(line),
+            None => {
+                text.push_str(line);
+
+                if index + 1 < lines.len() {
+                    text.push('\n');
+                }
+            }
+        }
+    }
+
+    text
+}
+
+/// Text field including its delimiters. Lines starting with `;` are protected with the
+/// text-prefix protocol and lines longer than `line_length` are folded.
+pub(crate) fn write_text_field(text: &str, line_length: usize) -> String {
+    let lines = text.split('\n').collect::<Vec<_>>();
+
+    let needs_prefix =
+        lines.iter().any(|line| line.starts_with(';')) || protocol(lines[0]).is_some();
+
+    let prefix = match needs_prefix {
+        true => PREFIX,
+        false => "",
+    };
+
+    let needs_folding = lines
+        .iter()
+        .any(|line| prefix.len() + line.chars().count() > line_length);
+
+    let mut field = String::from(";");
+
+    match (needs_prefix, needs_folding) {
+        (false, false) => {}
+        (false, true) => field.push_str("\\\n"),
+        (true, false) => field.push_str(&format!("{}\\\n", prefix)),
+        (true, true) => field.push_str(&format!("{}\\\\\n", prefix)),
+    }
+
+    if !needs_prefix && !needs_folding {
+        // content that starts on the first line would be ambiguous with an empty first line
+        field.push('\n');
+    }
+
+    for line in &lines {
+        match needs_folding {
+            true => fold_line(&mut field, prefix, line, line_length),
+            false => {
+                field.push_str(prefix);
+                field.push_str(line);
+                field.push('\n');
+            }
+        }
+    }
+
+    field.push(';');
+
+    field
+}
+
+/// Writes `line` as lines of at most `line_length` characters, each but the last ending in `\`
+fn fold_line(field: &mut String, prefix: &str, line: &str, line_length: usize) {
+    let width = line_length.saturating_sub(prefix.len() + 1).max(1);
+
+    let chars = line.chars().collect::<Vec<_>>();
+    let mut chunks = chars.chunks(width).peekable();
+
+    if chunks.peek().is_none() {
+        field.push_str(prefix);
+        field.push('\n');
+        return;
+    }
+
+    while let Some(chunk) = chunks.next() {
+        field.push_str(prefix);
+        field.extend(chunk);
+
+        if chunks.peek().is_some() {
+            field.push('\\');
+        }
+
+        field.push('\n');
+    }
+
+    // a line that ends like a fold is folded with an empty line, which keeps its line break
+    if protocol_fold_end(line) {
+        let last = field.len() - 1;
+
+        field.insert(last, '\\');
+        field.push_str(prefix);
+        field.push('\n');
+    }
+}
+
+fn protocol_fold_end(line: &str) -> bool {
+    line.trim_end_matches([' ', '\t']).ends_with('\\')
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read_text_field, write_text_field};
+
+    #[test]
+    fn test_read() {
+        assert_eq!(
+            read_text_field("\nline one\nline two"),
+            "line one\nline two"
+        );
+        assert_eq!(read_text_field("first\r\n  indented"), "first\n  indented");
+        assert_eq!(
+            read_text_field("\\\nlong \\\nline\nnext"),
+            "long line\nnext"
+        );
+        assert_eq!(read_text_field(">\\\n>;not the end\n>x"), ";not the end\nx");
+        assert_eq!(read_text_field("> \\\n>a"), "> \\\n>a");
+        assert_eq!(read_text_field(">\\\\\n>a\\\n>b"), "ab");
+        assert_eq!(read_text_field(">\\\n>a\nb"), ">\\\n>a\nb");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for text in [
+            "simple",
+            "two\nlines",
+            "\nleading line break",
+            "trailing line break\n",
+            ";semicolon\nat line start",
+            "\\",
+            "ends in a backslash \\\nnext",
+            &"x".repeat(200),
+            &format!(";{}\\\n{}", "y".repeat(100), "z".repeat(10)),
+        ] {
+            let field = write_text_field(text, 80);
+
+            assert!(
+                field.starts_with(';') && field.ends_with("\n;"),
+                "{}",
+                field
+            );
+
+            for line in field.lines() {
+                assert!(line.chars().count() <= 80, "{}", field);
+            }
+
+            let content = &field[1..field.len() - 2];
+
+            assert_eq!(read_text_field(content), text, "{}", field);
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use std::{borrow::Cow, str::FromStr};
+
+use anyhow::Context;
+use indexmap::IndexMap;
+
+use crate::measured::Measured;
+
+/// Value of a data item or of a single loop cell
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum CifValue {
+    /// Unquoted number as written in the file, e.g. `4.0094(2)`
+    Numeric(String),
+    Text(String),
+    /// Unquoted `?`
+    Unknown,
+    /// Unquoted `.`
+    Inapplicable,
+    /// CIF 2.0 list `[ ... ]`
+    List(Vec<CifValue>),
+    /// CIF 2.0 table `{ "key": value ... }`
+    Table(IndexMap<String, CifValue>),
+}
+
+impl CifValue {
+    /// Classifies an unquoted value. Quoted values are always [`CifValue::Text`].
+    pub fn from_unquoted(value: &str) -> Self {
+        match value {
+            "?" => CifValue::Unknown,
+            "." => CifValue::Inapplicable,
+            _ if is_number(value) => CifValue::Numeric(value.to_string()),
+            _ => CifValue::Text(value.to_string()),
+        }
+    }
+
+    /// Text of numbers and text values. `None` for `?`, `.`, lists and tables.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            CifValue::Numeric(text) | CifValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Numeric value without its standard uncertainty. Quoted numbers are accepted as well.
+    pub fn as_f64(&self) -> Option<f64> {
+        self.as_measured().map(|measured| measured.value)
+    }
+
+    /// Numeric value with its standard uncertainty. Quoted numbers are accepted as well.
+    pub fn as_measured(&self) -> Option<Measured> {
+        self.as_str()?.parse().ok()
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, CifValue::Unknown)
+    }
+
+    pub fn is_inapplicable(&self) -> bool {
+        matches!(self, CifValue::Inapplicable)
+    }
+
+    /// `?` or `.`
+    pub fn is_null(&self) -> bool {
+        self.is_unknown() || self.is_inapplicable()
+    }
+
+    pub fn as_list(&self) -> Option<&[CifValue]> {
+        match self {
+            CifValue::List(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    pub fn as_table(&self) -> Option<&IndexMap<String, CifValue>> {
+        match self {
+            CifValue::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    /// Parses a number or text value like [`str::parse`]. Numbers are parsed without their standard
+    /// uncertainty if `T` does not accept it, so both `f64` and [`Measured`] work.
+    pub fn parse<T: FromStr>(&self) -> anyhow::Result<T>
+    where
+        Result<T, <T as FromStr>::Err>: Context<T, <T as FromStr>::Err>,
+    {
+        match self {
+            CifValue::Numeric(text) => text
+                .parse::<T>()
+                .or_else(|_| strip_uncertainty(text).parse::<T>()),
+            CifValue::Text(text) => text.parse::<T>(),
+            _ => return Err(anyhow::anyhow!("Value `{}` can not be parsed", self)),
+        }
+        .context("Failed to parse value")
+    }
+
+    /// Like [`CifValue::parse`], but `?` and `.` are `None`
+    pub fn parse_optional<T: FromStr>(&self) -> anyhow::Result<Option<T>>
+    where
+        Result<T, <T as FromStr>::Err>: Context<T, <T as FromStr>::Err>,
+    {
+        match self.is_null() {
+            true => Ok(None),
+            false => self.parse().map(Some),
+        }
+    }
+}
+
+/// Removes the standard uncertainty in parentheses, keeping an exponent that follows it
+fn strip_uncertainty(text: &str) -> Cow<'_, str> {
+    match text.split_once('(') {
+        Some((value, rest)) => match rest.split_once(')') {
+            Some((_, "")) => Cow::Borrowed(value),
+            Some((_, exponent)) => Cow::Owned(format!("{}{}", value, exponent)),
+            None => Cow::Borrowed(value),
+        },
+        None => Cow::Borrowed(text),
+    }
+}
+
+/// Number as defined by the CIF grammar: an integer or float with an optional exponent and an
+/// optional standard uncertainty in parentheses, e.g. `-1.23e-5(4)`. The uncertainty is also
+/// accepted before the exponent (`-1.23(4)e-5`), which some programs write.
+pub(crate) fn is_number(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    let mut index = 0;
+
+    let count_digits = |index: &mut usize| {
+        let start = *index;
+
+        while bytes.get(*index).is_some_and(u8::is_ascii_digit) {
+            *index += 1;
+        }
+
+        *index - start
+    };
+
+    if matches!(bytes.first(), Some(b'+') | Some(b'-')) {
+        index += 1;
+    }
+
+    let mut digits = count_digits(&mut index);
+
+    if bytes.get(index) == Some(&b'.') {
+        index += 1;
+        digits += count_digits(&mut index);
+    }
+
+    if digits == 0 {
+        return false;
+    }
+
+    let exponent = |index: &mut usize| {
+        if !matches!(bytes.get(*index), Some(b'e') | Some(b'E')) {
+            return Some(false);
+        }
+
+        *index += 1;
+
+        if matches!(bytes.get(*index), Some(b'+') | Some(b'-')) {
+            *index += 1;
+        }
+
+        (count_digits(index) > 0).then_some(true)
+    };
+
+    let Some(has_exponent) = exponent(&mut index) else {
+        return false;
+    };
+
+    if bytes.get(index) == Some(&b'(') {
+        index += 1;
+
+        if count_digits(&mut index) == 0 || bytes.get(index) != Some(&b')') {
+            return false;
+        }
+
+        index += 1;
+
+        if !has_exponent && exponent(&mut index).is_none() {
+            return false;
+        }
+    }
+
+    index == bytes.len()
+}
+
+impl std::fmt::Display for CifValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CifValue::Numeric(text) | CifValue::Text(text) => write!(f, "{}", text),
+            CifValue::Unknown => write!(f, "?"),
+            CifValue::Inapplicable => write!(f, "."),
+            CifValue::List(list) => {
+                write!(f, "[")?;
+
+                for (index, value) in list.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, " ")?;
+                    }
+
+                    write_nested(f, value)?;
+                }
+
+                write!(f, "]")
+            }
+            CifValue::Table(table) => {
+                write!(f, "{{")?;
+
+                for (index, (key, value)) in table.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, " ")?;
+                    }
+
+                    write!(f, "'{}':", key)?;
+                    write_nested(f, value)?;
+                }
+
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// Text inside of lists and tables needs quotes if it would otherwise not be read back as the same value
+fn write_nested(f: &mut std::fmt::Formatter<'_>, value: &CifValue) -> std::fmt::Result {
+    match value {
+        CifValue::Text(text)
+            if text.is_empty()
+                || !matches!(CifValue::from_unquoted(text), CifValue::Text(_))
+                || text
+                    .chars()
+                    .any(|c| c.is_whitespace() || "[]{}'\"#$_;".contains(c)) =>
+        {
+            match text.contains("'''") {
+                true => write!(f, "\"\"\"{}\"\"\"", text),
+                false => write!(f, "'''{}'''", text),
+            }
+        }
+        _ => write!(f, "{}", value),
+    }
+}
+
+impl From<String> for CifValue {
+    fn from(value: String) -> Self {
+        CifValue::Text(value)
+    }
+}
+
+impl From<&str> for CifValue {
+    fn from(value: &str) -> Self {
+        CifValue::Text(value.to_string())
+    }
+}
+
+impl PartialEq<str> for CifValue {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == Some(other)
+    }
+}
+
+impl PartialEq<&str> for CifValue {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == Some(*other)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{is_number, CifValue};
+    use crate::measured::Measured;
+
+    #[test]
+    fn test_is_number() {
+        for number in [
+            "1",
+            "-0.5(3)",
+            "+.5",
+            "4.",
+            "4.0094(2)",
+            "1.23e-3(12)",
+            "1E5",
+        ] {
+            assert!(is_number(number), "{}", number);
+        }
+
+        for text in [
+            ".",
+            "-",
+            "e5",
+            "1.2.3",
+            "1(2",
+            "1()",
+            "4.0094(2)x",
+            "Ba1",
+            "1/4",
+        ] {
+            assert!(!is_number(text), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_null_values() {
+        assert_eq!(CifValue::from_unquoted("?"), CifValue::Unknown);
+        assert_eq!(CifValue::from_unquoted("."), CifValue::Inapplicable);
+        assert_eq!(CifValue::Unknown.parse_optional::<f64>().unwrap(), None);
+        assert_eq!(CifValue::Text("?".into()).as_str(), Some("?"));
+        assert!(CifValue::Text("?".into()).parse_optional::<f64>().is_err());
+        assert_eq!(CifValue::from_unquoted("0.0049(2)").as_f64(), Some(0.0049));
+        assert_eq!(
+            CifValue::from_unquoted("1.5(2)e2").parse::<f64>().unwrap(),
+            150.0
+        );
+        assert_eq!(
+            CifValue::from_unquoted("0.0049(2)")
+                .parse::<Measured>()
+                .unwrap()
+                .su,
+            Some(0.0002)
+        );
+        assert_eq!(
+            CifValue::Text("Ba (Ti O3)".into())
+                .parse::<String>()
+                .unwrap(),
+            "Ba (Ti O3)"
+        );
+    }
+}
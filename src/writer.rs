@@ -0,0 +1,528 @@
+// https://www.iucr.org/resources/cif/spec/version1.1/cifsyntax#linelength
+// Writes `Cif` and `DataBlock` as CIF 1.1 or CIF 2.0 text that reads back to the same values.
+
+use crate::{
+    lexer::{is_reserved_word, CifVersion},
+    parser::{Cif, DataBlock, Loop},
+    text_field::write_text_field,
+    value::{is_number, CifValue},
+};
+
+/// Maximum line length allowed by CIF 1.1 and CIF 2.0
+pub const MAX_LINE_LENGTH: usize = 2048;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteOptions {
+    pub version: CifVersion,
+    /// Lines are kept below this length where the values allow it. At most [`MAX_LINE_LENGTH`].
+    pub line_length: usize,
+}
+
+impl Default for WriteOptions {
+    /// CIF 1.1 with the 80 character lines recommended for compatibility with older programs
+    fn default() -> Self {
+        Self {
+            version: CifVersion::V1_1,
+            line_length: 80,
+        }
+    }
+}
+
+impl WriteOptions {
+    pub fn cif2() -> Self {
+        Self {
+            version: CifVersion::V2_0,
+            ..Self::default()
+        }
+    }
+
+    fn line_length(&self) -> usize {
+        self.line_length.min(MAX_LINE_LENGTH)
+    }
+}
+
+/// Value ready to be written
+pub(crate) enum Formatted {
+    /// Fits on the current line, with delimiters
+    Inline(String),
+    /// Semicolon delimited text field, which has to start on its own line
+    TextField(String),
+}
+
+pub fn write_cif(cif: &Cif) -> String {
+    let mut text = String::new();
+
+    write_cif_to(&mut text, cif, &WriteOptions::default())
+        .expect("writing to a String never fails");
+
+    text
+}
+
+pub(crate) fn write_cif_to(
+    out: &mut impl std::fmt::Write,
+    cif: &Cif,
+    options: &WriteOptions,
+) -> std::fmt::Result {
+    write_header(out, options)?;
+
+    for (name, data_block) in cif.iter() {
+        writeln!(out)?;
+        write_data_block_to(out, name, data_block, options)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn write_header(
+    out: &mut impl std::fmt::Write,
+    options: &WriteOptions,
+) -> std::fmt::Result {
+    match options.version {
+        CifVersion::V1_1 => writeln!(out, "#\\#CIF_1.1"),
+        CifVersion::V2_0 => writeln!(out, "#\\#CIF_2.0"),
+    }
+}
+
+/// Writes `data_<name>` and the items and loops in the order of the data block, followed by its
+/// save frames
+pub(crate) fn write_data_block_to(
+    out: &mut impl std::fmt::Write,
+    name: &str,
+    data_block: &DataBlock,
+    options: &WriteOptions,
+) -> std::fmt::Result {
+    writeln!(out, "data_{}", name)?;
+
+    write_items(out, data_block, options)?;
+
+    for (name, save_frame) in data_block.save_frames() {
+        writeln!(out, "\nsave_{}", name)?;
+        write_items(out, save_frame, options)?;
+        writeln!(out, "save_")?;
+    }
+
+    Ok(())
+}
+
+fn write_items(
+    out: &mut impl std::fmt::Write,
+    data_block: &DataBlock,
+    options: &WriteOptions,
+) -> std::fmt::Result {
+    let tag_width = data_block
+        .keys()
+        .filter(|name| !data_block.is_looped(name))
+        .map(|name| name.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    let mut written_loops = Vec::new();
+    let mut after_loop = false;
+
+    for (name, values) in data_block.iter() {
+        if let Some(loop_) = data_block.find_loop(name) {
+            if written_loops.contains(&loop_.names()) {
+                continue;
+            }
+
+            written_loops.push(loop_.names());
+
+            writeln!(out)?;
+            write_loop(out, loop_, options)?;
+            after_loop = true;
+
+            continue;
+        }
+
+        if after_loop {
+            writeln!(out)?;
+            after_loop = false;
+        }
+
+        let value = values.first().unwrap_or(&CifValue::Unknown);
+
+        match format_value(value, options) {
+            Formatted::Inline(value)
+                if tag_width + 1 + value.chars().count() <= options.line_length() =>
+            {
+                writeln!(out, "{:<width$} {}", name, value, width = tag_width)?
+            }
+            Formatted::Inline(value) => writeln!(out, "{}\n{}", name, value)?,
+            Formatted::TextField(value) => writeln!(out, "{}\n{}", name, value)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the loop header and the rows with aligned columns, wrapping rows that are too long
+fn write_loop(
+    out: &mut impl std::fmt::Write,
+    loop_: Loop<'_>,
+    options: &WriteOptions,
+) -> std::fmt::Result {
+    writeln!(out, "loop_")?;
+
+    for name in loop_.names() {
+        writeln!(out, "{}", name)?;
+    }
+
+    let rows = loop_
+        .rows()
+        .map(|row| {
+            row.values()
+                .map(|value| format_value(value.unwrap_or(&CifValue::Unknown), options))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut widths = vec![0; loop_.names().len()];
+
+    for row in &rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            if let Formatted::Inline(value) = value {
+                *width = (*width).max(value.chars().count());
+            }
+        }
+    }
+
+    for row in rows {
+        let mut line = String::new();
+
+        for (index, value) in row.into_iter().enumerate() {
+            match value {
+                Formatted::Inline(value) => {
+                    let padded = match index + 1 == widths.len() {
+                        true => value,
+                        false => format!("{:<width$}", value, width = widths[index]),
+                    };
+
+                    if !line.is_empty()
+                        && line.chars().count() + 1 + padded.trim_end().chars().count()
+                            > options.line_length()
+                    {
+                        writeln!(out, "{}", line.trim_end())?;
+                        line.clear();
+                    }
+
+                    if !line.is_empty() {
+                        line.push(' ');
+                    }
+
+                    line.push_str(&padded);
+                }
+                Formatted::TextField(value) => {
+                    if !line.trim_end().is_empty() {
+                        writeln!(out, "{}", line.trim_end())?;
+                    }
+
+                    writeln!(out, "{}", value)?;
+                    line.clear();
+                }
+            }
+        }
+
+        if !line.trim_end().is_empty() {
+            writeln!(out, "{}", line.trim_end())?;
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn format_value(value: &CifValue, options: &WriteOptions) -> Formatted {
+    match value {
+        CifValue::Numeric(text) => Formatted::Inline(text.clone()),
+        CifValue::Unknown => Formatted::Inline("?".to_string()),
+        CifValue::Inapplicable => Formatted::Inline(".".to_string()),
+        CifValue::Text(text) => format_text(text, options),
+        CifValue::List(_) | CifValue::Table(_) if options.version == CifVersion::V2_0 => {
+            Formatted::Inline(format_nested(value))
+        }
+        CifValue::List(_) | CifValue::Table(_) => {
+            log::warn!(
+                "CIF 1.1 has no lists and tables, `{}` is written as text",
+                value
+            );
+
+            format_text(&value.to_string(), options)
+        }
+    }
+}
+
+fn format_text(text: &str, options: &WriteOptions) -> Formatted {
+    let cif2 = options.version == CifVersion::V2_0;
+
+    if text.contains(['\n', '\r']) || text.chars().count() + 2 > options.line_length() {
+        return Formatted::TextField(write_text_field(text, options.line_length()));
+    }
+
+    if can_be_unquoted(text, cif2) {
+        return Formatted::Inline(text.to_string());
+    }
+
+    if let Some(quote) = ['\'', '"'].into_iter().find(|quote| !text.contains(*quote)) {
+        return Formatted::Inline(format!("{}{}{}", quote, text, quote));
+    }
+
+    // in CIF 1.1 a quote only ends the value if whitespace follows it
+    if !cif2 {
+        let closes = |quote: char| {
+            text.char_indices()
+                .any(|(index, c)| c == quote && text[index + 1..].starts_with(char::is_whitespace))
+        };
+
+        if let Some(quote) = ['\'', '"'].into_iter().find(|quote| !closes(*quote)) {
+            return Formatted::Inline(format!("{}{}{}", quote, text, quote));
+        }
+    }
+
+    match cif2 {
+        true => Formatted::Inline(triple_quoted(text)),
+        false => Formatted::TextField(write_text_field(text, options.line_length())),
+    }
+}
+
+fn can_be_unquoted(text: &str, cif2: bool) -> bool {
+    !text.is_empty()
+        && text != "?"
+        && text != "."
+        && !is_number(text)
+        && !is_reserved_word(text.as_bytes())
+        && !text.starts_with(['_', '#', '$', '\'', '"', ';', '[', ']'])
+        && !text.contains(char::is_whitespace)
+        && !(cif2 && text.contains(['[', ']', '{', '}']))
+}
+
+fn triple_quoted(text: &str) -> String {
+    match text.contains("'''") || text.ends_with('\'') {
+        true => format!("\"\"\"{}\"\"\"", text),
+        false => format!("'''{}'''", text),
+    }
+}
+
+/// CIF 2.0 list or table, with every text quoted
+fn format_nested(value: &CifValue) -> String {
+    match value {
+        CifValue::List(list) => format!(
+            "[{}]",
+            list.iter().map(format_nested).collect::<Vec<_>>().join(" ")
+        ),
+        CifValue::Table(table) => format!(
+            "{{{}}}",
+            table
+                .iter()
+                .map(|(key, value)| format!("{}:{}", triple_quoted(key), format_nested(value)))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ),
+        CifValue::Text(text) => triple_quoted(text),
+        _ => value.to_string(),
+    }
+}
+
+impl std::fmt::Display for Cif {
+    /// CIF 1.1 text with the default [`WriteOptions`]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_cif_to(f, self, &WriteOptions::default())
+    }
+}
+
+impl Cif {
+    /// Writes one data block at a time to `writer`
+    pub fn write<W: std::io::Write>(
+        &self,
+        mut writer: W,
+        options: &WriteOptions,
+    ) -> std::io::Result<()> {
+        let mut text = String::new();
+
+        write_header(&mut text, options).expect("writing to a String never fails");
+        writer.write_all(text.as_bytes())?;
+
+        for (name, data_block) in self.iter() {
+            writer.write_all(b"\n")?;
+            data_block.write(name, &mut writer, options)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_string_with(&self, options: &WriteOptions) -> String {
+        let mut text = String::new();
+
+        write_cif_to(&mut text, self, options).expect("writing to a String never fails");
+
+        text
+    }
+}
+
+impl DataBlock {
+    /// Writes the data block as `data_<name>` without the `#\#CIF_` header of a file
+    pub fn write<W: std::io::Write>(
+        &self,
+        name: &str,
+        mut writer: W,
+        options: &WriteOptions,
+    ) -> std::io::Result<()> {
+        writer.write_all(self.to_string_with(name, options).as_bytes())
+    }
+
+    pub fn to_string_with(&self, name: &str, options: &WriteOptions) -> String {
+        let mut text = String::new();
+
+        write_data_block_to(&mut text, name, self, options)
+            .expect("writing to a String never fails");
+
+        text
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{write_cif, WriteOptions};
+    use crate::{parser::read_cif, try_read_cif, value::CifValue, CifVersion, DataBlock};
+
+    fn assert_round_trip(data_block: &DataBlock, options: &WriteOptions) {
+        let text = data_block.to_string_with("test", options);
+
+        let header = match options.version {
+            CifVersion::V1_1 => "",
+            CifVersion::V2_0 => "#\\#CIF_2.0\n",
+        };
+
+        let cif = try_read_cif(format!("{}{}", header, text).as_bytes()).unwrap();
+
+        assert_eq!(**cif.get("test").unwrap(), **data_block, "{}", text);
+
+        for line in text.lines() {
+            assert!(line.chars().count() <= options.line_length, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_quoting() {
+        let mut data_block = DataBlock::default();
+
+        for (name, value) in [
+            ("_plain", "Ba1"),
+            ("_space", "P m -3 m"),
+            ("_quote", "O'Brien"),
+            ("_quote_space", "it's here"),
+            ("_both", "it's \"quoted\""),
+            ("_number_text", "1.5"),
+            ("_question", "?"),
+            ("_reserved", "data_x"),
+            ("_underscore", "_a"),
+            ("_empty", ""),
+        ] {
+            data_block.insert_item(name, value);
+        }
+
+        data_block.insert_item("_numeric", CifValue::Numeric("4.0094(2)".into()));
+        data_block.insert_item("_unknown", CifValue::Unknown);
+        data_block.insert_item("_long", "x ".repeat(60).trim_end());
+        data_block.insert_item("_res_file", "TITL x\n;comment\nEND\n");
+
+        assert_round_trip(&data_block, &WriteOptions::default());
+
+        let text = data_block.to_string_with("test", &WriteOptions::default());
+
+        assert!(text.contains("_plain       Ba1\n"), "{}", text);
+        assert!(text.contains("_quote       O'Brien\n"), "{}", text);
+        assert!(text.contains("_quote_space \"it's here\"\n"), "{}", text);
+        assert!(
+            text.contains("_both        'it's \"quoted\"'\n"),
+            "{}",
+            text
+        );
+        assert!(text.contains("_numeric     4.0094(2)\n"), "{}", text);
+
+        data_block.insert_item("_multi_line", "first\nsecond");
+
+        let text = data_block.to_string_with("test", &WriteOptions::default());
+
+        assert!(
+            text.contains("_multi_line\n;\nfirst\nsecond\n;\n"),
+            "{}",
+            text
+        );
+    }
+
+    #[test]
+    fn test_loops() {
+        let mut data_block = DataBlock::default();
+
+        data_block.insert_item("_cell_length_a", CifValue::Numeric("4.0094(2)".into()));
+        data_block
+            .insert_loop(
+                vec!["_atom_site_label".into(), "_atom_site_fract_x".into()],
+                vec![
+                    vec!["Ba1".into(), CifValue::Numeric("0".into())],
+                    vec!["O1".into(), CifValue::Numeric("0.5".into())],
+                    vec!["a b".into(), CifValue::Inapplicable],
+                ],
+            )
+            .unwrap();
+        data_block
+            .insert_loop(
+                vec!["_w".into(), "_x".into()],
+                vec![vec![
+                    "w".repeat(50).as_str().into(),
+                    "x".repeat(50).as_str().into(),
+                ]],
+            )
+            .unwrap();
+        data_block.insert_item("_after", "x");
+
+        assert_round_trip(&data_block, &WriteOptions::default());
+
+        let text = data_block.to_string_with("test", &WriteOptions::default());
+
+        assert!(
+            text.contains(
+                "loop_\n_atom_site_label\n_atom_site_fract_x\nBa1   0\nO1    0.5\n'a b' .\n"
+            ),
+            "{}",
+            text
+        );
+        assert!(
+            text.contains(&format!("\n{}\n{}\n", "w".repeat(50), "x".repeat(50))),
+            "{}",
+            text
+        );
+    }
+
+    #[test]
+    fn test_cif2() {
+        let cif = read_cif(
+            b"#\\#CIF_2.0\ndata_test\n_list [1 'a b' [x]]\n_table {'k':v \"l\":[]}\n_bracket 'a[1]'\n",
+        );
+
+        let data_block = cif.get("test").unwrap();
+
+        assert_round_trip(data_block, &WriteOptions::cif2());
+
+        let text = write_cif(&cif);
+
+        assert!(text.starts_with("#\\#CIF_1.1\n\ndata_test\n"), "{}", text);
+    }
+
+    #[test]
+    fn test_save_frames() {
+        let cif = read_cif(b"data_dic\n_title x\nsave_a\n_name a\nloop_\n_b\n1 2\nsave_\n");
+
+        let text = write_cif(&cif);
+
+        assert!(
+            text.ends_with("_title x\n\nsave_a\n_name a\n\nloop_\n_b\n1\n2\nsave_\n"),
+            "{}",
+            text
+        );
+
+        let written = try_read_cif(text.as_bytes()).unwrap();
+        let save_frame = written.get("dic").unwrap().save_frame("a").unwrap();
+
+        assert_eq!(save_frame["_b"], vec!["1", "2"]);
+    }
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "derive")]
+
+use cif::{Cif, FromDataBlock, FromLoop, Measured};
+
+#[derive(Debug, PartialEq, FromDataBlock)]
+struct CellParameters {
+    #[cif(tag = "_cell_length_a")]
+    a: f64,
+    #[cif(tag = "_cell_length_a", uncertainty)]
+    a_su: Option<f64>,
+    #[cif(tag = "_cell_angle_alpha")]
+    alpha: Measured,
+    #[cif(
+        tag = "_space_group_name_H-M_alt",
+        alias = "_symmetry_space_group_name_H-M"
+    )]
+    space_group: String,
+    #[cif(tag = "_space_group_IT_number", alias = "_symmetry_Int_Tables_number")]
+    space_group_number: u8,
+    #[cif(tag = "_cell_measurement_temperature", default = "default_temperature")]
+    temperature: f64,
+    #[cif(tag = "_exptl_crystal_density_diffrn", optional)]
+    density: Option<f64>,
+    cell_formula_units_z: u32,
+    #[cif(tag = "_diffrn_radiation_wavelength", default = 1.5406)]
+    wavelength: f64,
+}
+
+fn default_temperature() -> f64 {
+    293.0
+}
+
+#[derive(Debug, PartialEq, FromLoop)]
+struct AtomSite {
+    #[cif(tag = "_atom_site_label")]
+    label: String,
+    #[cif(tag = "_atom_site_U_iso_or_equiv")]
+    u_iso: f64,
+    #[cif(tag = "_atom_site_U_iso_or_equiv", uncertainty)]
+    u_iso_su: Option<f64>,
+    #[cif(tag = "_atom_site_occupancy", default)]
+    occupancy: f64,
+    #[cif(tag = "_atom_site_B_iso_or_equiv")]
+    b_iso: Option<f64>,
+}
+
+#[derive(Debug, PartialEq, FromLoop)]
+struct AnisoSite {
+    #[cif(tag = "_atom_site_label")]
+    label: String,
+    #[cif(tag = "_atom_site_aniso_U_11")]
+    u11: f64,
+}
+
+#[test]
+fn test_from_data_block() {
+    let bytes = std::fs::read(r"assets\BaTiO3.cif").unwrap();
+
+    let cif = Cif::from_bytes(&bytes);
+    let data_block = cif.first_key_value().unwrap().1;
+
+    let cell = data_block.extract::<CellParameters>().unwrap();
+
+    assert_eq!(
+        cell,
+        CellParameters {
+            a: 4.0094,
+            a_su: Some(0.0002),
+            alpha: Measured::new(90.0, None),
+            space_group: "P m -3 m".to_string(),
+            space_group_number: 221,
+            temperature: 293.0,
+            density: None,
+            cell_formula_units_z: 1,
+            wavelength: 1.5406,
+        }
+    );
+
+    let atoms = AtomSite::from_loop(data_block).unwrap();
+
+    assert_eq!(atoms.len(), 3);
+    assert_eq!(atoms[2].label, "O1");
+    assert_eq!(atoms[2].u_iso, 0.005);
+    assert_eq!(atoms[2].u_iso_su, Some(0.001));
+    assert_eq!(atoms[2].occupancy, 1.0);
+    assert_eq!(atoms[2].b_iso, None);
+}
+
+#[test]
+fn test_missing_data_name() {
+    let cif = Cif::from_bytes(b"data_x\n_cell_length_a 1\n");
+
+    let error = CellParameters::from_data_block(cif.get("x").unwrap()).unwrap_err();
+
+    assert_eq!(error.to_string(), "Data name `_cell_angle_alpha` not found");
+}
+
+#[test]
+fn test_columns_of_different_loops() {
+    let cif = Cif::from_bytes(
+        b"data_x
+loop_
+_atom_site_label
+_atom_site_fract_x
+O1 0.1
+O2 0.2
+loop_
+_atom_site_aniso_label
+_atom_site_aniso_U_11
+O2 0.01
+",
+    );
+
+    let error = AnisoSite::from_loop(cif.get("x").unwrap()).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Data names `_atom_site_label` and `_atom_site_aniso_U_11` are not in the same loop"
+    );
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "serde")]
+
+use cif::Measured;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+struct Structure {
+    cell_length_a: Measured,
+    cell_angle_alpha: f64,
+    #[serde(rename = "symmetry_space_group_name_H-M")]
+    space_group: String,
+    #[serde(rename = "_symmetry_Int_Tables_number")]
+    space_group_number: u16,
+    cell_measurement_temperature: Option<f64>,
+    atom_site: Vec<AtomSite>,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+struct AtomSite {
+    label: String,
+    type_symbol: String,
+    fract_x: f64,
+    fract_y: f64,
+    fract_z: f64,
+    #[serde(rename = "U_iso_or_equiv")]
+    u_iso: Measured,
+}
+
+#[test]
+fn test_from_str() {
+    let text = std::fs::read_to_string(r"assets\BaTiO3.cif").unwrap();
+
+    let structure: Structure = cif::from_str(&text).unwrap();
+
+    assert_eq!(structure.cell_length_a, Measured::new(4.0094, Some(0.0002)));
+    assert_eq!(structure.cell_angle_alpha, 90.0);
+    assert_eq!(structure.space_group, "P m -3 m");
+    assert_eq!(structure.space_group_number, 221);
+    assert_eq!(structure.cell_measurement_temperature, None);
+
+    assert_eq!(structure.atom_site.len(), 3);
+    assert_eq!(structure.atom_site[1].label, "Ti1");
+    assert_eq!(structure.atom_site[1].fract_x, 0.5);
+    assert_eq!(
+        structure.atom_site[2].u_iso,
+        Measured::new(0.005, Some(0.001))
+    );
+}
+
+#[test]
+fn test_unknown_and_errors() {
+    #[derive(Debug, Deserialize)]
+    struct Item {
+        a: Option<f64>,
+        b: Option<String>,
+        c: f64,
+    }
+
+    let item: Item = cif::from_str("data_x\n_a ?\n_b .\n_c 1.5(3)\n").unwrap();
+
+    assert_eq!((item.a, item.b, item.c), (None, None, 1.5));
+
+    assert!(cif::from_str::<Item>("data_x\n_a 1\n").is_err());
+    assert!(cif::from_str::<Item>("data_x\n_a 1\n_c text\n").is_err());
+}
+
+#[test]
+fn test_round_trip() {
+    let text = std::fs::read_to_string(r"assets\BaTiO3.cif").unwrap();
+
+    let structure: Structure = cif::from_str(&text).unwrap();
+
+    let written = cif::to_string(&structure).unwrap();
+
+    assert!(
+        written.starts_with("#\\#CIF_1.1\ndata_Structure\n"),
+        "{}",
+        written
+    );
+    assert!(written.contains("_cell_length_a"), "{}", written);
+    assert!(written.contains("4.0094(2)"), "{}", written);
+    assert!(written.contains("0.0050(10)"), "{}", written);
+    assert!(written.contains("_atom_site_U_iso_or_equiv"), "{}", written);
+
+    assert_eq!(cif::from_str::<Structure>(&written).unwrap(), structure);
+}
@@ -1,4 +1,4 @@
-use cif::Parser;
+use cif::{Cif, Parser};
 use crystallib::{AdpType, Atom, Atoms, Cell, Phase};
 
 #[test]
@@ -78,3 +78,123 @@ fn test() {
 
     assert_eq!(phase, expected_phase);
 }
+
+#[test]
+fn test_inapplicable_b_iso() {
+    let bytes = std::fs::read(r"assets\diamond.cif").unwrap();
+
+    let data = Parser::new(&bytes).parse();
+
+    let phase: Phase = data.first_key_value().unwrap().1.try_into().unwrap();
+
+    assert_eq!(phase.atoms.len(), 1);
+    assert_eq!(phase.atoms[0].u_iso_or_equiv, 0.0);
+    assert_eq!(phase.atoms[0].occupancy, 1.0);
+    assert_eq!(phase.atoms[0].multiplicity, Some(8.0));
+}
+
+#[test]
+fn test_phase_uncertainties() {
+    let bytes = std::fs::read(r"assets\BaTiO3.cif").unwrap();
+
+    let data = Parser::new(&bytes).parse();
+
+    let (phase, uncertainties) = data
+        .first_key_value()
+        .unwrap()
+        .1
+        .try_into_phase_with_uncertainties()
+        .unwrap();
+
+    assert_eq!(phase.cell.a, 4.0094);
+    assert_eq!(uncertainties.cell.a, Some(0.0002));
+    assert_eq!(uncertainties.cell.alpha, None);
+
+    assert_eq!(uncertainties.atoms.len(), phase.atoms.len());
+    assert_eq!(phase.atoms[2].u_iso_or_equiv, 0.005);
+    assert_eq!(uncertainties.atoms[2].u_iso_or_equiv, Some(0.001));
+    assert_eq!(uncertainties.atoms[2].x, None);
+}
+
+#[test]
+fn test_from_path() {
+    let cif = Cif::from_path(r"assets\BaTiO3.cif").unwrap();
+
+    let bytes = std::fs::read(r"assets\BaTiO3.cif").unwrap();
+
+    let expected = Cif::from_bytes(&bytes);
+
+    assert_eq!(cif.len(), expected.len());
+
+    for ((name, data_block), (expected_name, expected_data_block)) in
+        cif.iter().zip(expected.iter())
+    {
+        assert_eq!(name, expected_name);
+        assert_eq!(**data_block, **expected_data_block);
+    }
+}
+
+#[test]
+fn test_write_round_trip() {
+    let bytes = std::fs::read(r"assets\BaTiO3.cif").unwrap();
+
+    let cif = Cif::from_bytes(&bytes);
+
+    let written = Cif::try_from_bytes(cif.to_string().as_bytes()).unwrap();
+
+    for ((name, data_block), (written_name, written_data_block)) in cif.iter().zip(written.iter()) {
+        assert_eq!(name, written_name);
+        assert_eq!(**data_block, **written_data_block);
+    }
+}
+
+#[test]
+fn test_case_insensitive_phase() {
+    let bytes = std::fs::read(r"assets\BaTiO3.cif").unwrap();
+
+    let expected: Phase = Cif::from_bytes(&bytes)
+        .first_key_value()
+        .unwrap()
+        .1
+        .try_into()
+        .unwrap();
+
+    let text = String::from_utf8(bytes)
+        .unwrap()
+        .replace("_cell_length_a", "_Cell_Length_A")
+        .replace("_cell_", "_CELL_")
+        .replace("_atom_site_fract_x", "_Atom_Site_Fract_X")
+        .replace("_symmetry_Int_Tables_number", "_symmetry_int_tables_number");
+
+    let cif = Cif::try_from_bytes(text.as_bytes()).unwrap();
+
+    let phase: Phase = cif.first_key_value().unwrap().1.try_into().unwrap();
+
+    assert_eq!(phase, expected);
+}
+
+#[cfg(feature = "symmetry")]
+#[test]
+fn test_computed_multiplicity() {
+    let bytes = std::fs::read(r"assets\BaTiO3.cif").unwrap();
+
+    let expected: Phase = Cif::from_bytes(&bytes)
+        .first_key_value()
+        .unwrap()
+        .1
+        .try_into()
+        .unwrap();
+
+    let text = String::from_utf8(bytes)
+        .unwrap()
+        .replace("_atom_site_symmetry_multiplicity\n", "")
+        .replace("Ba1 Ba 1 a", "Ba1 Ba a")
+        .replace("Ti1 Ti 1 b", "Ti1 Ti b")
+        .replace("O1 O 3 c", "O1 O c");
+
+    let cif = Cif::try_from_bytes(text.as_bytes()).unwrap();
+
+    let phase: Phase = cif.first_key_value().unwrap().1.try_into().unwrap();
+
+    assert_eq!(phase, expected);
+}